@@ -1,11 +1,33 @@
 use anyhow::{Context, Result};
 
 use crate::config;
+use crate::error::RegistryError;
+use crate::search_cache;
+use crate::ui;
 use crate::types::*;
+use crate::utils::{self, RangeResume};
+
+const CATEGORIES_CACHE_TTL_SECS: i64 = 3600; // 1 hour
+const PRODUCTS_PAGE_SIZE: u32 = 100;
+/// Refresh a pre-signed upload URL this close to its expiry instead of starting with it.
+const UPLOAD_URL_MARGIN: std::time::Duration = std::time::Duration::from_secs(30);
+/// New upload URLs to request after a 403 before giving up.
+const UPLOAD_URL_REFRESHES: u32 = 2;
+const MAX_PRODUCT_PAGES: u32 = 500;
+/// Tries per download; each retry resumes where the last one stopped.
+const DOWNLOAD_ATTEMPTS: u32 = 4;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CachedCategories {
+    categories: Vec<Category>,
+    fetched_at: i64,
+}
 
 pub struct BaroClient {
     client: reqwest::Client,
     token: Option<String>,
+    /// Registry to talk to instead of the configured one
+    base_url: Option<String>,
 }
 
 impl BaroClient {
@@ -13,6 +35,16 @@ impl BaroClient {
         Self {
             client: reqwest::Client::new(),
             token: Some(token.to_string()),
+            base_url: None,
+        }
+    }
+
+    /// A client for a different registry than `BARO_API_URL` (e.g. `baro restore --registry`).
+    pub fn with_registry(token: &str, base_url: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            token: Some(token.to_string()),
+            base_url: Some(base_url.trim_end_matches('/').to_string()),
         }
     }
 
@@ -20,53 +52,69 @@ impl BaroClient {
         Self {
             client: reqwest::Client::new(),
             token: None,
+            base_url: None,
         }
     }
 
     fn base_url(&self) -> String {
-        config::api_base_url()
+        self.base_url.clone().unwrap_or_else(config::api_base_url)
     }
 
-    async fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+    async fn send_json<T: serde::de::DeserializeOwned>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+        headers: &[(&str, String)],
+    ) -> Result<T> {
         let url = format!("{}{}", self.base_url(), path);
-        let mut req = self.client.get(&url)
+        let mut req = self.client.request(method.clone(), &url)
             .header("X-Baro-CLI-Version", env!("CARGO_PKG_VERSION"));
+        for (name, value) in headers {
+            req = req.header(*name, value);
+        }
+        if let Some(body) = body {
+            req = req.json(body);
+        }
         if let Some(ref token) = self.token {
             req = req.bearer_auth(token);
         }
-        let resp = req.send().await.context(format!("Failed to connect: GET {}", path))?;
+        let resp = req.send().await.context(format!("Failed to connect: {} {}", method, path))?;
         if !resp.status().is_success() {
-            let status = resp.status();
-            let body: ApiError = resp.json().await.unwrap_or(ApiError {
-                error: format!("HTTP {}", status),
-            });
-            return Err(anyhow::anyhow!("{}", body.error));
+            let mut err = RegistryError::from_response(&resp, format!("HTTP {}", resp.status()));
+            if let Ok(body) = resp.json::<ApiError>().await {
+                err.message = body.error;
+                err.code = body.code;
+                err.hint = body.hint;
+            }
+            return Err(err.into());
         }
         let data = resp.json().await.context("Failed to parse response")?;
         Ok(data)
     }
 
+    async fn delete_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send_json(reqwest::Method::DELETE, path, None, &[]).await
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send_json(reqwest::Method::GET, path, None, &[]).await
+    }
+
     async fn post_json<T: serde::de::DeserializeOwned>(
         &self,
         path: &str,
         body: &serde_json::Value,
     ) -> Result<T> {
-        let url = format!("{}{}", self.base_url(), path);
-        let mut req = self.client.post(&url).json(body)
-            .header("X-Baro-CLI-Version", env!("CARGO_PKG_VERSION"));
-        if let Some(ref token) = self.token {
-            req = req.bearer_auth(token);
-        }
-        let resp = req.send().await.context(format!("Failed to connect: POST {}", path))?;
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let body: ApiError = resp.json().await.unwrap_or(ApiError {
-                error: format!("HTTP {}", status),
-            });
-            return Err(anyhow::anyhow!("{}", body.error));
-        }
-        let data = resp.json().await.context("Failed to parse response")?;
-        Ok(data)
+        self.send_json(reqwest::Method::POST, path, Some(body), &[]).await
+    }
+
+    async fn patch_json<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> Result<T> {
+        self.send_json(reqwest::Method::PATCH, path, Some(body), &[]).await
     }
 
     // -- Auth --
@@ -75,32 +123,70 @@ impl BaroClient {
         self.get_json("/api/auth/me").await
     }
 
+    // -- Signing keys --
+
+    pub async fn register_signing_key(&self, public_key: &str) -> Result<serde_json::Value> {
+        self.post_json(
+            "/api/auth/me/signing-keys",
+            &serde_json::json!({ "public_key": public_key, "algorithm": "ed25519" }),
+        )
+        .await
+    }
+
+    pub async fn list_signing_keys(&self, username: &str) -> Result<SigningKeysResponse> {
+        self.get_json(&format!("/api/users/{}/signing-keys", username)).await
+    }
+
+    // -- Teams --
+
+    pub async fn list_my_teams(&self) -> Result<TeamsResponse> {
+        self.get_json("/api/teams/me").await
+    }
+
     // -- Products --
 
     pub async fn list_products(
         &self,
         q: Option<&str>,
-        category: Option<&str>,
+        filters: &ProductFilters<'_>,
         sort: &str,
         limit: u32,
         page: u32,
+        explain: bool,
     ) -> Result<ProductsResponse> {
-        let mut params = vec![
-            format!("sort={}", sort),
-            format!("limit={}", limit),
-            format!("page={}", page),
-        ];
-        if let Some(q) = q {
-            params.push(format!(
-                "q={}",
-                urlencoded(q)
-            ));
+        self.get_json(&products_path(q, filters, sort, limit, page, explain)).await
+    }
+
+    /// A `products_path` search through the on-disk cache: answered from disk
+    /// when repeated within a few minutes, or at any age when `offline` or the
+    /// registry can't be reached. Cached answers come with their age in seconds.
+    pub async fn list_products_cached(&self, path: &str, offline: bool) -> Result<(ProductsResponse, Option<i64>)> {
+        // One cache serves every registry
+        let key = format!("{}{}", config::api_base_url(), path);
+        let cached = search_cache::get(&key);
+        let from_cache = |entry: search_cache::SearchEntry| -> Result<(ProductsResponse, Option<i64>)> {
+            let age = entry.age_secs();
+            Ok((serde_json::from_value(entry.response).context("Invalid cached search")?, Some(age)))
+        };
+        match cached {
+            Some(entry) if offline || entry.age_secs() < search_cache::FRESH_SECS => return from_cache(entry),
+            None if offline => {
+                return Err(anyhow::anyhow!("This search isn't cached yet; run it once without --offline"));
+            }
+            _ => {}
         }
-        if let Some(cat) = category {
-            params.push(format!("category={}", cat));
+        match self.get_json::<serde_json::Value>(path).await {
+            Ok(response) => {
+                search_cache::record(&key, &response);
+                Ok((serde_json::from_value(response)?, None))
+            }
+            // No answer from the registry at all: stale results beat none
+            Err(e) if crate::error::status(&e).is_none() => match search_cache::get(&key) {
+                Some(entry) => from_cache(entry),
+                None => Err(e),
+            },
+            Err(e) => Err(e),
         }
-        let path = format!("/api/products?{}", params.join("&"));
-        self.get_json(&path).await
     }
 
     pub async fn get_product(&self, username: &str, slug: &str) -> Result<Product> {
@@ -115,25 +201,96 @@ impl BaroClient {
     }
 
     pub async fn list_my_products(&self) -> Result<MyProductsResponse> {
-        self.get_json("/api/products/me").await
+        self.get_all_products("/api/products/me").await
+    }
+
+    /// Fetch every page of an owned-products listing. Slug existence checks rely
+    /// on seeing all products, so a truncated first page must not be mistaken
+    /// for the full list.
+    async fn get_all_products(&self, path: &str) -> Result<MyProductsResponse> {
+        let mut products: Vec<Product> = Vec::new();
+        let mut total = None;
+        for page in 1..=MAX_PRODUCT_PAGES {
+            let resp: MyProductsResponse = self
+                .get_json(&format!("{}?page={}&limit={}", path, page, PRODUCTS_PAGE_SIZE))
+                .await?;
+            total = resp.total.or(total);
+            if absorb_products_page(&mut products, resp.products, total) {
+                return Ok(MyProductsResponse { products, total });
+            }
+        }
+        Err(anyhow::anyhow!(
+            "Product list did not finish after {} pages; refusing to guess whether a slug exists",
+            MAX_PRODUCT_PAGES
+        ))
+    }
+
+    pub async fn check_slug(&self, slug: &str) -> Result<SlugAvailability> {
+        self.get_json(&format!("/api/slugs/{}", slug)).await
     }
 
     pub async fn create_product(
         &self,
-        slug: &str,
-        name: &str,
-        description: &str,
-        category_slug: &str,
-        license: &str,
+        req: &CreateProductRequest<'_>,
     ) -> Result<CreateProductResponse> {
-        let body = serde_json::json!({
-            "slug": slug,
-            "name": name,
-            "description": description,
-            "category_slug": category_slug,
-            "license": license,
-        });
-        self.post_json("/api/products", &body).await
+        self.post_json("/api/products", &serde_json::to_value(req)?).await
+    }
+
+    /// Products owned by `team`, or by the caller when `team` is None.
+    pub async fn list_owned_products(&self, team: Option<&str>) -> Result<MyProductsResponse> {
+        match team {
+            Some(team) => self.get_all_products(&format!("/api/teams/{}/products", team)).await,
+            None => self.list_my_products().await,
+        }
+    }
+
+    pub async fn update_product(&self, product_id: &str, req: &UpdateProductRequest) -> Result<Product> {
+        #[derive(serde::Deserialize)]
+        struct Resp {
+            product: Product,
+        }
+        let resp: Resp = self
+            .patch_json(&format!("/api/products/{}", product_id), &serde_json::to_value(req)?)
+            .await?;
+        Ok(resp.product)
+    }
+
+    pub async fn set_visibility(
+        &self,
+        username: &str,
+        slug: &str,
+        visibility: &str,
+    ) -> Result<Product> {
+        #[derive(serde::Deserialize)]
+        struct Resp {
+            product: Product,
+        }
+        let resp: Resp = self
+            .patch_json(
+                &format!("/api/products/{}/{}", username, slug),
+                &serde_json::json!({
+                    "visibility": visibility,
+                    "is_private": visibility == "private",
+                }),
+            )
+            .await?;
+        Ok(resp.product)
+    }
+
+    /// Mark a product deprecated with `message`, or lift it with None. Releases
+    /// stay forkable; consumers are warned.
+    pub async fn set_deprecation(&self, username: &str, slug: &str, message: Option<&str>) -> Result<Product> {
+        #[derive(serde::Deserialize)]
+        struct Resp {
+            product: Product,
+        }
+        let resp: Resp = self
+            .patch_json(
+                &format!("/api/products/{}/{}", username, slug),
+                &serde_json::json!({ "deprecation_message": message }),
+            )
+            .await?;
+        Ok(resp.product)
     }
 
     // -- Releases --
@@ -146,28 +303,21 @@ impl BaroClient {
         .await
     }
 
+    /// Every release of one of your products, including unfinished uploads and
+    /// releases awaiting or failing review, with their upload and review status.
+    pub async fn list_own_releases(&self, username: &str, slug: &str) -> Result<ReleasesResponse> {
+        self.get_json(&format!("/api/products/{}/{}/releases?include=all", username, slug)).await
+    }
+
     pub async fn create_release(
         &self,
         username: &str,
         slug: &str,
-        version: &str,
-        changelog: &str,
-        file_size_bytes: i64,
-        file_hash_sha256: &str,
-        readme: Option<&str>,
+        req: &CreateReleaseRequest<'_>,
     ) -> Result<CreateReleaseResponse> {
-        let mut body = serde_json::json!({
-            "version": version,
-            "changelog": changelog,
-            "file_size_bytes": file_size_bytes,
-            "file_hash_sha256": file_hash_sha256,
-        });
-        if let Some(readme_content) = readme {
-            body["readme"] = serde_json::Value::String(readme_content.to_string());
-        }
         self.post_json(
             &format!("/api/products/{}/{}/releases", username, slug),
-            &body,
+            &serde_json::to_value(req)?,
         )
         .await
     }
@@ -185,14 +335,132 @@ impl BaroClient {
         username: &str,
         slug: &str,
         version: &str,
+        preview_token: Option<&str>,
     ) -> Result<DownloadResponse> {
+        // Opt-in only (`share_client_hints` in config.json)
+        let hints: Vec<(&str, String)> = config::client_hint()
+            .map(|hint| ("X-Baro-Client-Hint", hint))
+            .into_iter()
+            .collect();
+        let mut path = format!("/api/products/{}/{}/releases/{}/download", username, slug, version);
+        if let Some(token) = preview_token {
+            path.push_str(&format!("?preview_token={}", urlencoded(token)));
+        }
+        self.send_json(reqwest::Method::GET, &path, None, &hints).await
+    }
+
+    pub async fn promote_release(&self, username: &str, slug: &str, version: &str) -> Result<ConfirmResponse> {
+        self.post_json(
+            &format!("/api/products/{}/{}/releases/{}/promote", username, slug, version),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn get_product_stats(&self, username: &str, slug: &str) -> Result<DownloadStats> {
+        self.get_json(&format!("/api/products/{}/{}/stats", username, slug)).await
+    }
+
+    /// Fork, download, rating and remake history for the last `days` days.
+    /// Registries without analytics answer 404.
+    pub async fn get_product_analytics(&self, username: &str, slug: &str, days: u32) -> Result<ProductAnalytics> {
+        self.get_json(&format!("/api/products/{}/{}/analytics?days={}", username, slug, days)).await
+    }
+
+    // -- Reviews --
+
+    pub async fn list_reviews(&self, username: &str, slug: &str) -> Result<ReviewsResponse> {
+        self.get_json(&format!("/api/products/{}/{}/reviews", username, slug)).await
+    }
+
+    /// Rate a product you've forked, with an optional review; rating again replaces it.
+    pub async fn rate_product(&self, username: &str, slug: &str, rating: u8, body: Option<&str>) -> Result<Review> {
+        self.post_json(
+            &format!("/api/products/{}/{}/reviews", username, slug),
+            &serde_json::json!({ "rating": rating, "body": body }),
+        )
+        .await
+    }
+
+    /// Answer a review of one of your products (replaces an earlier reply).
+    pub async fn reply_to_review(&self, review_id: &str, body: &str) -> Result<Review> {
+        self.post_json(&format!("/api/reviews/{}/reply", review_id), &serde_json::json!({ "body": body }))
+            .await
+    }
+
+    pub async fn attach_provenance(
+        &self,
+        release_id: &str,
+        provenance: &SignedProvenance,
+    ) -> Result<serde_json::Value> {
+        self.post_json(
+            &format!("/api/releases/{}/provenance", release_id),
+            &serde_json::to_value(provenance)?,
+        )
+        .await
+    }
+
+    pub async fn attach_sbom(&self, release_id: &str, sbom: &serde_json::Value) -> Result<serde_json::Value> {
+        self.post_json(&format!("/api/releases/{}/sbom", release_id), sbom).await
+    }
+
+    pub async fn get_sbom(&self, username: &str, slug: &str, version: &str) -> Result<serde_json::Value> {
+        self.get_json(&format!(
+            "/api/products/{}/{}/releases/{}/sbom",
+            username, slug, version
+        ))
+        .await
+    }
+
+    /// The README submitted with a release (`readme` is absent for releases
+    /// published before the registry stored it).
+    pub async fn get_readme(&self, username: &str, slug: &str, version: &str) -> Result<ReadmeResponse> {
         self.get_json(&format!(
-            "/api/products/{}/{}/releases/{}/download",
+            "/api/products/{}/{}/releases/{}/readme",
             username, slug, version
         ))
         .await
     }
 
+    pub async fn get_provenance(
+        &self,
+        username: &str,
+        slug: &str,
+        version: &str,
+    ) -> Result<SignedProvenance> {
+        self.get_json(&format!(
+            "/api/products/{}/{}/releases/{}/provenance",
+            username, slug, version
+        ))
+        .await
+    }
+
+    // -- Moderation (admin-scoped token) --
+
+    #[cfg(feature = "moderation")]
+    pub async fn review_queue(&self) -> Result<ReviewQueueResponse> {
+        self.get_json("/api/admin/releases?status=pending_review").await
+    }
+
+    #[cfg(feature = "moderation")]
+    pub async fn review_release(
+        &self,
+        release_id: &str,
+        decision: &str,
+        reason: Option<&str>,
+    ) -> Result<serde_json::Value> {
+        self.post_json(
+            &format!("/api/admin/releases/{}/{}", release_id, decision),
+            &serde_json::json!({ "reason": reason }),
+        )
+        .await
+    }
+
+    #[cfg(feature = "moderation")]
+    pub async fn admin_download(&self, release_id: &str) -> Result<DownloadResponse> {
+        self.get_json(&format!("/api/admin/releases/{}/download", release_id)).await
+    }
+
     // -- Remakes --
 
     pub async fn track_remake(
@@ -212,51 +480,348 @@ impl BaroClient {
         .await
     }
 
+    /// Send feedback, optionally with a crash report. Works without logging in.
+    pub async fn send_feedback(&self, message: Option<&str>, crash_report: Option<&str>) -> Result<serde_json::Value> {
+        self.post_json(
+            "/api/feedback",
+            &serde_json::json!({
+                "message": message,
+                "crash_report": crash_report,
+                "cli_version": env!("CARGO_PKG_VERSION"),
+            }),
+        )
+        .await
+    }
+
     // -- Categories --
 
     pub async fn list_categories(&self) -> Result<CategoriesResponse> {
         self.get_json("/api/categories").await
     }
 
+    /// Like `list_categories`, but served from a local cache when fresh.
+    /// Used for help text where a slightly stale list is acceptable.
+    pub async fn list_categories_cached(&self) -> Result<Vec<Category>> {
+        let path = config::config_dir()?.join("categories.json");
+        if let Ok(data) = std::fs::read_to_string(&path) {
+            if let Ok(cached) = serde_json::from_str::<CachedCategories>(&data) {
+                let age = chrono::Utc::now().timestamp() - cached.fetched_at;
+                if (0..CATEGORIES_CACHE_TTL_SECS).contains(&age) {
+                    return Ok(cached.categories);
+                }
+            }
+        }
+
+        let resp = self.list_categories().await?;
+        let cached = CachedCategories {
+            categories: resp.categories,
+            fetched_at: chrono::Utc::now().timestamp(),
+        };
+        if let Ok(json) = serde_json::to_string(&cached) {
+            let _ = crate::utils::write_atomic(&path, json.as_bytes());
+        }
+        Ok(cached.categories)
+    }
+
     // -- R2 direct operations --
 
-    pub async fn upload_to_r2(&self, upload_url: &str, data: &[u8]) -> Result<()> {
+    async fn put_file(&self, upload_url: &str, content_type: &str, data: &[u8]) -> Result<reqwest::StatusCode> {
         let resp = self
             .client
             .put(upload_url)
-            .header("Content-Type", "application/gzip")
+            .header("Content-Type", content_type)
             .body(data.to_vec())
             .send()
             .await
             .context("Failed to upload to storage")?;
+        Ok(resp.status())
+    }
 
-        if !resp.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Upload failed with status {}",
-                resp.status()
-            ));
+    /// Upload a release archive to its pre-signed URL. The URL is replaced via
+    /// `refresh_upload_url` when it is about to expire, or when storage answers
+    /// 403 because it expired during a slow upload.
+    pub async fn upload_release(
+        &self,
+        release: &CreateReleaseResponse,
+        issued_at: std::time::Instant,
+        data: &[u8],
+    ) -> Result<()> {
+        let expires_at = issued_at + std::time::Duration::from_secs(release.upload_expires_in);
+        let mut url = if expires_at.saturating_duration_since(std::time::Instant::now()) < UPLOAD_URL_MARGIN {
+            self.refresh_upload_url(&release.release_id).await?.upload_url
+        } else {
+            release.upload_url.clone()
+        };
+        let mut refreshes = 0;
+        loop {
+            let status = self.put_file(&url, "application/gzip", data).await?;
+            if status.is_success() {
+                return Ok(());
+            }
+            if status != reqwest::StatusCode::FORBIDDEN || refreshes == UPLOAD_URL_REFRESHES {
+                return Err(RegistryError::status(status, format!("Upload failed with status {}", status)).into());
+            }
+            eprintln!("  Upload URL expired; requesting a new one...");
+            refreshes += 1;
+            url = self.refresh_upload_url(&release.release_id).await?.upload_url;
+        }
+    }
+
+    /// Register an extra asset on an unconfirmed release and upload it.
+    pub async fn upload_asset(&self, release_id: &str, req: &CreateAssetRequest<'_>, data: &[u8]) -> Result<()> {
+        let created: CreateAssetResponse = self
+            .post_json(&format!("/api/releases/{}/assets", release_id), &serde_json::to_value(req)?)
+            .await?;
+        let status = self.put_file(&created.upload_url, "application/octet-stream", data).await?;
+        if !status.is_success() {
+            return Err(RegistryError::status(status, format!("Upload of {} failed with status {}", req.name, status)).into());
         }
         Ok(())
     }
 
-    pub async fn download_from_r2(&self, download_url: &str) -> Result<Vec<u8>> {
-        let resp = self
-            .client
-            .get(download_url)
+    pub async fn list_assets(&self, username: &str, slug: &str, version: &str) -> Result<AssetsResponse> {
+        self.get_json(&format!(
+            "/api/products/{}/{}/releases/{}/assets",
+            username, slug, version
+        ))
+        .await
+    }
+
+    /// Register a product page image/video and upload it. Returns the media ID.
+    pub async fn upload_media(
+        &self,
+        username: &str,
+        slug: &str,
+        req: &CreateMediaRequest<'_>,
+        data: &[u8],
+    ) -> Result<String> {
+        let created: CreateMediaResponse = self
+            .post_json(&format!("/api/products/{}/{}/media", username, slug), &serde_json::to_value(req)?)
+            .await?;
+        let status = self.put_file(&created.upload_url, req.content_type, data).await?;
+        if !status.is_success() {
+            return Err(RegistryError::status(status, format!("Upload of {} failed with status {}", req.filename, status)).into());
+        }
+        Ok(created.media_id)
+    }
+
+    pub async fn list_media(&self, username: &str, slug: &str) -> Result<MediaResponse> {
+        self.get_json(&format!("/api/products/{}/{}/media", username, slug)).await
+    }
+
+    pub async fn remove_media(&self, username: &str, slug: &str, media_id: &str) -> Result<serde_json::Value> {
+        self.delete_json(&format!("/api/products/{}/{}/media/{}", username, slug, media_id)).await
+    }
+
+    pub async fn refresh_upload_url(&self, release_id: &str) -> Result<UploadUrlResponse> {
+        self.post_json(
+            &format!("/api/releases/{}/upload-url", release_id),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    /// Download to `dest` through `<dest>.part`, returning (size, sha256 hex).
+    /// Bytes already in the `.part` file (from an earlier interrupted run) are
+    /// kept and the rest is requested with a `Range` header; dropped
+    /// connections are retried the same way. On failure the `.part` file is
+    /// left for the next attempt.
+    pub async fn download_to_file(&self, download_url: &str, dest: &std::path::Path) -> Result<(u64, String)> {
+        use sha2::{Digest, Sha256};
+        use tokio::io::AsyncReadExt;
+
+        let partial = utils::partial_path(dest);
+        let mut hasher = Sha256::new();
+        let mut have = 0u64;
+        if let Ok(mut existing) = tokio::fs::File::open(&partial).await {
+            let mut buf = vec![0u8; 64 * 1024];
+            loop {
+                let n = existing.read(&mut buf).await?;
+                if n == 0 {
+                    break;
+                }
+                hasher.update(&buf[..n]);
+                have += n as u64;
+            }
+            if have > 0 {
+                eprintln!("  Resuming download at {}", utils::format_bytes(have as i64));
+            }
+        }
+
+        let mut attempt = 1;
+        loop {
+            match self.download_range(download_url, &partial, &mut hasher, &mut have).await {
+                Ok(()) => break,
+                Err(DownloadError::Fatal(e)) => return Err(e),
+                Err(DownloadError::Stale) => {
+                    eprintln!("  Partial download doesn't match the file; starting over");
+                    let _ = tokio::fs::remove_file(&partial).await;
+                    hasher = Sha256::new();
+                    have = 0;
+                }
+                Err(DownloadError::Interrupted(e)) if attempt < DOWNLOAD_ATTEMPTS => {
+                    eprintln!("  Download interrupted ({:#}); resuming at {}...", e, utils::format_bytes(have as i64));
+                    tokio::time::sleep(std::time::Duration::from_secs(1 << attempt)).await;
+                    attempt += 1;
+                }
+                Err(DownloadError::Interrupted(e)) => {
+                    return Err(e.context(format!(
+                        "Download failed after {} attempts; run the command again to resume from {}",
+                        DOWNLOAD_ATTEMPTS,
+                        utils::format_bytes(have as i64)
+                    )));
+                }
+            }
+        }
+
+        tokio::fs::rename(&partial, dest)
+            .await
+            .with_context(|| format!("Failed to move download into place at {}", dest.display()))?;
+        Ok((have, format!("{:x}", hasher.finalize())))
+    }
+
+    /// One request for the bytes after `have`, appended to `partial`.
+    async fn download_range(
+        &self,
+        download_url: &str,
+        partial: &std::path::Path,
+        hasher: &mut sha2::Sha256,
+        have: &mut u64,
+    ) -> std::result::Result<(), DownloadError> {
+        use sha2::Digest;
+        use tokio::io::AsyncWriteExt;
+
+        let mut req = self.client.get(download_url);
+        if *have > 0 {
+            req = req.header(reqwest::header::RANGE, format!("bytes={}-", have));
+        }
+        let mut resp = req
             .send()
             .await
-            .context("Failed to download from storage")?;
+            .context("Failed to download from storage")
+            .map_err(DownloadError::Interrupted)?;
 
-        if !resp.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Download failed with status {}",
-                resp.status()
-            ));
+        let status = resp.status();
+        let resume = if *have == 0 {
+            RangeResume::Restart
+        } else {
+            let content_range = resp.headers().get(reqwest::header::CONTENT_RANGE).and_then(|v| v.to_str().ok());
+            utils::range_resume(status.as_u16(), content_range, *have)
+        };
+        if resume == RangeResume::Complete {
+            return Ok(());
+        }
+        if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && *have > 0 {
+            return Err(DownloadError::Stale);
+        }
+        if !status.is_success() {
+            let err: anyhow::Error = RegistryError::from_response(&resp, format!("Download failed with status {}", status)).into();
+            // Server-side hiccups are worth another try; anything else (an expired link) is not
+            return Err(if status.is_server_error() { DownloadError::Interrupted(err) } else { DownloadError::Fatal(err) });
         }
 
-        let bytes = resp.bytes().await?.to_vec();
-        Ok(bytes)
+        let mut options = tokio::fs::OpenOptions::new();
+        if resume == RangeResume::Append {
+            options.append(true);
+        } else {
+            options.write(true).create(true).truncate(true);
+            *hasher = sha2::Sha256::new();
+            *have = 0;
+        }
+        let mut file = options
+            .open(partial)
+            .await
+            .with_context(|| format!("Failed to open {}", partial.display()))
+            .map_err(DownloadError::Fatal)?;
+
+        let name = partial.file_name().unwrap_or_default().to_string_lossy().trim_end_matches(".part").to_string();
+        let mut progress = ui::Progress::new(name, resp.content_length().map(|len| len + *have));
+        loop {
+            let chunk = match resp.chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(e) => return Err(DownloadError::Interrupted(anyhow::Error::from(e).context("Download interrupted"))),
+            };
+            file.write_all(&chunk).await.map_err(|e| DownloadError::Fatal(e.into()))?;
+            hasher.update(&chunk);
+            *have += chunk.len() as u64;
+            progress.update(*have);
+        }
+        file.flush().await.map_err(|e| DownloadError::Fatal(e.into()))?;
+        Ok(())
+    }
+}
+
+enum DownloadError {
+    /// The connection dropped or the server failed; retry with a Range request
+    Interrupted(anyhow::Error),
+    Fatal(anyhow::Error),
+    /// 416 for a `.part` file that doesn't match the download (longer than
+    /// it, or left from a different file); discard it and start from byte 0
+    Stale,
+}
+
+/// Add one page of an owned-products listing to `products`; true once the
+/// listing is complete. A server that ignores `page` returns the first page
+/// again, which also means there is nothing more to fetch.
+fn absorb_products_page(products: &mut Vec<Product>, page: Vec<Product>, total: Option<u64>) -> bool {
+    if page.first().is_some_and(|p| products.iter().any(|q| q.id == p.id)) {
+        return true;
+    }
+    let got = page.len();
+    products.extend(page);
+    got == 0
+        || match total {
+            Some(total) => products.len() as u64 >= total,
+            None => got < PRODUCTS_PAGE_SIZE as usize,
+        }
+}
+
+/// The registry search request for a query and its filters.
+pub fn products_path(
+    q: Option<&str>,
+    filters: &ProductFilters<'_>,
+    sort: &str,
+    limit: u32,
+    page: u32,
+    explain: bool,
+) -> String {
+    let mut params = vec![
+        format!("sort={}", sort),
+        format!("limit={}", limit),
+        format!("page={}", page),
+    ];
+    if let Some(q) = q {
+        params.push(format!(
+            "q={}",
+            urlencoded(q)
+        ));
+    }
+    if let Some(cat) = filters.category {
+        params.push(format!("category={}", cat));
+    }
+    for tag in filters.tags {
+        params.push(format!("tag={}", urlencoded(tag)));
+    }
+    if let Some(publisher) = filters.publisher {
+        params.push(format!("publisher={}", urlencoded(publisher)));
+    }
+    for publisher in filters.exclude_publishers {
+        params.push(format!("exclude_publisher={}", urlencoded(publisher)));
     }
+    if let Some(min) = filters.min_rating {
+        params.push(format!("min_rating={}", min));
+    }
+    if let Some(min) = filters.min_forks {
+        params.push(format!("min_forks={}", min));
+    }
+    if let Some(origin) = filters.remakes_of {
+        params.push(format!("remakes_of={}", urlencoded(origin)));
+    }
+    if explain {
+        params.push("explain=1".to_string());
+    }
+    format!("/api/products?{}", params.join("&"))
 }
 
 fn urlencoded(s: &str) -> String {
@@ -268,3 +833,43 @@ fn urlencoded(s: &str) -> String {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(ids: std::ops::Range<usize>) -> Vec<Product> {
+        ids.map(|i| {
+            serde_json::from_value(serde_json::json!({
+                "id": format!("p{}", i), "slug": format!("s{}", i), "name": "n", "description": "",
+                "license": null, "latest_version": null, "review_status": "approved", "is_private": false,
+                "created_at": "", "updated_at": "", "publisher": null, "category": null, "stats": null,
+            }))
+            .unwrap()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn repeated_page_ends_the_listing() {
+        let full = PRODUCTS_PAGE_SIZE as usize;
+        let mut products = Vec::new();
+        assert!(!absorb_products_page(&mut products, page(0..full), None));
+        // The server ignored `page` and sent the first page again
+        assert!(absorb_products_page(&mut products, page(0..full), None));
+        assert_eq!(products.len(), full);
+    }
+
+    #[test]
+    fn short_last_page_ends_the_listing() {
+        let full = PRODUCTS_PAGE_SIZE as usize;
+        let mut products = Vec::new();
+        assert!(!absorb_products_page(&mut products, page(0..full), None));
+        assert!(absorb_products_page(&mut products, page(full..full + 3), None));
+        assert_eq!(products.len(), full + 3);
+
+        let mut products = Vec::new();
+        assert!(!absorb_products_page(&mut products, page(0..2), Some(4)));
+        assert!(absorb_products_page(&mut products, page(2..4), Some(4)));
+    }
+}
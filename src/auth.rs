@@ -9,11 +9,14 @@ use crate::config;
 const LOGIN_TIMEOUT_SECS: u64 = 120;
 const POLL_INTERVAL_SECS: u64 = 2;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredCredentials {
     pub access_token: String,
     pub refresh_token: String,
     pub expires_at: i64,
+    /// When the auth server last refused `refresh_token`; such a session can't be revived
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_rejected_at: Option<i64>,
 }
 
 fn save_credentials(creds: &StoredCredentials) -> Result<()> {
@@ -104,6 +107,26 @@ pub async fn login() -> Result<()> {
     Ok(())
 }
 
+/// Delete stored credentials whose refresh token the auth server has rejected.
+/// An expired access token alone is no reason: the refresh token usually
+/// outlives it. Unreadable credentials are left for `baro login` to replace.
+/// Returns whether any were removed.
+pub fn remove_rejected_credentials() -> bool {
+    let Ok(path) = config::credentials_path() else { return false };
+    let rejected = load_credentials().is_ok_and(|creds| creds.refresh_rejected_at.is_some());
+    rejected && std::fs::remove_file(&path).is_ok()
+}
+
+/// Local login state, derived from stored credentials without any network call.
+pub fn auth_state() -> &'static str {
+    match load_credentials() {
+        Ok(creds) if chrono::Utc::now().timestamp() < creds.expires_at => "logged_in",
+        // Refresh tokens outlive access tokens; the next command will refresh
+        Ok(_) => "expired",
+        Err(_) => "logged_out",
+    }
+}
+
 pub async fn get_token() -> Result<String> {
     let creds = load_credentials()?;
 
@@ -130,7 +153,16 @@ async fn refresh_token(creds: &StoredCredentials) -> Result<String> {
             "refresh_token": creds.refresh_token,
         }))
         .send()
-        .await?
+        .await?;
+    // 400/401 mean the refresh token itself was refused (revoked or expired),
+    // unlike a server error; remember it so maintenance can clear the session
+    if matches!(resp.status().as_u16(), 400 | 401) {
+        let _ = save_credentials(&StoredCredentials {
+            refresh_rejected_at: Some(chrono::Utc::now().timestamp()),
+            ..creds.clone()
+        });
+    }
+    let resp = resp
         .error_for_status()
         .context("Token refresh failed. Run 'baro login' to re-authenticate.")?;
 
@@ -147,6 +179,7 @@ async fn refresh_token(creds: &StoredCredentials) -> Result<String> {
         expires_at: body["expires_at"]
             .as_i64()
             .ok_or_else(|| anyhow::anyhow!("No expires_at in refresh response"))?,
+        refresh_rejected_at: None,
     };
 
     let token = new_creds.access_token.clone();
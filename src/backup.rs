@@ -0,0 +1,184 @@
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::Path;
+
+use crate::utils;
+
+const INDEX_FILE: &str = "index.json";
+
+/// `index.json` at the root of a backup: every product and release it holds.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct BackupIndex {
+    pub publisher: String,
+    pub generated_at: String,
+    pub products: Vec<BackupProduct>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupProduct {
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// `public`, `unlisted` or `private`; missing from backups made by older versions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    pub releases: Vec<BackupRelease>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupRelease {
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<String>,
+    pub created_at: String,
+    /// Archive path relative to the backup root
+    pub file: String,
+    pub sha256: String,
+    pub size_bytes: u64,
+}
+
+impl BackupProduct {
+    /// Releases in the order they were originally published, for replaying into a registry.
+    pub fn releases_oldest_first(&self) -> Vec<&BackupRelease> {
+        let mut releases: Vec<&BackupRelease> = self.releases.iter().collect();
+        releases.sort_by_key(|r| {
+            chrono::DateTime::parse_from_rfc3339(&r.created_at)
+                .map(|t| t.timestamp())
+                .unwrap_or(i64::MAX)
+        });
+        releases
+    }
+}
+
+impl BackupIndex {
+    /// The recorded release, if this backup already holds it.
+    pub fn release(&self, slug: &str, version: &str) -> Option<&BackupRelease> {
+        self.products
+            .iter()
+            .find(|p| p.slug == slug)
+            .and_then(|p| p.releases.iter().find(|r| r.version == version))
+    }
+}
+
+/// Where a release's archive lives inside the backup: `<slug>/<version>/<slug>-<version>.tar.gz`.
+pub fn archive_rel_path(slug: &str, version: &str) -> String {
+    format!("{}/{}/{}-{}.tar.gz", slug, version, slug, version)
+}
+
+pub fn load_index(root: &Path) -> Result<Option<BackupIndex>> {
+    match std::fs::read_to_string(root.join(INDEX_FILE)) {
+        Ok(data) => Ok(Some(
+            serde_json::from_str(&data).context("Corrupt backup index.json")?,
+        )),
+        Err(_) => Ok(None),
+    }
+}
+
+pub fn save_index(root: &Path, index: &BackupIndex) -> Result<()> {
+    let json = serde_json::to_string_pretty(index)?;
+    utils::write_atomic(&root.join(INDEX_FILE), json.as_bytes())?;
+    Ok(())
+}
+
+/// SHA-256 of a file, streamed. None if the file can't be read.
+pub fn sha256_file(path: &Path) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Some(format!("{:x}", hasher.finalize()))
+}
+
+/// Whether `dest` already holds an archive with hash `expected` (a finished
+/// earlier download; this is what makes an interrupted backup resumable).
+pub fn is_complete(dest: &Path, expected: &str) -> bool {
+    sha256_file(dest).is_some_and(|h| h == expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn index_roundtrip_and_lookup() {
+        let dir = tempdir().unwrap();
+        assert!(load_index(dir.path()).unwrap().is_none());
+
+        let index = BackupIndex {
+            publisher: "alice".to_string(),
+            generated_at: "2026-01-01T00:00:00Z".to_string(),
+            products: vec![BackupProduct {
+                slug: "demo".to_string(),
+                name: "Demo".to_string(),
+                description: "A demo".to_string(),
+                license: Some("MIT".to_string()),
+                category: None,
+                visibility: Some("unlisted".to_string()),
+                releases: vec![BackupRelease {
+                    version: "1.0.0".to_string(),
+                    changelog: None,
+                    created_at: "2026-01-01T00:00:00Z".to_string(),
+                    file: archive_rel_path("demo", "1.0.0"),
+                    sha256: "ab".repeat(32),
+                    size_bytes: 10,
+                }],
+            }],
+        };
+        save_index(dir.path(), &index).unwrap();
+        let loaded = load_index(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.release("demo", "1.0.0").unwrap().file, "demo/1.0.0/demo-1.0.0.tar.gz");
+        assert!(loaded.release("demo", "2.0.0").is_none());
+        assert_eq!(loaded.products[0].visibility.as_deref(), Some("unlisted"));
+    }
+
+    #[test]
+    fn completed_download_detected_by_hash() {
+        let dir = tempdir().unwrap();
+        let dest = dir.path().join("a.tar.gz");
+        assert!(!is_complete(&dest, "x"));
+        fs::write(&dest, "hello").unwrap();
+        let hash = sha256_file(&dest).unwrap();
+        assert!(is_complete(&dest, &hash));
+        assert!(!is_complete(&dest, &"00".repeat(32)));
+    }
+
+    #[test]
+    fn releases_replay_oldest_first() {
+        let release = |version: &str, created_at: &str| BackupRelease {
+            version: version.to_string(),
+            changelog: None,
+            created_at: created_at.to_string(),
+            file: archive_rel_path("demo", version),
+            sha256: String::new(),
+            size_bytes: 0,
+        };
+        let product = BackupProduct {
+            slug: "demo".to_string(),
+            name: "Demo".to_string(),
+            description: String::new(),
+            license: None,
+            category: None,
+            visibility: None,
+            releases: vec![
+                release("1.10.0", "2026-03-01T00:00:00Z"),
+                release("1.2.0", "2026-01-01T00:00:00+00:00"),
+                release("1.9.0", "2026-02-01T09:00:00+09:00"),
+            ],
+        };
+        let order: Vec<&str> = product.releases_oldest_first().iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(order, vec!["1.2.0", "1.9.0", "1.10.0"]);
+    }
+}
@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::diff::{self, TreeDiff};
+use crate::manifest;
+use crate::packaging;
+
+const BASELINE_FILE: &str = "files.json";
+
+/// SHA-256 of every file as forked (or as of the last `upstream pull`), so
+/// local edits can be told apart from untouched files.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Upstream version the hashes belong to
+    pub version: String,
+    pub files: BTreeMap<String, String>,
+}
+
+/// `.baro/files.json`
+pub fn path(dir: &Path) -> PathBuf {
+    manifest::path(dir).with_file_name(BASELINE_FILE)
+}
+
+pub fn hashes(files: &BTreeMap<String, Vec<u8>>) -> BTreeMap<String, String> {
+    files.iter().map(|(p, c)| (p.clone(), packaging::sha256_hex(c))).collect()
+}
+
+/// Record `files` as the baseline of the product in `dir`.
+pub fn write(dir: &Path, version: &str, files: &BTreeMap<String, Vec<u8>>) -> Result<()> {
+    let baseline = Baseline { version: version.to_string(), files: hashes(files) };
+    std::fs::write(path(dir), serde_json::to_string_pretty(&baseline)?)
+        .context("Failed to write .baro/files.json")
+}
+
+/// Record the tree in `dir` as it is now.
+pub fn record(dir: &Path, version: &str) -> Result<()> {
+    write(dir, version, &packaging::read_tree(dir)?)
+}
+
+/// The recorded baseline; None for forks made before baselines were kept.
+pub fn read(dir: &Path) -> Result<Option<Baseline>> {
+    match std::fs::read_to_string(path(dir)) {
+        Ok(data) => Ok(Some(serde_json::from_str(&data).context("Invalid .baro/files.json")?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+impl Baseline {
+    /// Files modified, added and removed since the baseline.
+    pub fn changes(&self, current: &BTreeMap<String, Vec<u8>>) -> TreeDiff {
+        diff::compare_trees(&self.files, &hashes(current))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reports_changes_since_baseline() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".baro")).unwrap();
+        assert!(read(dir.path()).unwrap().is_none());
+
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "b").unwrap();
+        record(dir.path(), "1.0.0").unwrap();
+        let baseline = read(dir.path()).unwrap().unwrap();
+        assert_eq!(baseline.version, "1.0.0");
+        assert!(baseline.changes(&packaging::read_tree(dir.path()).unwrap()).is_empty());
+
+        std::fs::write(dir.path().join("a.txt"), "edited").unwrap();
+        std::fs::remove_file(dir.path().join("b.txt")).unwrap();
+        std::fs::write(dir.path().join("c.txt"), "c").unwrap();
+        let changes = baseline.changes(&packaging::read_tree(dir.path()).unwrap());
+        assert_eq!((changes.modified, changes.added, changes.removed), (vec!["a.txt".to_string()], vec!["c.txt".to_string()], vec!["b.txt".to_string()]));
+    }
+}
@@ -0,0 +1,299 @@
+use anyhow::Result;
+use std::io::{Read, Write};
+
+use crate::types::Product;
+use crate::ui;
+use crate::utils;
+
+/// A key press, as far as the browser cares.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Enter,
+    Esc,
+    Char(char),
+}
+
+/// What the caller should do after a key press.
+#[derive(Debug, PartialEq)]
+pub enum Step {
+    /// Redraw (the selection may have moved)
+    Redraw,
+    /// Fetch the next page of results and `extend` with it
+    LoadMore,
+    /// Open the selected product's page in a browser
+    Open(String),
+    /// Leave the browser and fork the selected product
+    Fork(String),
+    Quit,
+}
+
+/// One result row: `user/slug`, version and description.
+pub struct Entry {
+    pub id: String,
+    pub version: Option<String>,
+    pub description: String,
+}
+
+impl Entry {
+    pub fn from_product(p: &Product) -> Self {
+        let publisher = p.publisher.as_ref().map(|r| r.username.as_str()).unwrap_or("?");
+        Entry {
+            id: format!("{}/{}", publisher, p.slug),
+            version: p.latest_version.clone(),
+            description: p.description.clone(),
+        }
+    }
+}
+
+/// Result list with a selection, scrolled to keep the selection visible.
+pub struct Browser {
+    pub title: String,
+    entries: Vec<Entry>,
+    total: u64,
+    selected: usize,
+    offset: usize,
+    /// Rows the list pane had at the last render, for paging
+    rows: usize,
+    pub status: String,
+}
+
+impl Browser {
+    pub fn new(title: String, entries: Vec<Entry>, total: u64) -> Self {
+        Browser { title, entries, total, selected: 0, offset: 0, rows: 10, status: String::new() }
+    }
+
+    pub fn selected(&self) -> Option<&Entry> {
+        self.entries.get(self.selected)
+    }
+
+    pub fn has_more(&self) -> bool {
+        (self.entries.len() as u64) < self.total
+    }
+
+    pub fn extend(&mut self, entries: Vec<Entry>) {
+        if entries.is_empty() {
+            // The registry has fewer results than it claimed
+            self.total = self.entries.len() as u64;
+        }
+        self.entries.extend(entries);
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.entries.len().saturating_sub(1));
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + self.rows {
+            self.offset = self.selected + 1 - self.rows;
+        }
+    }
+
+    pub fn handle(&mut self, key: Key) -> Step {
+        self.status.clear();
+        let last = self.entries.len().saturating_sub(1);
+        match key {
+            Key::Up | Key::Char('k') => self.select(self.selected.saturating_sub(1)),
+            // Moving past the last loaded result fetches the next page
+            Key::Down | Key::Char('j') if self.selected == last && self.has_more() => return Step::LoadMore,
+            Key::Down | Key::Char('j') => self.select(self.selected + 1),
+            Key::PageUp => self.select(self.selected.saturating_sub(self.rows)),
+            Key::PageDown => self.select(self.selected + self.rows),
+            Key::Char('n') if self.has_more() => return Step::LoadMore,
+            Key::Enter | Key::Char('f') => {
+                if let Some(entry) = self.selected() {
+                    return Step::Fork(entry.id.clone());
+                }
+            }
+            Key::Char('o') => {
+                if let Some(entry) = self.selected() {
+                    return Step::Open(entry.id.clone());
+                }
+            }
+            Key::Char('q') | Key::Esc => return Step::Quit,
+            _ => {}
+        }
+        Step::Redraw
+    }
+
+    /// Screen lines for a `width` x `height` terminal: the result list on top,
+    /// `preview` (the selected product's README) below, and a key legend.
+    pub fn render(&mut self, preview: &str, width: usize, height: usize) -> Vec<String> {
+        let width = width.max(20);
+        // Title, separator and legend take three lines; the list gets up to half the rest
+        let body = height.saturating_sub(3).max(2);
+        self.rows = (body / 2).clamp(1, self.entries.len().max(1));
+        self.select(self.selected);
+
+        let fit = |s: &str| utils::truncate_str(s, width);
+        let mut lines = vec![fit(&format!("{} ({} of {} loaded)", self.title, self.entries.len(), self.total))];
+        for (i, entry) in self.entries.iter().enumerate().skip(self.offset).take(self.rows) {
+            let marker = if i == self.selected { '>' } else { ' ' };
+            let version = entry.version.as_deref().map(|v| format!(" v{}", v)).unwrap_or_default();
+            lines.push(fit(&format!("{} {}{}  {}", marker, entry.id, version, entry.description)));
+        }
+        lines.push("-".repeat(width));
+        let preview_rows = body - self.rows;
+        lines.extend(preview.lines().take(preview_rows).map(fit));
+        while lines.len() < height.saturating_sub(1) {
+            lines.push(String::new());
+        }
+        let legend = if self.status.is_empty() {
+            "up/down or j/k move  enter/f fork  o open in browser  n more  q quit"
+        } else {
+            self.status.as_str()
+        };
+        lines.push(fit(legend));
+        lines
+    }
+}
+
+/// The terminal in raw mode on the alternate screen; restored on drop.
+pub struct Screen {
+    #[cfg(unix)]
+    saved: libc::termios,
+}
+
+impl Screen {
+    #[cfg(unix)]
+    pub fn enter() -> Result<Self> {
+        // SAFETY: tcgetattr/tcsetattr only read and write the termios struct we pass
+        let saved = unsafe {
+            let mut saved = std::mem::zeroed::<libc::termios>();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut saved) != 0 {
+                return Err(std::io::Error::last_os_error().into());
+            }
+            let mut raw = saved;
+            libc::cfmakeraw(&mut raw);
+            // Reads wait at most 0.1s, so a lone Esc isn't mistaken for the start of a sequence
+            raw.c_cc[libc::VMIN] = 0;
+            raw.c_cc[libc::VTIME] = 1;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) != 0 {
+                return Err(std::io::Error::last_os_error().into());
+            }
+            saved
+        };
+        print!("\x1b[?1049h\x1b[?25l");
+        std::io::stdout().flush()?;
+        Ok(Screen { saved })
+    }
+
+    #[cfg(not(unix))]
+    pub fn enter() -> Result<Self> {
+        Err(anyhow::anyhow!("The interactive browser needs a Unix terminal; use `baro search` without --interactive"))
+    }
+
+    /// Terminal (columns, rows); 80x24 if it can't be determined.
+    pub fn size(&self) -> (usize, usize) {
+        ui::terminal_size().unwrap_or((80, 24))
+    }
+
+    pub fn draw(&self, lines: &[String]) -> Result<()> {
+        let mut out = std::io::stdout().lock();
+        write!(out, "\x1b[H\x1b[2J")?;
+        // Raw mode doesn't translate \n, so return the carriage explicitly
+        write!(out, "{}", lines.join("\r\n"))?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Block until a key we understand is pressed.
+    pub fn read_key(&self) -> Result<Key> {
+        let mut stdin = std::io::stdin().lock();
+        loop {
+            let Some(byte) = read_byte(&mut stdin)? else { continue };
+            let key = match byte {
+                b'\r' | b'\n' => Key::Enter,
+                // Ctrl-C arrives as a byte in raw mode
+                3 => Key::Char('q'),
+                0x1b => match read_byte(&mut stdin)? {
+                    None => Key::Esc,
+                    Some(b'[') => match read_byte(&mut stdin)? {
+                        Some(b'A') => Key::Up,
+                        Some(b'B') => Key::Down,
+                        Some(b'5') => {
+                            read_byte(&mut stdin)?;
+                            Key::PageUp
+                        }
+                        Some(b'6') => {
+                            read_byte(&mut stdin)?;
+                            Key::PageDown
+                        }
+                        _ => continue,
+                    },
+                    Some(_) => continue,
+                },
+                b if b.is_ascii_graphic() => Key::Char(b as char),
+                _ => continue,
+            };
+            return Ok(key);
+        }
+    }
+}
+
+fn read_byte(input: &mut impl Read) -> Result<Option<u8>> {
+    let mut buf = [0u8; 1];
+    Ok((input.read(&mut buf)? == 1).then_some(buf[0]))
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = std::io::stdout().flush();
+        #[cfg(unix)]
+        // SAFETY: restores the attributes read in `enter`
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.saved);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn browser(count: usize, total: u64) -> Browser {
+        let entries = (0..count)
+            .map(|i| Entry { id: format!("alice/p{}", i), version: Some("1.0.0".to_string()), description: "desc".to_string() })
+            .collect();
+        Browser::new("baro search".to_string(), entries, total)
+    }
+
+    #[test]
+    fn navigation_scrolls_and_loads_more() {
+        let mut b = browser(10, 15);
+        b.render("", 80, 11); // 4 list rows
+        assert_eq!(b.handle(Key::Up), Step::Redraw);
+        assert_eq!(b.selected().unwrap().id, "alice/p0");
+        for _ in 0..5 {
+            b.handle(Key::Down);
+        }
+        let lines = b.render("", 80, 11);
+        assert!(lines[4].starts_with("> alice/p5"));
+        assert!(lines[1].starts_with("  alice/p2"));
+
+        b.handle(Key::PageDown);
+        b.handle(Key::PageDown);
+        assert_eq!(b.selected().unwrap().id, "alice/p9");
+        assert_eq!(b.handle(Key::Down), Step::LoadMore);
+        b.extend(Vec::new());
+        assert!(!b.has_more());
+        assert_eq!(b.handle(Key::Down), Step::Redraw);
+
+        assert_eq!(b.handle(Key::Enter), Step::Fork("alice/p9".to_string()));
+        assert_eq!(b.handle(Key::Char('o')), Step::Open("alice/p9".to_string()));
+        assert_eq!(b.handle(Key::Char('q')), Step::Quit);
+    }
+
+    #[test]
+    fn render_fits_the_terminal() {
+        let mut b = browser(3, 3);
+        let preview = "# Title\n".repeat(50);
+        let lines = b.render(&preview, 30, 20);
+        assert_eq!(lines.len(), 20);
+        assert!(lines.iter().all(|l| l.chars().count() <= 30));
+        assert_eq!(lines[4], "-".repeat(30));
+    }
+}
@@ -1,8 +1,38 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(name = "baro", about = "CLI for the Baro AI product marketplace", version)]
 pub struct Cli {
+    /// Run as if baro was started in <PATH>
+    #[arg(short = 'C', global = true, value_name = "PATH")]
+    pub chdir: Option<std::path::PathBuf>,
+
+    /// Answer yes to every confirmation prompt
+    #[arg(short = 'y', long, global = true)]
+    pub yes: bool,
+
+    /// Screen-reader friendly output: plain words instead of symbols, periodic
+    /// status lines for long transfers (also via TERM=dumb or BARO_ACCESSIBLE=1)
+    #[arg(long, global = true)]
+    pub accessible: bool,
+
+    /// Timestamp style: local (relative + local time), utc, or iso (RFC 3339, for scripts)
+    #[arg(long, global = true, value_enum, default_value = "local")]
+    pub time_format: crate::ui::TimeFormat,
+
+    /// Shorthand for --time-format utc
+    #[arg(long, global = true)]
+    pub utc: bool,
+
+    /// Never prompt; fail with instructions where input would be needed (implied without a terminal)
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
+
+    /// How to report a failure: text, or json (one object on stderr with code,
+    /// message, hint, retryable and request_id)
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    pub error_format: OutputFormat,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -13,126 +43,907 @@ pub enum Commands {
     Login,
 
     /// Publish a product release (package + validate + upload)
-    Publish {
-        /// Version string (e.g., "1.0.0")
+    Publish(PublishArgs),
+
+    /// Publish a forked product as your own (one-time, then use publish for updates)
+    Remake(RemakeArgs),
+
+    /// Fork a product (download + unpack)
+    Fork(ForkArgs),
+
+    /// Alias for fork (hidden)
+    #[command(hide = true)]
+    Clone(ForkArgs),
+
+    /// Search for products
+    Search(SearchArgs),
+
+    /// Top products of a category, no query needed (lists categories without one)
+    Browse {
+        /// Category slug, e.g. ai-agents
+        category: Option<String>,
+
+        /// Sort order: downloads, rating, recent
+        #[arg(long, default_value = "downloads")]
+        sort: String,
+
+        /// Max results to show
+        #[arg(long, default_value = "20")]
+        limit: u32,
+
+        /// Page of results to start from
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        page: u32,
+
+        /// One aligned row per result
         #[arg(long)]
-        version: String,
+        table: bool,
+    },
 
-        /// Changelog describing what changed and why
+    /// Initialize a baro product in the current directory
+    Init {
+        /// Product slug (default: derived from directory name)
         #[arg(long)]
-        changelog: Option<String>,
+        slug: Option<String>,
+
+        /// Product directory (default: current directory)
+        #[arg(long, value_name = "DIR")]
+        path: Option<std::path::PathBuf>,
+    },
 
-        /// Category slug (e.g., developer-tools, productivity, ai-agents)
+    /// Change a product's name, description, category, license or links without a new release
+    Edit {
+        /// Product: user/product (default: product in current directory)
         #[arg(long)]
-        category: Option<String>,
+        product: Option<String>,
 
-        /// Product display name (default: from build file or directory name)
+        /// New display name
         #[arg(long)]
         name: Option<String>,
 
-        /// Product description, 50+ chars (default: from build file)
+        /// New description (50+ chars)
         #[arg(long)]
         description: Option<String>,
 
-        /// License identifier (default: MIT)
-        #[arg(long, default_value = "MIT")]
-        license: String,
+        /// New category slug
+        #[arg(long)]
+        category: Option<String>,
+
+        /// New SPDX license expression
+        #[arg(long)]
+        license: Option<String>,
 
+        #[command(flatten)]
+        links: LinkArgs,
     },
 
-    /// Publish a forked product as your own (one-time, then use publish for updates)
-    Remake {
-        /// Version string (e.g., "1.0.0")
+    /// List your published products
+    Products {
+        /// Filter by status: published, pending_review, unlisted, rejected
         #[arg(long)]
-        version: String,
+        status: Option<String>,
 
-        /// Product slug (must differ from original if you own a product with the same slug)
-        #[arg(long)]
-        slug: Option<String>,
+        /// Order by: remakes, forks, rating, updated (default: registry order)
+        #[arg(long, value_enum)]
+        sort: Option<ProductSort>,
 
-        /// Changelog describing what changed and why
+        /// One aligned row per product
         #[arg(long)]
-        changelog: Option<String>,
+        table: bool,
 
-        /// Category slug (e.g., developer-tools, productivity, ai-agents)
-        #[arg(long)]
-        category: String,
+        /// With --table: don't cut columns to the terminal width
+        #[arg(long, requires = "table")]
+        wide: bool,
 
-        /// Product display name (default: from build file or directory name)
+        /// Print the products (slug, versions, review status, stats) as a JSON array
+        #[arg(long, conflicts_with = "table")]
+        json: bool,
+    },
+
+    /// Make a preview release public (see `baro publish --preview`)
+    Promote {
+        /// Version to promote
+        version: String,
+
+        /// Product: user/product (default: product in current directory)
         #[arg(long)]
-        name: Option<String>,
+        product: Option<String>,
+    },
+
+    /// Download every release of your products into a local backup directory
+    Backup {
+        /// Backup directory (created if missing)
+        #[arg(long, default_value = "baro-backup", value_name = "DIR")]
+        out: std::path::PathBuf,
 
-        /// Product description, 50+ chars (default: from build file)
+        /// Only fetch releases not already recorded in the backup's index.json
         #[arg(long)]
-        description: Option<String>,
+        incremental: bool,
 
-        /// License identifier (default: MIT)
-        #[arg(long, default_value = "MIT")]
-        license: String,
+        /// Parallel downloads
+        #[arg(long, default_value = "4")]
+        jobs: usize,
     },
 
-    /// Fork a product (download + unpack)
-    Fork {
-        /// Product identifier: user/product or user/product@version
+    /// Download every release of a product, with metadata, into a local directory
+    Mirror {
+        /// Product identifier: user/product
         product: String,
 
-        /// Output directory (default: product slug)
-        #[arg(long)]
-        dir: Option<String>,
+        /// Mirror directory (created if missing); same layout as `baro backup`
+        dir: std::path::PathBuf,
     },
 
-    /// Alias for fork (hidden)
-    #[command(hide = true)]
-    Clone {
+    /// Recreate the products and releases in a backup, on this or another registry
+    Restore {
+        /// Backup directory written by `baro backup`
+        dir: std::path::PathBuf,
+
+        /// Registry API URL to restore into (default: the configured registry).
+        /// Authenticates with BARO_REGISTRY_TOKEN, which is required for another registry;
+        /// your current login is only used for the configured one.
+        #[arg(long, value_name = "URL")]
+        registry: Option<String>,
+    },
+
+    /// Show details about a product
+    Info {
         /// Product identifier: user/product or user/product@version
         product: String,
 
-        /// Output directory (default: product slug)
+        /// Print the release's CycloneDX SBOM (JSON) instead
         #[arg(long)]
-        dir: Option<String>,
+        sbom: bool,
     },
 
-    /// Search for products
-    Search {
-        /// Search query
-        query: String,
+    /// Open a product's page in the browser
+    Open {
+        /// Product identifier: user/product (default: this directory's product, or the product it was forked from)
+        product: Option<String>,
+    },
 
-        /// Filter by category slug
-        #[arg(long)]
-        category: Option<String>,
+    /// List a product's releases, newest first
+    Releases {
+        /// Product identifier: user/product (with --mine, default: the product in this directory)
+        #[arg(required_unless_present = "mine")]
+        product: Option<String>,
 
-        /// Sort order: recent, downloads, rating
-        #[arg(long, default_value = "recent")]
-        sort: String,
+        /// Your own product: every release with its upload and review status
+        #[arg(long)]
+        mine: bool,
 
-        /// Max results to show
-        #[arg(long, default_value = "20")]
-        limit: u32,
+        /// Print the releases as a JSON array
+        #[arg(long)]
+        json: bool,
     },
 
-    /// Initialize a baro product in the current directory
-    Init {
-        /// Product slug (default: derived from directory name)
+    /// Show a product's README, formatted for the terminal
+    Readme {
+        /// Product identifier: user/product or user/product@version
+        product: String,
+
+        /// Print the Markdown source unchanged (for piping)
         #[arg(long)]
-        slug: Option<String>,
+        raw: bool,
     },
 
-    /// List your published products
-    Products {
-        /// Filter by status: published, pending_review, unlisted, rejected
-        #[arg(long)]
-        status: Option<String>,
+    /// Show download, fork, rating and remake stats for your products
+    Stats {
+        /// Product identifier: user/product (default: the product in this directory)
+        product: Option<String>,
+
+        /// Days of history for download and fork trends
+        #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..=365))]
+        days: u32,
+
+        /// One row per product you publish instead of one product in detail
+        #[arg(long, conflicts_with = "product")]
+        all: bool,
     },
 
     /// Show product identity and fork origin info
-    Status,
+    Status {
+        /// Print a stable JSON report (for editor integrations)
+        #[arg(long)]
+        json: bool,
+
+        /// Product directory (default: current directory)
+        #[arg(long, value_name = "DIR")]
+        path: Option<std::path::PathBuf>,
+    },
 
     /// Check for new releases from fork origin
-    Upstream,
+    #[command(args_conflicts_with_subcommands = true)]
+    Upstream {
+        /// Check every fork in this directory and below (default: the current directory)
+        #[arg(short, long, value_name = "DIR", num_args = 0..=1, default_missing_value = ".")]
+        recursive: Option<std::path::PathBuf>,
+
+        /// Origins to check at once with --recursive
+        #[arg(long, default_value_t = 8, requires = "recursive")]
+        jobs: usize,
+
+        /// Upstream to use, as named in `baro upstream remote list` (default: origin)
+        #[arg(long, global = true, value_name = "NAME")]
+        remote: Option<String>,
+
+        #[command(subcommand)]
+        action: Option<UpstreamAction>,
+    },
 
     /// Pull upstream changes into a sibling directory for AI-assisted merge
     Pull,
 
+    /// Run the project's build (and optionally tests) to check it still works
+    ///
+    /// Commands get no stdin, a filtered environment (no tokens or cloud
+    /// credentials) and a timeout, but are not sandboxed: they run with your
+    /// file system and network access, as they would from a shell.
+    Health {
+        /// Also run the test suite
+        #[arg(long)]
+        test: bool,
+
+        /// Per-command timeout in seconds
+        #[arg(long, default_value = "600")]
+        timeout: u64,
+    },
+
+    /// Verify a release's hash and signature against the publisher's keys
+    Verify {
+        /// Product identifier: user/product[@version] (default: this fork's origin and version)
+        product: Option<String>,
+
+        /// Also verify the signed build provenance (CI builder, source commit)
+        #[arg(long)]
+        provenance: bool,
+    },
+
+    /// Generate a release signing key and register its public half
+    Keygen {
+        /// Replace an existing key
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Product slug helpers
+    Slug {
+        #[command(subcommand)]
+        action: SlugAction,
+    },
+
+    /// Save and restore local checkpoints of your working tree (independent of git)
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+
+    /// Show or change who can see one of your products
+    Visibility {
+        /// New visibility: public, unlisted, private (default: show the current one)
+        #[arg(value_enum)]
+        level: Option<Visibility>,
+
+        /// Product to change: user/product (default: product in current directory)
+        #[arg(long)]
+        product: Option<String>,
+    },
+
+    /// Mark one of your products deprecated, warning anyone who forks or tracks it
+    Deprecate {
+        /// Product to deprecate: user/product (default: product in current directory)
+        product: Option<String>,
+
+        /// Why, and what to use instead, e.g. "superseded by user/other"
+        #[arg(long, short, required_unless_present = "undo")]
+        message: Option<String>,
+
+        /// Lift the deprecation
+        #[arg(long, conflicts_with = "message")]
+        undo: bool,
+    },
+
+    /// Manage screenshots and other media on your product page
+    Media {
+        #[command(subcommand)]
+        action: MediaAction,
+    },
+
+    /// Registry moderation (admin token required via BARO_ADMIN_TOKEN)
+    #[cfg(feature = "moderation")]
+    Mod {
+        #[command(subcommand)]
+        action: ModAction,
+    },
+
+    /// Read the ratings and reviews of your products, and reply to them
+    #[command(args_conflicts_with_subcommands = true)]
+    Reviews {
+        /// Product: user/product (default: the product in this directory, else all of yours)
+        product: Option<String>,
+
+        /// Only reviews you haven't replied to
+        #[arg(long)]
+        unanswered: bool,
+
+        #[command(subcommand)]
+        action: Option<ReviewAction>,
+    },
+
+    /// Rate a product you've forked, optionally with a short review
+    Rate {
+        /// Product: user/product
+        product: String,
+
+        /// Stars, from 1 to 5
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=5))]
+        rating: u8,
+
+        /// A short review shown on the product page
+        #[arg(long, short)]
+        review: Option<String>,
+    },
+
+    /// Get told when the origins of your forks publish new releases
+    Notify {
+        #[command(subcommand)]
+        action: NotifyAction,
+    },
+
+    /// Clean up local caches, old crash reports and stale temp files
+    Maintenance {
+        #[command(subcommand)]
+        action: MaintenanceAction,
+    },
+
+    /// Send feedback to the baro team, optionally attaching a crash report
+    Feedback {
+        /// What happened or what you'd like to see
+        #[arg(required_unless_present_any = ["crash", "crash_file"])]
+        message: Option<String>,
+
+        /// Attach the most recent crash report
+        #[arg(long)]
+        crash: bool,
+
+        /// Attach this crash report instead of the most recent one
+        #[arg(long, value_name = "PATH")]
+        crash_file: Option<std::path::PathBuf>,
+    },
+
     /// Log out and remove stored credentials
     Logout,
 }
+
+#[cfg(feature = "moderation")]
+#[derive(Subcommand)]
+pub enum ModAction {
+    /// List releases awaiting review
+    Queue,
+
+    /// Approve a pending release
+    Approve {
+        /// Release ID (see `baro mod queue`)
+        release: String,
+
+        /// Note recorded with the decision
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Reject a pending release
+    Reject {
+        /// Release ID (see `baro mod queue`)
+        release: String,
+
+        /// Why the release was rejected (shown to the publisher)
+        #[arg(long)]
+        reason: String,
+    },
+
+    /// Download a pending release and audit its contents
+    Inspect {
+        /// Release ID (see `baro mod queue`)
+        release: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MediaAction {
+    /// Upload an image or short video (png, jpg, gif, webp, mp4, webm)
+    Add {
+        /// File to upload
+        file: std::path::PathBuf,
+
+        /// Caption shown with the media
+        #[arg(long)]
+        caption: Option<String>,
+
+        /// Product: user/product (default: product in current directory)
+        #[arg(long)]
+        product: Option<String>,
+    },
+
+    /// List the product page's media
+    List {
+        /// Product: user/product (default: product in current directory)
+        #[arg(long)]
+        product: Option<String>,
+    },
+
+    /// Remove media from the product page
+    Remove {
+        /// Media ID (see `baro media list`)
+        id: String,
+
+        /// Product: user/product (default: product in current directory)
+        #[arg(long)]
+        product: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SlugAction {
+    /// Check whether a slug is valid and free, globally and in your namespace
+    Check {
+        /// Slug to check
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum UpstreamAction {
+    /// Show which files changed since forking, compared with the forked release
+    Diff {
+        /// Compare with the latest upstream release instead
+        #[arg(long)]
+        latest: bool,
+
+        /// Print unified diffs of changed text files
+        #[arg(short, long)]
+        patch: bool,
+    },
+    /// Merge the latest upstream release into this fork, keeping local changes
+    Pull {
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Replay your local changes onto a fresh copy of the latest upstream release,
+    /// updating this project only if every change applies
+    Rebase,
+    /// Point this project at a different origin (e.g. after the upstream moved publishers)
+    Set {
+        /// user/product[@version]: the release this project is based on (default:
+        /// the current base version if the new origin has it, else its latest)
+        product: String,
+    },
+    /// Don't offer upstream releases above VERSION until unpinned
+    Pin {
+        #[arg(required_unless_present = "clear")]
+        version: Option<String>,
+
+        /// Remove the pin and follow the latest release again
+        #[arg(long, conflicts_with = "version")]
+        clear: bool,
+    },
+    /// Skip an upstream release: `baro upstream` and notifications stop offering it
+    Ignore {
+        version: String,
+
+        /// Offer the version again
+        #[arg(long)]
+        undo: bool,
+    },
+    /// Track further upstreams besides the fork's origin
+    Remote {
+        #[command(subcommand)]
+        action: RemoteAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RemoteAction {
+    /// Track another product as upstream NAME, starting from its latest (or the given) release
+    Add {
+        name: String,
+        /// user/product[@version]: the release this project already includes
+        product: String,
+    },
+    /// Stop tracking upstream NAME
+    Remove { name: String },
+    /// List tracked upstreams
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum NotifyAction {
+    /// Announce new upstream releases of this fork after any baro command
+    Enable {
+        /// Fork directory (default: the current project)
+        path: Option<std::path::PathBuf>,
+    },
+    /// Stop announcing updates for this fork
+    Disable {
+        /// Fork directory (default: the current project)
+        path: Option<std::path::PathBuf>,
+    },
+    /// List subscribed forks and what is known about their origins
+    Status,
+}
+
+#[derive(Subcommand)]
+pub enum ReviewAction {
+    /// Reply to a review (the id is shown by `baro reviews`); replying again replaces it
+    Reply {
+        review_id: String,
+
+        /// Your reply, shown under the review
+        #[arg(long, short)]
+        message: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MaintenanceAction {
+    /// Run a full maintenance pass now (it also runs automatically once a day)
+    Run,
+}
+
+#[derive(Subcommand)]
+pub enum SnapshotAction {
+    /// Save a snapshot of the current files
+    Save {
+        /// Short note describing this checkpoint
+        #[arg(short, long)]
+        message: Option<String>,
+    },
+
+    /// List saved snapshots
+    List,
+
+    /// Restore files from a snapshot (the current state is saved first)
+    Restore {
+        /// Snapshot ID (see `baro snapshot list`)
+        id: String,
+
+        /// Only restore these files or directories (default: everything)
+        paths: Vec<String>,
+    },
+}
+
+#[derive(Args, Clone)]
+pub struct PublishArgs {
+    /// Version string (e.g., "1.0.0")
+    #[arg(long, required_unless_present = "workspace")]
+    pub version: Option<String>,
+
+    /// Changelog describing what changed and why
+    #[arg(long)]
+    pub changelog: Option<String>,
+
+    /// Build the changelog from conventional commits since the last published version's tag
+    #[arg(long, conflicts_with = "changelog")]
+    pub changelog_from_git: bool,
+
+    /// Category slug (e.g., developer-tools, productivity, ai-agents)
+    #[arg(long)]
+    pub category: Option<String>,
+
+    /// Topic tag for discovery, e.g. discord-bot (repeatable, up to 10)
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+
+    /// Markdown file for the product page instead of README.md (default: `readme_path` from config)
+    #[arg(long, value_name = "PATH", conflicts_with = "workspace")]
+    pub readme: Option<std::path::PathBuf>,
+
+    /// Product display name (default: from build file or directory name)
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Product description, 50+ chars (default: from build file)
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// SPDX license expression, e.g. MIT or "Apache-2.0 OR MIT" (default: detected from the LICENSE file, else MIT)
+    #[arg(long)]
+    pub license: Option<String>,
+
+    #[command(flatten)]
+    pub visibility: VisibilityArgs,
+
+    #[command(flatten)]
+    pub links: LinkArgs,
+
+    #[command(flatten)]
+    pub git_tag: GitTagArgs,
+
+    /// Publish under a team namespace you belong to (default: your user, or the manifest's team)
+    #[arg(long)]
+    pub team: Option<String>,
+
+    /// Publish even if the git working tree has uncommitted or untracked files
+    #[arg(long)]
+    pub allow_dirty: bool,
+
+    /// Package the tree at a git tag or commit instead of the working tree
+    #[arg(long = "ref", value_name = "REF")]
+    pub git_ref: Option<String>,
+
+    /// Include the git history as a bundle (.baro/history.bundle) in the archive: the
+    /// last 100 commits at --ref (default HEAD) that touch the product, limited to its directory
+    #[arg(long)]
+    pub with_git_history: bool,
+
+    /// Product directory to publish (default: current directory)
+    #[arg(long, value_name = "DIR")]
+    pub path: Option<std::path::PathBuf>,
+
+    /// Publish every member listed in .baro/workspace.json, each at its own version
+    #[arg(long, conflicts_with_all = ["version", "name", "description", "changelog"])]
+    pub workspace: bool,
+
+    /// Publish as a preview: hidden from browse and forkable only via a shareable link
+    /// until `baro promote <version>`
+    #[arg(long)]
+    pub preview: bool,
+
+    /// Extra file to publish next to the source archive, e.g. a prebuilt binary (repeatable)
+    #[arg(long = "asset", value_name = "PATH")]
+    pub assets: Vec<std::path::PathBuf>,
+
+    /// Result format: text, or json (one object per published release on stdout; progress goes to stderr)
+    #[arg(long, value_enum, default_value = "text")]
+    pub output: OutputFormat,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Sort keys for `baro products`, highest first.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum ProductSort {
+    Remakes,
+    Forks,
+    Rating,
+    Updated,
+}
+
+#[derive(Args)]
+pub struct SearchArgs {
+    /// Search query
+    #[arg(required_unless_present_any = ["saved", "category", "tags", "publisher", "remakes_of"])]
+    pub query: Option<String>,
+
+    /// Filter by category slug
+    #[arg(long)]
+    pub category: Option<String>,
+
+    /// Only products tagged with TAG (repeatable; all must match)
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+
+    /// Only products published by USER
+    #[arg(long, value_name = "USER")]
+    pub publisher: Option<String>,
+
+    /// Hide products published by USER (repeatable)
+    #[arg(long = "exclude-publisher", value_name = "USER", conflicts_with = "publisher")]
+    pub exclude_publishers: Vec<String>,
+
+    /// Only products rated at least this on average (1-5)
+    #[arg(long, value_name = "RATING")]
+    pub min_rating: Option<f64>,
+
+    /// Only products forked at least N times
+    #[arg(long, value_name = "N")]
+    pub min_forks: Option<u64>,
+
+    /// Only published remakes of user/product, to compare its variants
+    #[arg(long, value_name = "USER/PRODUCT")]
+    pub remakes_of: Option<String>,
+
+    /// Sort order: recent, downloads, rating
+    #[arg(long, default_value = "recent")]
+    pub sort: String,
+
+    /// Max results to show
+    #[arg(long, default_value = "20")]
+    pub limit: u32,
+
+    /// Page of results to start from (pages are --limit long)
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub page: u32,
+
+    /// Show the ranking components (relevance, recency, forks, rating) behind each result
+    #[arg(long)]
+    pub explain_ranking: bool,
+
+    /// Save this query and its filters under a name
+    #[arg(long, value_name = "NAME", conflicts_with = "saved")]
+    pub save: Option<String>,
+
+    /// Re-run a saved search by name
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["query", "category", "tags", "publisher", "exclude_publishers", "min_rating", "min_forks", "remakes_of"])]
+    pub saved: Option<String>,
+
+    /// With --saved: mark results that are new since the last run
+    #[arg(long)]
+    pub diff: bool,
+
+    /// Browse results in a full-screen list with a README preview; fork or open the selection
+    #[arg(long, short = 'i', conflicts_with_all = ["explain_ranking", "diff", "table"])]
+    pub interactive: bool,
+
+    /// One aligned row per result
+    #[arg(long, conflicts_with = "explain_ranking")]
+    pub table: bool,
+
+    /// Answer from results cached by earlier searches, without the network
+    #[arg(long, conflicts_with_all = ["interactive", "remakes_of"])]
+    pub offline: bool,
+
+    /// With --table: don't cut columns to the terminal width
+    #[arg(long, requires = "table")]
+    pub wide: bool,
+}
+
+#[derive(Args)]
+pub struct ForkArgs {
+    /// Product identifier: user/product or user/product@version
+    pub product: String,
+
+    /// Destination directory, e.g. `.` for the current (empty) directory (default: product slug)
+    #[arg(value_name = "DEST", conflicts_with = "dir")]
+    pub dest: Option<String>,
+
+    /// Same as DEST
+    #[arg(long)]
+    pub dir: Option<String>,
+
+    /// Access token from a preview link (`baro publish --preview`)
+    #[arg(long)]
+    pub preview_token: Option<String>,
+
+    /// Also download the release's extra assets (prebuilt binaries) into .baro/assets/
+    #[arg(long)]
+    pub assets: bool,
+
+    /// Replace an existing destination; the old tree is removed only after the new one is in place
+    #[arg(long)]
+    pub force: bool,
+
+    /// Show the release's file tree, sizes and README excerpt without writing anything
+    #[arg(long, conflicts_with_all = ["dest", "dir", "assets", "force"])]
+    pub list: bool,
+
+    /// Refuse to extract a release that isn't signed by a registered key of its publisher
+    #[arg(long)]
+    pub require_signature: bool,
+
+    /// Don't fetch the dependencies the product declares (normally unpacked into vendor/)
+    #[arg(long)]
+    pub no_deps: bool,
+
+    /// Run the product's setup script without asking
+    #[arg(long, conflicts_with_all = ["no_setup", "list"])]
+    pub run_setup: bool,
+
+    /// Never run the product's setup script
+    #[arg(long)]
+    pub no_setup: bool,
+}
+
+#[derive(Args)]
+pub struct RemakeArgs {
+    /// Version string (e.g., "1.0.0")
+    #[arg(long)]
+    pub version: String,
+
+    /// Product slug (must differ from original if you own a product with the same slug)
+    #[arg(long)]
+    pub slug: Option<String>,
+
+    /// Changelog describing what changed and why
+    #[arg(long)]
+    pub changelog: Option<String>,
+
+    /// Category slug (e.g., developer-tools, productivity, ai-agents)
+    #[arg(long)]
+    pub category: String,
+
+    /// Product display name (default: from build file or directory name)
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Product description, 50+ chars (default: from build file)
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// SPDX license expression, e.g. MIT or "Apache-2.0 OR MIT" (default: detected from the LICENSE file, else MIT)
+    #[arg(long)]
+    pub license: Option<String>,
+
+    #[command(flatten)]
+    pub visibility: VisibilityArgs,
+
+    #[command(flatten)]
+    pub git_tag: GitTagArgs,
+}
+
+#[derive(Args, Clone)]
+pub struct GitTagArgs {
+    /// Create a v<version> git tag after a successful publish (default: from config)
+    #[arg(long)]
+    pub git_tag: bool,
+
+    /// Create the tag and push it to the origin remote
+    #[arg(long)]
+    pub push_tag: bool,
+}
+
+#[derive(Args, Clone)]
+pub struct LinkArgs {
+    /// Project homepage URL (default: from Cargo.toml/package.json)
+    #[arg(long, value_name = "URL")]
+    pub homepage: Option<String>,
+
+    /// Source repository URL (default: from Cargo.toml/package.json)
+    #[arg(long, value_name = "URL")]
+    pub repository: Option<String>,
+
+    /// Documentation URL (default: from Cargo.toml)
+    #[arg(long, value_name = "URL")]
+    pub docs: Option<String>,
+}
+
+impl LinkArgs {
+    pub fn links(self) -> crate::types::ProductLinks {
+        crate::types::ProductLinks { homepage: self.homepage, repository: self.repository, documentation: self.docs }
+    }
+}
+
+#[derive(Args, Clone)]
+pub struct VisibilityArgs {
+    /// Only you can see and fork the product
+    #[arg(long, conflicts_with = "unlisted")]
+    pub private: bool,
+
+    /// Reachable by link, but hidden from search and browse
+    #[arg(long)]
+    pub unlisted: bool,
+}
+
+impl VisibilityArgs {
+    /// The visibility requested on the command line, if any.
+    pub fn level(&self) -> Option<Visibility> {
+        if self.private {
+            Some(Visibility::Private)
+        } else if self.unlisted {
+            Some(Visibility::Unlisted)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum Visibility {
+    Public,
+    Unlisted,
+    Private,
+}
+
+impl Visibility {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Visibility::Public => "public",
+            Visibility::Unlisted => "unlisted",
+            Visibility::Private => "private",
+        }
+    }
+}
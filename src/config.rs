@@ -1,4 +1,5 @@
 use anyhow::Result;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 const DEFAULT_API_BASE: &str = "https://baro-sync.com";
@@ -14,6 +15,57 @@ pub fn config_dir() -> Result<PathBuf> {
     Ok(dir)
 }
 
+/// User preferences from `~/.config/baro/config.json`. Every field is optional;
+/// command-line flags take precedence.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct Settings {
+    /// Create a `v<version>` git tag after each successful publish
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_tag: Option<bool>,
+    /// Also push the tag to the `origin` remote
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_tag_push: Option<bool>,
+    /// Endpoint for CLI update checks (default: GitHub releases). Self-hosted
+    /// registries return `{ "latest_version", "minimum_version", "install_hint" }`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_check_url: Option<String>,
+    /// Send a coarse client hint (OS family, CPU architecture) with fork downloads
+    /// so publishers see aggregate platform stats. Off unless set to `true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub share_client_hints: Option<bool>,
+    /// Product-page README to publish instead of the detected README, relative
+    /// to the product root (e.g. `docs/MARKETPLACE.md`). `--readme` overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readme_path: Option<String>,
+    /// Named searches for `baro search --saved <name>`, e.g.
+    /// `"searches": { "my-ai-agents": { "query": "agent", "category": "ai-agents", "sort": "rating" } }`.
+    /// `--save` writes here too, with each search's last results. Each is parsed
+    /// when used, so a malformed entry can't discard the other settings.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub searches: BTreeMap<String, serde_json::Value>,
+}
+
+/// The opt-in download hint, e.g. `os=linux; arch=x86_64`. `None` unless enabled.
+/// The CLI version already travels in `X-Baro-CLI-Version`.
+pub fn client_hint() -> Option<String> {
+    settings().share_client_hints.unwrap_or(false).then(|| {
+        format!("os={}; arch={}", std::env::consts::OS, std::env::consts::ARCH)
+    })
+}
+
+pub fn config_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("config.json"))
+}
+
+/// Load settings, falling back to defaults when the file is missing or malformed.
+pub fn settings() -> Settings {
+    config_path()
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
 pub fn credentials_path() -> Result<PathBuf> {
     Ok(config_dir()?.join("credentials.json"))
 }
@@ -22,6 +74,17 @@ pub fn api_base_url() -> String {
     std::env::var("BARO_API_URL").unwrap_or_else(|_| DEFAULT_API_BASE.to_string())
 }
 
+/// Public page for a product on the configured registry.
+pub fn product_url(namespace: &str, slug: &str) -> String {
+    format!("{}/{}/{}", api_base_url().trim_end_matches('/'), namespace, slug)
+}
+
+/// Admin-scoped API token for `baro mod`, from `BARO_ADMIN_TOKEN`.
+#[cfg(feature = "moderation")]
+pub fn admin_token() -> Option<String> {
+    std::env::var("BARO_ADMIN_TOKEN").ok().filter(|t| !t.is_empty())
+}
+
 pub fn supabase_url() -> String {
     std::env::var("BARO_SUPABASE_URL").unwrap_or_else(|_| DEFAULT_SUPABASE_URL.to_string())
 }
@@ -30,3 +93,18 @@ pub fn supabase_anon_key() -> String {
     std::env::var("BARO_SUPABASE_ANON_KEY")
         .unwrap_or_else(|_| DEFAULT_SUPABASE_ANON_KEY.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_search_keeps_other_settings() {
+        let settings: Settings = serde_json::from_str(
+            r#"{"readme_path": "docs/PAGE.md", "searches": {"bad": {"limit": "20"}, "ai": {"category": "ai-agents"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(settings.readme_path.as_deref(), Some("docs/PAGE.md"));
+        assert_eq!(settings.searches.len(), 2);
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+
+/// Flags whose values never go into a crash report.
+const SECRET_FLAGS: &[&str] = &["--preview-token", "--token", "--password", "--secret"];
+
+/// `~/.config/baro/crash-reports/`
+pub fn reports_dir() -> Result<PathBuf> {
+    Ok(config::config_dir()?.join("crash-reports"))
+}
+
+/// Replace the panic's backtrace dump with a short message and a report file
+/// (version, OS, command line, panic message, backtrace) under `reports_dir()`.
+pub fn install() {
+    std::panic::set_hook(Box::new(|info| {
+        let message = match info.payload().downcast_ref::<&str>() {
+            Some(s) => s.to_string(),
+            None => info.payload().downcast_ref::<String>().cloned().unwrap_or_else(|| "unknown panic".to_string()),
+        };
+        let location = info.location().map(|l| format!("{}:{}", l.file(), l.line())).unwrap_or_default();
+        let args: Vec<String> = std::env::args().collect();
+        let report = render(&message, &location, &args, &std::backtrace::Backtrace::force_capture().to_string());
+
+        eprintln!("baro crashed: {}", redact(&message, home().as_deref()));
+        match save(&report) {
+            Ok(path) => {
+                eprintln!("A crash report was saved to {}", path.display());
+                eprintln!("Please send it with: baro feedback --crash-file \"{}\"", path.display());
+            }
+            Err(e) => eprintln!("Could not save a crash report ({}):\n{}", e, report),
+        }
+    }));
+}
+
+fn home() -> Option<String> {
+    dirs::home_dir().map(|h| h.to_string_lossy().to_string())
+}
+
+/// The report text, with secrets and the home directory redacted.
+fn render(message: &str, location: &str, args: &[String], backtrace: &str) -> String {
+    let home = home();
+    let text = format!(
+        "baro {}\nos: {} {}\ntime: {}\ncommand: {}\npanic: {}\nat: {}\n\nbacktrace:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        chrono::Utc::now().to_rfc3339(),
+        redact_args(args).join(" "),
+        message,
+        location,
+        backtrace
+    );
+    redact(&text, home.as_deref())
+}
+
+fn save(report: &str) -> Result<PathBuf> {
+    let dir = reports_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("crash-{}.txt", chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ")));
+    std::fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// Hide the values of `SECRET_FLAGS` (`--flag value` and `--flag=value`).
+fn redact_args(args: &[String]) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+    let mut hide_next = false;
+    for arg in args {
+        if hide_next {
+            out.push("<redacted>".to_string());
+            hide_next = false;
+        } else if let Some((flag, _)) = arg.split_once('=').filter(|(f, _)| SECRET_FLAGS.contains(f)) {
+            out.push(format!("{}=<redacted>", flag));
+        } else {
+            hide_next = SECRET_FLAGS.contains(&arg.as_str());
+            out.push(arg.clone());
+        }
+    }
+    out
+}
+
+/// Replace the home directory with `~`, so reports don't carry the user name.
+fn redact(text: &str, home: Option<&str>) -> String {
+    match home.filter(|h| h.len() > 1) {
+        Some(home) => text.replace(home, "~"),
+        None => text.to_string(),
+    }
+}
+
+/// The most recent report, for `baro feedback --crash` without a path.
+pub fn latest_report() -> Result<PathBuf> {
+    let dir = reports_dir()?;
+    newest_in(&dir).ok_or_else(|| anyhow::anyhow!("No crash reports in {}", dir.display()))
+}
+
+fn newest_in(dir: &Path) -> Option<PathBuf> {
+    // Names embed a sortable UTC timestamp
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("crash-")))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn secrets_and_home_are_redacted() {
+        let args = strings(&["baro", "fork", "a/b", "--preview-token", "s3cret", "--token=abc", "--dir", "x"]);
+        assert_eq!(
+            redact_args(&args),
+            strings(&["baro", "fork", "a/b", "--preview-token", "<redacted>", "--token=<redacted>", "--dir", "x"])
+        );
+        assert_eq!(redact("at /home/alice/p/src", Some("/home/alice")), "at ~/p/src");
+        assert_eq!(redact("at /x", Some("/")), "at /x");
+    }
+
+    #[test]
+    fn newest_report_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(newest_in(dir.path()).is_none());
+        for name in ["crash-20240101T000000.000Z.txt", "crash-20240301T000000.000Z.txt", "notes.txt"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        assert_eq!(newest_in(dir.path()).unwrap().file_name().unwrap(), "crash-20240301T000000.000Z.txt");
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+
+/// One line of a line-by-line comparison.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Edit<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Largest changed region (old lines × new lines) diffed line by line;
+/// beyond it a file is only reported as modified.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Files that differ between two trees (path → contents).
+#[derive(Debug, Default, PartialEq)]
+pub struct TreeDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+impl TreeDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// "12 files modified, 3 added", or "no changes"
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = [(self.modified.len(), "modified"), (self.added.len(), "added"), (self.removed.len(), "removed")]
+            .iter()
+            .filter(|(n, _)| *n > 0)
+            .enumerate()
+            .map(|(i, (n, label))| match (i, n) {
+                (0, 1) => format!("1 file {}", label),
+                (0, n) => format!("{} files {}", n, label),
+                (_, n) => format!("{} {}", n, label),
+            })
+            .collect();
+        if parts.is_empty() {
+            "no changes".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// Compare `old` and `new` by path and content (or hash). Paths are sorted.
+pub fn compare_trees<V: PartialEq>(old: &BTreeMap<String, V>, new: &BTreeMap<String, V>) -> TreeDiff {
+    let mut diff = TreeDiff::default();
+    for (path, content) in new {
+        match old.get(path) {
+            None => diff.added.push(path.clone()),
+            Some(previous) if previous != content => diff.modified.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+    diff.removed = old.keys().filter(|p| !new.contains_key(*p)).cloned().collect();
+    diff
+}
+
+/// Text content, or None for binary files (NUL bytes or invalid UTF-8).
+pub fn as_text(bytes: &[u8]) -> Option<&str> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    std::str::from_utf8(bytes).ok()
+}
+
+/// Line diff of `old` → `new` (longest common subsequence, after trimming the
+/// shared prefix and suffix). None when the changed region is too large.
+pub fn line_diff<'a>(old: &'a str, new: &'a str) -> Option<Vec<Edit<'a>>> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(x, y)| x == y).count();
+    let (mid_a, mid_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    if (mid_a.len() + 1).saturating_mul(mid_b.len() + 1) > MAX_DIFF_CELLS {
+        return None;
+    }
+
+    // lcs[i][j]: common subsequence length of mid_a[i..] and mid_b[j..]
+    let width = mid_b.len() + 1;
+    let mut lcs = vec![0u32; (mid_a.len() + 1) * width];
+    for i in (0..mid_a.len()).rev() {
+        for j in (0..mid_b.len()).rev() {
+            lcs[i * width + j] = if mid_a[i] == mid_b[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut edits: Vec<Edit> = a[..prefix].iter().map(|l| Edit::Same(l)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < mid_a.len() || j < mid_b.len() {
+        if i < mid_a.len() && j < mid_b.len() && mid_a[i] == mid_b[j] {
+            edits.push(Edit::Same(mid_a[i]));
+            i += 1;
+            j += 1;
+        } else if i < mid_a.len() && (j == mid_b.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+            // Removals before additions, as diff(1) prints them
+            edits.push(Edit::Removed(mid_a[i]));
+            i += 1;
+        } else {
+            edits.push(Edit::Added(mid_b[j]));
+            j += 1;
+        }
+    }
+    edits.extend(a[a.len() - suffix..].iter().map(|l| Edit::Same(l)));
+    Some(edits)
+}
+
+/// (lines added, lines removed)
+pub fn counts(edits: &[Edit]) -> (usize, usize) {
+    edits.iter().fold((0, 0), |(add, del), e| match e {
+        Edit::Added(_) => (add + 1, del),
+        Edit::Removed(_) => (add, del + 1),
+        Edit::Same(_) => (add, del),
+    })
+}
+
+/// Unified diff text with `context` lines around each change; empty when nothing changed.
+pub fn unified(edits: &[Edit], old_name: &str, new_name: &str, context: usize) -> String {
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (i, _) in edits.iter().enumerate().filter(|(_, e)| !matches!(e, Edit::Same(_))) {
+        let (start, end) = (i.saturating_sub(context), (i + context + 1).min(edits.len()));
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    // Line numbers (0-based) in each file at every edit position
+    let mut positions = Vec::with_capacity(edits.len());
+    let (mut old_line, mut new_line) = (0, 0);
+    for e in edits {
+        positions.push((old_line, new_line));
+        match e {
+            Edit::Same(_) => (old_line, new_line) = (old_line + 1, new_line + 1),
+            Edit::Removed(_) => old_line += 1,
+            Edit::Added(_) => new_line += 1,
+        }
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for (start, end) in hunks {
+        let hunk = &edits[start..end];
+        let old_len = hunk.iter().filter(|e| !matches!(e, Edit::Added(_))).count();
+        let new_len = hunk.iter().filter(|e| !matches!(e, Edit::Removed(_))).count();
+        let (old_start, new_start) = positions[start];
+        // An empty side is numbered by the line before it, as in diff(1)
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start + usize::from(old_len > 0),
+            old_len,
+            new_start + usize::from(new_len > 0),
+            new_len
+        ));
+        for e in hunk {
+            let (mark, line) = match e {
+                Edit::Same(l) => (' ', l),
+                Edit::Removed(l) => ('-', l),
+                Edit::Added(l) => ('+', l),
+            };
+            out.push(mark);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(files: &[(&str, &str)]) -> BTreeMap<String, Vec<u8>> {
+        files.iter().map(|(p, c)| (p.to_string(), c.as_bytes().to_vec())).collect()
+    }
+
+    #[test]
+    fn compares_trees() {
+        let old = tree(&[("a.txt", "1"), ("b.txt", "2"), ("c.txt", "3")]);
+        let new = tree(&[("a.txt", "1"), ("b.txt", "changed"), ("d.txt", "4")]);
+        let diff = compare_trees(&old, &new);
+        assert_eq!(diff.added, vec!["d.txt"]);
+        assert_eq!(diff.removed, vec!["c.txt"]);
+        assert_eq!(diff.modified, vec!["b.txt"]);
+        assert_eq!(diff.summary(), "1 file modified, 1 added, 1 removed");
+        assert!(compare_trees(&old, &old).is_empty());
+        assert_eq!(compare_trees(&old, &old).summary(), "no changes");
+    }
+
+    #[test]
+    fn line_diff_and_unified_output() {
+        let old = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\nnine\n";
+        let new = "one\ntwo\nthree\nFOUR\nfive\nsix\nseven\neight\nnine\nten\n";
+        let edits = line_diff(old, new).unwrap();
+        assert_eq!(counts(&edits), (2, 1));
+        assert_eq!(
+            unified(&edits, "a/f", "b/f", 1),
+            "--- a/f\n+++ b/f\n@@ -3,3 +3,3 @@\n three\n-four\n+FOUR\n five\n@@ -9,1 +9,2 @@\n nine\n+ten\n"
+        );
+        assert_eq!(unified(&line_diff(old, old).unwrap(), "a", "b", 3), "");
+    }
+
+    #[test]
+    fn diff_edge_cases() {
+        let edits = line_diff("", "new\n").unwrap();
+        assert_eq!(edits, vec![Edit::Added("new")]);
+        assert_eq!(unified(&edits, "a", "b", 3), "--- a\n+++ b\n@@ -0,0 +1,1 @@\n+new\n");
+        assert_eq!(as_text(b"bin\0ary"), None);
+        assert_eq!(as_text(b"text"), Some("text"));
+    }
+}
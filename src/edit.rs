@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+
+/// Product metadata that `baro edit` can change, as shown in the editor.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProductFields {
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub license: String,
+}
+
+const HEADER: &str = "\
+# Edit the product metadata below, then save and close the editor.
+# Lines starting with # are ignored. Leave the file unchanged to cancel.
+# Write a line break inside a value as \\n (and a backslash as \\\\).
+";
+
+/// Keep a value on one line: `\n` and `\r` are written escaped, as are backslashes.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\r', "\\r").replace('\n', "\\n")
+}
+
+/// Undo `escape`. Any other backslash is kept as typed.
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('r')) => {
+                out.push('\r');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// The editor buffer: one `key: value` line per field.
+pub fn render(fields: &ProductFields) -> String {
+    format!(
+        "{}\nname: {}\ndescription: {}\ncategory: {}\nlicense: {}\n",
+        HEADER,
+        escape(&fields.name),
+        escape(&fields.description),
+        escape(&fields.category),
+        escape(&fields.license)
+    )
+}
+
+/// Parse an edited buffer. Every field must be present exactly once; values
+/// are trimmed.
+pub fn parse(text: &str) -> Result<ProductFields> {
+    let mut fields = ProductFields::default();
+    let mut seen: Vec<&str> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Line {}: expected `key: value`", i + 1))?;
+        let (key, value) = (key.trim(), unescape(value.trim()).trim().to_string());
+        let slot = match key {
+            "name" => &mut fields.name,
+            "description" => &mut fields.description,
+            "category" => &mut fields.category,
+            "license" => &mut fields.license,
+            _ => return Err(anyhow::anyhow!("Line {}: unknown field '{}'", i + 1, key)),
+        };
+        if seen.contains(&key) {
+            return Err(anyhow::anyhow!("Line {}: '{}' appears twice", i + 1, key));
+        }
+        seen.push(key);
+        *slot = value;
+    }
+    for key in ["name", "description", "category", "license"] {
+        if !seen.contains(&key) {
+            return Err(anyhow::anyhow!("Missing field '{}'", key));
+        }
+    }
+    Ok(fields)
+}
+
+/// Open `fields` in `$VISUAL` / `$EDITOR` (default `vi`) and return the edited values.
+pub fn in_editor(fields: &ProductFields) -> Result<ProductFields> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let path = std::env::temp_dir().join(format!("baro-edit-{}.txt", uuid::Uuid::new_v4()));
+    std::fs::write(&path, render(fields))?;
+
+    // The editor setting may carry arguments (e.g. `code --wait`)
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to start editor '{}'. Set $EDITOR, or pass the fields as flags.", editor));
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    if !status?.success() {
+        return Err(anyhow::anyhow!("Editor exited with an error; nothing changed"));
+    }
+    parse(&edited?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_parse_roundtrip() {
+        let fields = ProductFields {
+            name: "Demo: The Tool".to_string(),
+            description: "Does things".to_string(),
+            category: "productivity".to_string(),
+            license: "Apache-2.0 OR MIT".to_string(),
+        };
+        assert_eq!(parse(&render(&fields)).unwrap(), fields);
+    }
+
+    #[test]
+    fn multi_line_description_roundtrip() {
+        let fields = ProductFields {
+            name: "Demo".to_string(),
+            description: "First paragraph.\r\n\n- a list item\n- C:\\path\\n".to_string(),
+            category: "productivity".to_string(),
+            license: "MIT".to_string(),
+        };
+        let buffer = render(&fields);
+        assert!(buffer.contains("description: First paragraph.\\r\\n\\n- a list item\\n- C:\\\\path\\\\n\n"));
+        assert_eq!(parse(&buffer).unwrap(), fields);
+    }
+
+    #[test]
+    fn parse_rejects_bad_buffers() {
+        assert!(parse("name: a\ndescription: b\ncategory: c").unwrap_err().to_string().contains("license"));
+        assert!(parse("name: a\nname: b").unwrap_err().to_string().contains("twice"));
+        assert!(parse("title: a").unwrap_err().to_string().contains("unknown field"));
+        assert!(parse("just text").is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use serde::Serialize;
+
+/// A failed registry or storage request. `Display` is just the message, so
+/// text output reads the same as a plain error; `--error-format json` also
+/// reports the status, code, hint and request ID.
+#[derive(Debug)]
+pub struct RegistryError {
+    pub status: u16,
+    /// Machine-readable code from the registry's error body, if it sent one
+    pub code: Option<String>,
+    pub message: String,
+    pub hint: Option<String>,
+    /// `X-Request-Id` of the failed response, for support requests
+    pub request_id: Option<String>,
+}
+
+impl std::fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+impl RegistryError {
+    /// An error for a bare status, e.g. from a storage upload with no error body.
+    pub fn status(status: reqwest::StatusCode, message: String) -> Self {
+        RegistryError { status: status.as_u16(), code: None, message, hint: None, request_id: None }
+    }
+
+    /// `message` for a failed response, picking up its request ID.
+    pub fn from_response(resp: &reqwest::Response, message: String) -> Self {
+        let request_id = resp
+            .headers()
+            .get("x-request-id")
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        RegistryError { status: resp.status().as_u16(), code: None, message, hint: None, request_id }
+    }
+}
+
+/// The final error object printed to stderr under `--error-format json`.
+/// Bump `schema_version` on any breaking change to this shape.
+#[derive(Debug, Serialize)]
+pub struct ErrorReport {
+    pub schema_version: u32,
+    pub code: String,
+    pub message: String,
+    pub hint: Option<String>,
+    /// Whether running the same command again may succeed
+    pub retryable: bool,
+    pub status: Option<u16>,
+    pub request_id: Option<String>,
+}
+
+/// The HTTP status of the first registry error in `err`'s chain.
+pub fn status(err: &anyhow::Error) -> Option<u16> {
+    err.chain().find_map(|cause| cause.downcast_ref::<RegistryError>()).map(|e| e.status)
+}
+
+/// Stable code for an HTTP status the registry didn't label itself.
+fn status_code(status: u16) -> &'static str {
+    match status {
+        400 | 422 => "invalid_request",
+        401 => "unauthorized",
+        403 => "forbidden",
+        404 => "not_found",
+        409 => "conflict",
+        413 => "too_large",
+        429 => "rate_limited",
+        500..=599 => "server_error",
+        _ => "http_error",
+    }
+}
+
+/// Classify `err` by the first typed error in its chain.
+pub fn report(err: &anyhow::Error) -> ErrorReport {
+    let message = format!("{:#}", err);
+    let mut report = ErrorReport {
+        schema_version: 1,
+        code: "error".to_string(),
+        message,
+        hint: None,
+        retryable: false,
+        status: None,
+        request_id: None,
+    };
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<RegistryError>() {
+            report.code = e.code.clone().unwrap_or_else(|| status_code(e.status).to_string());
+            report.hint = e.hint.clone();
+            report.retryable = e.status == 429 || e.status >= 500;
+            report.status = Some(e.status);
+            report.request_id = e.request_id.clone();
+            if e.status == 401 && report.hint.is_none() {
+                report.hint = Some("Run `baro login`".to_string());
+            }
+            return report;
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            report.code = "network".to_string();
+            report.retryable = e.is_connect() || e.is_timeout() || e.is_request() || e.is_body();
+            report.status = e.status().map(|s| s.as_u16());
+            return report;
+        }
+        if cause.downcast_ref::<std::io::Error>().is_some() {
+            report.code = "io".to_string();
+            return report;
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    fn registry(status: u16, code: Option<&str>) -> anyhow::Error {
+        anyhow::Error::new(RegistryError {
+            status,
+            code: code.map(String::from),
+            message: "Product not found".to_string(),
+            hint: None,
+            request_id: Some("req_123".to_string()),
+        })
+    }
+
+    #[test]
+    fn registry_errors_keep_code_and_request_id() {
+        let err = Err::<(), _>(registry(404, None)).context("Fetching alice/demo").unwrap_err();
+        let r = report(&err);
+        assert_eq!(r.code, "not_found");
+        assert_eq!(r.message, "Fetching alice/demo: Product not found");
+        assert_eq!(r.request_id.as_deref(), Some("req_123"));
+        assert!(!r.retryable);
+        assert_eq!(status(&err), Some(404));
+        assert_eq!(status(&anyhow::anyhow!("plain")), None);
+
+        assert_eq!(report(&registry(409, Some("version_exists"))).code, "version_exists");
+        assert!(report(&registry(503, None)).retryable);
+        assert_eq!(report(&registry(401, None)).hint.as_deref(), Some("Run `baro login`"));
+    }
+
+    #[test]
+    fn untyped_errors_are_generic() {
+        let r = report(&anyhow::anyhow!("Invalid slug 'X'"));
+        assert_eq!((r.code.as_str(), r.retryable, r.status), ("error", false, None));
+        let io = anyhow::Error::new(std::io::Error::new(std::io::ErrorKind::NotFound, "missing"));
+        assert_eq!(report(&io).code, "io");
+    }
+}
@@ -0,0 +1,369 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Run `git` in `dir` and return trimmed stdout. Fails if git is missing or exits non-zero.
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("git {} failed: {}", args.join(" "), stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+/// Whether `dir` is inside a git work tree. False when git is not installed.
+pub fn is_repo(dir: &Path) -> bool {
+    git(dir, &["rev-parse", "--is-inside-work-tree"])
+        .map(|out| out == "true")
+        .unwrap_or(false)
+}
+
+/// Uncommitted changes and untracked (non-ignored) files under `dir`,
+/// one `git status --porcelain` line each. Empty when clean.
+pub fn dirty_files(dir: &Path) -> Result<Vec<String>> {
+    let out = git(dir, &["status", "--porcelain", "--untracked-files=all", "--", "."])?;
+    Ok(out.lines().map(|l| l.to_string()).collect())
+}
+
+/// Full commit hash `rev` resolves to.
+pub fn rev_parse(dir: &Path, rev: &str) -> Result<String> {
+    git(dir, &["rev-parse", "--verify", &format!("{}^{{commit}}", rev)])
+}
+
+/// URL of the `origin` remote, if one is configured.
+pub fn origin_url(dir: &Path) -> Option<String> {
+    git(dir, &["remote", "get-url", "origin"]).ok().filter(|u| !u.is_empty())
+}
+
+/// Whether a tag named `name` already exists.
+pub fn tag_exists(dir: &Path, name: &str) -> bool {
+    git(dir, &["rev-parse", "-q", "--verify", &format!("refs/tags/{}", name)]).is_ok()
+}
+
+/// Create an annotated tag pointing at `target` (a commit-ish such as `HEAD`).
+pub fn create_tag(dir: &Path, name: &str, message: &str, target: &str) -> Result<()> {
+    git(dir, &["tag", "-a", name, "-m", message, target]).map(|_| ())
+}
+
+/// Subjects of commits reachable from `until` but not `since`, newest first,
+/// limited to the part of the repository under `dir`.
+pub fn commit_subjects(dir: &Path, since: Option<&str>, until: &str) -> Result<Vec<String>> {
+    let range = match since {
+        Some(since) => format!("{}..{}", since, until),
+        None => until.to_string(),
+    };
+    let out = git(dir, &["log", "--no-merges", "--format=%s", &range, "--", "."])?;
+    Ok(out.lines().filter(|l| !l.trim().is_empty()).map(|l| l.to_string()).collect())
+}
+
+/// The last `max_commits` first-parent commits at `git_ref` that touch `dir`, oldest first.
+fn history_commits(dir: &Path, git_ref: &str, max_commits: usize) -> Result<Vec<String>> {
+    let max = format!("--max-count={}", max_commits);
+    let out = git(dir, &["rev-list", "--reverse", "--first-parent", &max, git_ref, "--", "."])?;
+    Ok(out.lines().map(|l| l.to_string()).collect())
+}
+
+/// Every path under `dir` that appears in the history `create_bundle` writes:
+/// the oldest included tree plus everything added, changed or deleted since,
+/// relative to `dir`.
+pub fn history_paths(dir: &Path, git_ref: &str, max_commits: usize) -> Result<Vec<String>> {
+    let commits = history_commits(dir, git_ref, max_commits)?;
+    let Some(oldest) = commits.first() else { return Ok(Vec::new()) };
+    let range = format!("{}..{}", oldest, git_ref);
+    let changed = git(
+        dir,
+        &["log", "--format=", "--name-only", "--no-renames", "--relative", "--first-parent", &range, "--", "."],
+    )?;
+    // ls-tree lists paths relative to, and limited to, the current directory
+    let initial = git(dir, &["ls-tree", "-r", "--name-only", oldest])?;
+    let mut paths: Vec<String> = changed.lines().chain(initial.lines()).filter(|l| !l.is_empty()).map(|l| l.to_string()).collect();
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Write a `git bundle` to `dest` holding a cleaned, shallow copy of the history
+/// at `git_ref`: its last `max_commits` first-parent commits touching `dir`,
+/// rewritten so each commit holds only `dir`'s subtree (the rest of the
+/// repository, and older history, stays out). Authors, dates and messages are kept.
+pub fn create_bundle(dir: &Path, git_ref: &str, max_commits: usize, dest: &Path) -> Result<()> {
+    let dest = dest.to_str().ok_or_else(|| anyhow::anyhow!("Path contains invalid UTF-8"))?;
+    let commits = history_commits(dir, git_ref, max_commits)?;
+    if commits.is_empty() {
+        return Err(anyhow::anyhow!("No commits at {} touch this directory", git_ref));
+    }
+    let prefix = git(dir, &["rev-parse", "--show-prefix"])?;
+    let objects = git(dir, &["rev-parse", "--path-format=absolute", "--git-common-dir"])?;
+
+    // A scratch repository that borrows the source's objects, so rewriting
+    // never writes into the user's repository
+    let scratch = std::env::temp_dir().join(format!("baro-history-{}", uuid::Uuid::new_v4()));
+    let _cleanup = ExportedTree { path: scratch.clone() };
+    let scratch_arg = scratch.to_str().ok_or_else(|| anyhow::anyhow!("Path contains invalid UTF-8"))?;
+    git(&std::env::temp_dir(), &["init", "-q", "--bare", scratch_arg])?;
+    std::fs::write(scratch.join("objects/info/alternates"), format!("{}/objects\n", objects))?;
+
+    let mut parent: Option<String> = None;
+    let mut parent_tree = String::new();
+    for commit in &commits {
+        let tree_ish = if prefix.is_empty() {
+            format!("{}^{{tree}}", commit)
+        } else {
+            format!("{}:{}", commit, prefix.trim_end_matches('/'))
+        };
+        let Ok(tree) = git(dir, &["rev-parse", "--verify", &tree_ish]) else { continue };
+        if tree == parent_tree {
+            continue;
+        }
+        let meta = git(dir, &["log", "-1", "--format=%an%x00%ae%x00%aI%x00%cn%x00%ce%x00%cI%x00%B", commit])?;
+        let fields: Vec<&str> = meta.splitn(7, '\0').collect();
+        let [author, author_email, author_date, committer, committer_email, committer_date, message] = fields[..] else {
+            return Err(anyhow::anyhow!("Unexpected git log output for {}", commit));
+        };
+        let mut args = vec!["commit-tree", tree.as_str(), "-m", message];
+        if let Some(ref p) = parent {
+            args.extend(["-p", p.as_str()]);
+        }
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(&scratch)
+            .env("GIT_AUTHOR_NAME", author)
+            .env("GIT_AUTHOR_EMAIL", author_email)
+            .env("GIT_AUTHOR_DATE", author_date)
+            .env("GIT_COMMITTER_NAME", committer)
+            .env("GIT_COMMITTER_EMAIL", committer_email)
+            .env("GIT_COMMITTER_DATE", committer_date)
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow::anyhow!("git commit-tree failed: {}", stderr.trim()));
+        }
+        parent = Some(String::from_utf8_lossy(&output.stdout).trim().to_string());
+        parent_tree = tree;
+    }
+
+    let head = parent.ok_or_else(|| anyhow::anyhow!("No commits at {} touch this directory", git_ref))?;
+    git(&scratch, &["update-ref", "refs/heads/main", &head])?;
+    git(&scratch, &["symbolic-ref", "HEAD", "refs/heads/main"])?;
+    git(&scratch, &["bundle", "create", "-q", dest, "HEAD", "refs/heads/main"]).map(|_| ())
+}
+
+/// A git tree exported to a temporary directory. Removed on drop.
+pub struct ExportedTree {
+    path: PathBuf,
+}
+
+impl ExportedTree {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ExportedTree {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Export the tree at `git_ref` into a fresh temp directory, limited to the
+/// part of the repository that `dir` covers (so subdirectory products work).
+pub fn export_ref(dir: &Path, git_ref: &str) -> Result<ExportedTree> {
+    let commit = git(dir, &["rev-parse", "--verify", &format!("{}^{{commit}}", git_ref)])
+        .with_context(|| format!("Unknown git ref '{}'", git_ref))?;
+    let prefix = git(dir, &["rev-parse", "--show-prefix"])?;
+    let toplevel = git(dir, &["rev-parse", "--show-toplevel"])?;
+    let tree_ish = if prefix.is_empty() {
+        commit
+    } else {
+        format!("{}:{}", commit, prefix.trim_end_matches('/'))
+    };
+
+    let output = Command::new("git")
+        .args(["archive", "--format=tar", &tree_ish])
+        // Run from the top level: in a subdirectory git would re-apply the prefix
+        .current_dir(&toplevel)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("git archive {} failed: {}", git_ref, stderr.trim()));
+    }
+
+    let path = std::env::temp_dir().join(format!("baro-ref-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&path)?;
+    let exported = ExportedTree { path };
+    tar::Archive::new(output.stdout.as_slice())
+        .unpack(exported.path())
+        .context("Failed to unpack git archive")?;
+    Ok(exported)
+}
+
+/// Push a single tag to the `origin` remote.
+pub fn push_tag(dir: &Path, name: &str) -> Result<()> {
+    git(dir, &["push", "origin", &format!("refs/tags/{}", name)]).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn init_repo(dir: &Path) {
+        git(dir, &["init", "-q"]).unwrap();
+        git(dir, &["config", "user.email", "test@example.com"]).unwrap();
+        git(dir, &["config", "user.name", "Test"]).unwrap();
+    }
+
+    #[test]
+    fn plain_directory_is_not_repo() {
+        let dir = tempdir().unwrap();
+        assert!(!is_repo(dir.path()));
+    }
+
+    #[test]
+    fn clean_after_commit() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        fs::write(dir.path().join("a.txt"), "a").unwrap();
+        git(dir.path(), &["add", "-A"]).unwrap();
+        git(dir.path(), &["commit", "-qm", "init"]).unwrap();
+        assert!(is_repo(dir.path()));
+        assert!(dirty_files(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn reports_modified_and_untracked_but_not_ignored() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        fs::write(dir.path().join("a.txt"), "a").unwrap();
+        fs::write(dir.path().join(".gitignore"), "build/\n").unwrap();
+        git(dir.path(), &["add", "-A"]).unwrap();
+        git(dir.path(), &["commit", "-qm", "init"]).unwrap();
+
+        fs::write(dir.path().join("a.txt"), "changed").unwrap();
+        fs::write(dir.path().join("new.txt"), "new").unwrap();
+        fs::create_dir(dir.path().join("build")).unwrap();
+        fs::write(dir.path().join("build/out"), "x").unwrap();
+
+        let dirty = dirty_files(dir.path()).unwrap();
+        assert_eq!(dirty.len(), 2, "{:?}", dirty);
+        assert!(dirty.iter().any(|l| l.ends_with("a.txt")));
+        assert!(dirty.iter().any(|l| l.ends_with("new.txt")));
+    }
+
+    #[test]
+    fn create_tag_at_head() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        fs::write(dir.path().join("a.txt"), "a").unwrap();
+        git(dir.path(), &["add", "-A"]).unwrap();
+        git(dir.path(), &["commit", "-qm", "init"]).unwrap();
+
+        assert!(!tag_exists(dir.path(), "v1.0.0"));
+        create_tag(dir.path(), "v1.0.0", "Release 1.0.0", "HEAD").unwrap();
+        assert!(tag_exists(dir.path(), "v1.0.0"));
+        assert!(create_tag(dir.path(), "v1.0.0", "again", "HEAD").is_err());
+    }
+
+    #[test]
+    fn export_ref_ignores_working_tree_changes() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        fs::create_dir(dir.path().join("app")).unwrap();
+        fs::write(dir.path().join("app/a.txt"), "committed").unwrap();
+        fs::write(dir.path().join("top.txt"), "top").unwrap();
+        git(dir.path(), &["add", "-A"]).unwrap();
+        git(dir.path(), &["commit", "-qm", "init"]).unwrap();
+        git(dir.path(), &["tag", "v1"]).unwrap();
+        fs::write(dir.path().join("app/a.txt"), "dirty").unwrap();
+        fs::write(dir.path().join("app/new.txt"), "untracked").unwrap();
+
+        let exported = export_ref(&dir.path().join("app"), "v1").unwrap();
+        let out = exported.path().to_path_buf();
+        assert_eq!(fs::read_to_string(out.join("a.txt")).unwrap(), "committed");
+        assert!(!out.join("new.txt").exists());
+        assert!(!out.join("top.txt").exists());
+
+        drop(exported);
+        assert!(!out.exists());
+    }
+
+    #[test]
+    fn commit_subjects_since_tag() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        for (i, msg) in ["feat: first", "fix: second", "feat: third"].iter().enumerate() {
+            fs::write(dir.path().join("a.txt"), i.to_string()).unwrap();
+            git(dir.path(), &["add", "-A"]).unwrap();
+            git(dir.path(), &["commit", "-qm", msg]).unwrap();
+            if i == 0 {
+                git(dir.path(), &["tag", "v1.0.0"]).unwrap();
+            }
+        }
+        assert_eq!(
+            commit_subjects(dir.path(), Some("v1.0.0"), "HEAD").unwrap(),
+            vec!["feat: third", "fix: second"]
+        );
+        assert_eq!(commit_subjects(dir.path(), None, "HEAD").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn history_paths_include_deleted_files() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        fs::write(dir.path().join(".env"), "SECRET=1").unwrap();
+        fs::write(dir.path().join("a.txt"), "a").unwrap();
+        git(dir.path(), &["add", "-A"]).unwrap();
+        git(dir.path(), &["commit", "-qm", "oops"]).unwrap();
+        git(dir.path(), &["rm", "-q", ".env"]).unwrap();
+        git(dir.path(), &["commit", "-qm", "remove"]).unwrap();
+
+        assert_eq!(history_paths(dir.path(), "HEAD", 10).unwrap(), vec![".env", "a.txt"]);
+        // Only the latest commit is kept, and .env is already gone from its tree
+        assert_eq!(history_paths(dir.path(), "HEAD", 1).unwrap(), vec!["a.txt"]);
+    }
+
+    #[test]
+    fn bundle_holds_only_the_product_subtree_up_to_the_ref() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        fs::create_dir_all(dir.path().join("product")).unwrap();
+        fs::write(dir.path().join("other.txt"), "not ours").unwrap();
+        for i in 1..=3 {
+            fs::write(dir.path().join("product/a.txt"), i.to_string()).unwrap();
+            git(dir.path(), &["add", "-A"]).unwrap();
+            git(dir.path(), &["commit", "-qm", &format!("change {}", i)]).unwrap();
+        }
+        git(dir.path(), &["tag", "v2", "HEAD~1"]).unwrap();
+        fs::write(dir.path().join("other.txt"), "still not ours").unwrap();
+        git(dir.path(), &["commit", "-qam", "outside"]).unwrap();
+
+        let product = dir.path().join("product");
+        assert_eq!(history_paths(&product, "v2", 10).unwrap(), vec!["a.txt"]);
+        let bundle = dir.path().join("history.bundle");
+        create_bundle(&product, "v2", 1, &bundle).unwrap();
+
+        let clone = dir.path().join("clone");
+        git(dir.path(), &["clone", "-q", bundle.to_str().unwrap(), clone.to_str().unwrap()]).unwrap();
+        assert_eq!(git(&clone, &["log", "--format=%s"]).unwrap(), "change 2");
+        assert_eq!(fs::read_to_string(clone.join("a.txt")).unwrap(), "2");
+        assert!(!clone.join("other.txt").exists());
+    }
+
+    #[test]
+    fn export_unknown_ref_fails() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        fs::write(dir.path().join("a.txt"), "a").unwrap();
+        git(dir.path(), &["add", "-A"]).unwrap();
+        git(dir.path(), &["commit", "-qm", "init"]).unwrap();
+        assert!(export_ref(dir.path(), "nope").is_err());
+    }
+}
@@ -0,0 +1,192 @@
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+/// A build or test command detected from the project's build files.
+pub struct HealthCheck {
+    pub label: &'static str,
+    pub program: &'static str,
+    pub args: Vec<String>,
+}
+
+impl HealthCheck {
+    fn new(label: &'static str, program: &'static str, args: &[&str]) -> Self {
+        Self {
+            label,
+            program,
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    pub fn command_line(&self) -> String {
+        std::iter::once(self.program.to_string())
+            .chain(self.args.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+pub struct CheckOutcome {
+    pub passed: bool,
+    pub timed_out: bool,
+    /// Most relevant output lines (error lines first, else the tail)
+    pub summary: Vec<String>,
+}
+
+/// Environment variables passed through to checks; everything else (API tokens,
+/// cloud credentials) is withheld from the subprocess.
+const PASSTHROUGH_ENV: &[&str] = &[
+    "PATH", "HOME", "USER", "LANG", "TERM", "TMPDIR", "TEMP", "TMP",
+    "SYSTEMROOT", "APPDATA", "LOCALAPPDATA", "USERPROFILE",
+    "CARGO_HOME", "RUSTUP_HOME", "GOPATH", "GOCACHE", "NVM_DIR", "VIRTUAL_ENV",
+];
+
+const SUMMARY_LINES: usize = 15;
+
+/// Detect build (and optionally test) commands from build files in `dir`.
+pub fn detect_checks(dir: &Path, include_tests: bool) -> Vec<HealthCheck> {
+    let mut checks = Vec::new();
+
+    if dir.join("Cargo.toml").exists() {
+        checks.push(HealthCheck::new("build", "cargo", &["check", "--quiet"]));
+        if include_tests {
+            checks.push(HealthCheck::new("test", "cargo", &["test", "--quiet"]));
+        }
+    }
+
+    if let Ok(content) = std::fs::read_to_string(dir.join("package.json")) {
+        let scripts = serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .and_then(|v| v["scripts"].as_object().cloned())
+            .unwrap_or_default();
+        if scripts.contains_key("build") {
+            checks.push(HealthCheck::new("build", "npm", &["run", "build", "--silent"]));
+        }
+        if include_tests && scripts.contains_key("test") {
+            checks.push(HealthCheck::new("test", "npm", &["test", "--silent"]));
+        }
+    }
+
+    if dir.join("go.mod").exists() {
+        checks.push(HealthCheck::new("build", "go", &["build", "./..."]));
+        if include_tests {
+            checks.push(HealthCheck::new("test", "go", &["test", "./..."]));
+        }
+    }
+
+    if dir.join("pyproject.toml").exists() || dir.join("setup.py").exists() {
+        checks.push(HealthCheck::new("build", "python3", &["-m", "compileall", "-q", "."]));
+        if include_tests {
+            checks.push(HealthCheck::new("test", "python3", &["-m", "pytest", "-q"]));
+        }
+    }
+
+    if checks.is_empty() && dir.join("Makefile").exists() {
+        checks.push(HealthCheck::new("build", "make", &[]));
+    }
+
+    checks
+}
+
+/// Run a check in `dir` with no stdin, a filtered environment and a timeout.
+/// This is not a sandbox: the build has the user's file system and network.
+pub async fn run_check(dir: &Path, check: &HealthCheck, timeout: Duration) -> CheckOutcome {
+    let mut cmd = tokio::process::Command::new(check.program);
+    cmd.args(&check.args)
+        .current_dir(dir)
+        .env_clear()
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    for key in PASSTHROUGH_ENV {
+        if let Ok(value) = std::env::var(key) {
+            cmd.env(key, value);
+        }
+    }
+
+    let output = match tokio::time::timeout(timeout, cmd.output()).await {
+        Err(_) => {
+            return CheckOutcome {
+                passed: false,
+                timed_out: true,
+                summary: vec![format!("Timed out after {}s", timeout.as_secs())],
+            }
+        }
+        Ok(Err(e)) => {
+            return CheckOutcome {
+                passed: false,
+                timed_out: false,
+                summary: vec![format!("Could not run {}: {}", check.program, e)],
+            }
+        }
+        Ok(Ok(output)) => output,
+    };
+
+    let mut text = String::from_utf8_lossy(&output.stderr).to_string();
+    text.push_str(&String::from_utf8_lossy(&output.stdout));
+    CheckOutcome {
+        passed: output.status.success(),
+        timed_out: false,
+        summary: if output.status.success() { Vec::new() } else { summarize(&text) },
+    }
+}
+
+/// Pick the lines most likely to explain a failure.
+fn summarize(output: &str) -> Vec<String> {
+    let lines: Vec<&str> = output.lines().filter(|l| !l.trim().is_empty()).collect();
+    let errors: Vec<String> = lines
+        .iter()
+        .filter(|l| l.to_lowercase().contains("error"))
+        .take(SUMMARY_LINES)
+        .map(|l| l.to_string())
+        .collect();
+    if !errors.is_empty() {
+        return errors;
+    }
+    let start = lines.len().saturating_sub(SUMMARY_LINES);
+    lines[start..].iter().map(|l| l.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn detects_cargo_and_npm_scripts() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"build": "vite build", "test": "vitest"}}"#,
+        ).unwrap();
+
+        let lines: Vec<String> = detect_checks(dir.path(), false).iter().map(|c| c.command_line()).collect();
+        assert_eq!(lines, vec!["cargo check --quiet", "npm run build --silent"]);
+
+        let with_tests = detect_checks(dir.path(), true);
+        assert_eq!(with_tests.iter().filter(|c| c.label == "test").count(), 2);
+    }
+
+    #[test]
+    fn makefile_only_as_fallback() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("Makefile"), "all:").unwrap();
+        assert_eq!(detect_checks(dir.path(), false)[0].command_line(), "make");
+
+        fs::write(dir.path().join("go.mod"), "module x").unwrap();
+        let lines: Vec<String> = detect_checks(dir.path(), false).iter().map(|c| c.command_line()).collect();
+        assert_eq!(lines, vec!["go build ./..."]);
+    }
+
+    #[test]
+    fn summarize_prefers_error_lines() {
+        let out = "Compiling x\nerror[E0425]: cannot find value `y`\n  --> src/main.rs:1\nwarning: unused\n";
+        assert_eq!(summarize(out), vec!["error[E0425]: cannot find value `y`"]);
+
+        let out = "line1\nline2\n";
+        assert_eq!(summarize(out), vec!["line1", "line2"]);
+    }
+}
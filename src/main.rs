@@ -1,87 +1,340 @@
 mod api;
 mod auth;
+mod backup;
+mod baseline;
+mod browse;
 mod cli;
 mod config;
+mod crash;
+mod diff;
+mod edit;
+mod error;
+mod git;
+mod health;
+mod maintenance;
 mod manifest;
+mod markdown;
+mod merge;
+mod notify;
 mod packaging;
+mod provenance;
 mod publish_gate;
+mod saved_search;
+mod sbom;
+mod search_cache;
+mod signing;
+mod snapshot;
+mod spdx;
 mod types;
+mod ui;
 mod update_check;
+mod upstream;
+mod upstream_cache;
 mod utils;
+mod wizard;
+mod workspace;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, ForkArgs, GitTagArgs, MaintenanceAction, MediaAction, NotifyAction, OutputFormat, ProductSort, PublishArgs, RemakeArgs, ReviewAction, SearchArgs, SlugAction, SnapshotAction, RemoteAction, UpstreamAction, Visibility};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by `publish --output json`: stdout then carries only the result object.
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Human-readable progress: stdout, or stderr when stdout is reserved for JSON.
+macro_rules! progress {
+    ($($arg:tt)*) => {
+        if JSON_OUTPUT.load(Ordering::Relaxed) {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    crash::install();
     let cli = Cli::parse();
+    let error_format = cli.error_format;
+    if let Some(ref dir) = cli.chdir {
+        if let Err(e) = std::env::set_current_dir(dir) {
+            return report_failure(error_format, anyhow::anyhow!("Cannot change to directory '{}': {}", dir.display(), e));
+        }
+    }
+    wizard::set_mode(cli.yes, cli.non_interactive);
+    ui::init(cli.accessible);
+    ui::set_time_format(if cli.utc { ui::TimeFormat::Utc } else { cli.time_format });
     let update_handle = update_check::spawn_check();
+    let notify_handle = notify::spawn_refresh_if_due();
 
     let result = match cli.command {
         Commands::Login => {
             auth::login().await
         }
-        Commands::Publish {
-            version,
-            changelog,
-            category,
-            name,
-            description,
-            license,
-        } => {
-            cmd_publish(version, changelog, category, name, description, license).await
-        }
-        Commands::Remake {
-            version,
-            slug,
-            changelog,
-            category,
-            name,
-            description,
-            license,
-        } => {
-            cmd_remake(version, slug, changelog, category, name, description, license).await
+        Commands::Publish(args) => {
+            cmd_publish(args).await
         }
-        Commands::Fork { product, dir } | Commands::Clone { product, dir } => {
-            cmd_fork(&product, dir.as_deref()).await
+        Commands::Remake(args) => {
+            cmd_remake(args).await
         }
-        Commands::Search {
-            query,
-            category,
-            sort,
-            limit,
-        } => {
-            cmd_search(&query, category.as_deref(), &sort, limit).await
+        Commands::Fork(args) | Commands::Clone(args) if args.list => {
+            cmd_fork_list(&args.product, args.preview_token.as_deref()).await
+        }
+        Commands::Fork(args) | Commands::Clone(args) => {
+            cmd_fork(args).await
+        }
+        Commands::Search(args) => {
+            cmd_search(args).await
+        }
+        Commands::Browse { category: None, .. } => {
+            cmd_categories().await
+        }
+        Commands::Browse { category, sort, limit, page, table } => {
+            cmd_search(SearchArgs {
+                query: None,
+                category,
+                tags: Vec::new(),
+                publisher: None,
+                exclude_publishers: Vec::new(),
+                min_rating: None,
+                min_forks: None,
+                remakes_of: None,
+                sort,
+                limit,
+                page,
+                explain_ranking: false,
+                save: None,
+                saved: None,
+                diff: false,
+                interactive: false,
+                table,
+                wide: false,
+                offline: false,
+            })
+            .await
+        }
+        Commands::Init { slug, path } => {
+            cmd_init(slug, path.as_deref())
+        }
+        Commands::Edit { product, name, description, category, license, links } => {
+            let fields = types::UpdateProductRequest {
+                name, description, category_slug: category, license, links: links.links(), tags: None,
+            };
+            cmd_edit(product.as_deref(), fields).await
+        }
+        Commands::Products { status, sort, table, wide, json } => {
+            cmd_products(status, sort, table.then_some(wide), json).await
+        }
+        Commands::Promote { version, product } => {
+            cmd_promote(&version, product.as_deref()).await
+        }
+        Commands::Backup { out, incremental, jobs } => {
+            cmd_backup(&out, incremental, jobs).await
+        }
+        Commands::Mirror { product, dir } => {
+            cmd_mirror(&product, &dir).await
+        }
+        Commands::Restore { dir, registry } => {
+            cmd_restore(&dir, registry.as_deref()).await
+        }
+        Commands::Info { product, sbom } => {
+            cmd_info(&product, sbom).await
+        }
+        Commands::Open { product } => {
+            cmd_open(product.as_deref())
+        }
+        Commands::Releases { product, mine: true, json } => {
+            cmd_own_releases(product.as_deref(), json).await
+        }
+        Commands::Releases { product, json, .. } => {
+            cmd_releases(product.as_deref().unwrap_or_default(), json).await
+        }
+        Commands::Readme { product, raw } => {
+            cmd_readme(&product, raw).await
+        }
+        Commands::Stats { all: true, days, .. } => {
+            cmd_stats_all(days).await
+        }
+        Commands::Stats { product, days, .. } => {
+            cmd_stats(product.as_deref(), days).await
+        }
+        Commands::Status { json, path } => {
+            if json {
+                cmd_status_json(path.as_deref())
+            } else {
+                cmd_status(path.as_deref())
+            }
+        }
+        Commands::Upstream { recursive: Some(_), remote: Some(_), .. } => {
+            Err(anyhow::anyhow!("--remote can't be combined with --recursive, which checks each fork's origin"))
+        }
+        Commands::Upstream { recursive: Some(dir), jobs, .. } => {
+            cmd_upstream_recursive(&dir, jobs).await
+        }
+        Commands::Upstream { action: None, remote, .. } => {
+            cmd_upstream(remote.as_deref()).await
+        }
+        Commands::Upstream { action: Some(UpstreamAction::Diff { latest, patch }), remote, .. } => {
+            cmd_upstream_diff(latest, patch, remote.as_deref()).await
+        }
+        Commands::Upstream { action: Some(UpstreamAction::Pull { dry_run }), remote, .. } => {
+            cmd_upstream_pull(dry_run, remote.as_deref()).await
+        }
+        Commands::Upstream { action: Some(UpstreamAction::Rebase), remote, .. } => {
+            cmd_upstream_rebase(remote.as_deref()).await
         }
-        Commands::Init { slug } => {
-            cmd_init(slug)
+        Commands::Upstream { action: Some(UpstreamAction::Set { product }), remote, .. } => {
+            cmd_upstream_set(&product, remote.as_deref()).await
         }
-        Commands::Products { status } => {
-            cmd_products(status).await
+        Commands::Upstream { action: Some(UpstreamAction::Pin { version, clear: _ }), remote, .. } => {
+            cmd_upstream_pin(version.as_deref(), remote.as_deref()).await
         }
-        Commands::Status => {
-            cmd_status()
+        Commands::Upstream { action: Some(UpstreamAction::Ignore { version, undo }), remote, .. } => {
+            cmd_upstream_ignore(&version, undo, remote.as_deref())
         }
-        Commands::Upstream => {
-            cmd_upstream().await
+        Commands::Upstream { action: Some(UpstreamAction::Remote { action }), .. } => {
+            cmd_upstream_remote(action).await
         }
         Commands::Pull => {
             cmd_pull().await
         }
+        Commands::Health { test, timeout } => {
+            cmd_health(test, timeout).await
+        }
+        Commands::Verify { product, provenance } => {
+            cmd_verify(product.as_deref(), provenance).await
+        }
+        Commands::Keygen { force } => {
+            cmd_keygen(force).await
+        }
+        Commands::Slug { action: SlugAction::Check { name } } => {
+            cmd_slug_check(&name).await
+        }
+        Commands::Snapshot { action } => {
+            cmd_snapshot(action)
+        }
+        Commands::Visibility { level, product } => {
+            cmd_visibility(level, product.as_deref()).await
+        }
+        Commands::Deprecate { product, message, .. } => {
+            cmd_deprecate(product.as_deref(), message.as_deref()).await
+        }
+        Commands::Media { action } => {
+            cmd_media(action).await
+        }
+        #[cfg(feature = "moderation")]
+        Commands::Mod { action } => {
+            cmd_mod(action).await
+        }
+        Commands::Maintenance { action: MaintenanceAction::Run } => {
+            cmd_maintenance_run();
+            Ok(())
+        }
+        Commands::Feedback { message, crash, crash_file } => {
+            cmd_feedback(message.as_deref(), crash, crash_file).await
+        }
         Commands::Logout => {
             cmd_logout()
         }
+        Commands::Notify { action } => {
+            cmd_notify(action)
+        }
+        Commands::Reviews { action: Some(ReviewAction::Reply { review_id, message }), .. } => {
+            cmd_review_reply(&review_id, &message).await
+        }
+        Commands::Reviews { product, unanswered, action: None } => {
+            cmd_reviews(product.as_deref(), unanswered).await
+        }
+        Commands::Rate { product, rating, review } => {
+            cmd_rate(&product, rating, review.as_deref()).await
+        }
     };
 
+    // Daily cleanup, run after the command so it never touches files in use
+    let maintenance_handle = maintenance::spawn_if_due();
+
     // Print update notice if available (non-blocking, 100ms timeout)
     if let Ok(Ok(Some(notice))) =
         tokio::time::timeout(std::time::Duration::from_millis(100), update_handle).await
     {
         eprintln!("{}", notice);
     }
+    if let Some(handle) = notify_handle {
+        let _ = tokio::time::timeout(std::time::Duration::from_millis(100), handle).await;
+    }
+    if let Some(notice) = notify::pending_notice() {
+        eprintln!("{}", notice);
+    }
+    if let Some(handle) = maintenance_handle {
+        let _ = tokio::time::timeout(std::time::Duration::from_millis(50), handle).await;
+    }
+
+    match result {
+        Err(e) => report_failure(error_format, e),
+        ok => ok,
+    }
+}
+
+/// Hand a failure back to `main` for the usual `Error: ...` text, or print it
+/// as a single JSON object on stderr and exit 1 under `--error-format json`.
+fn report_failure(format: OutputFormat, err: anyhow::Error) -> Result<()> {
+    if format == OutputFormat::Text {
+        return Err(err);
+    }
+    if let Ok(json) = serde_json::to_string(&error::report(&err)) {
+        eprintln!("{}", json);
+    }
+    std::process::exit(1);
+}
+
+/// The directory a command starts from: `--path` (relative to the cwd) when given,
+/// else the cwd.
+fn start_dir(path: Option<&std::path::Path>) -> Result<std::path::PathBuf> {
+    let cwd = std::env::current_dir()?;
+    match path {
+        Some(p) => {
+            let dir = cwd.join(p);
+            if !dir.is_dir() {
+                return Err(anyhow::anyhow!("--path {} is not a directory", p.display()));
+            }
+            Ok(dir)
+        }
+        None => Ok(cwd),
+    }
+}
+
+/// The product root: the nearest ancestor of the cwd holding `.baro/manifest.json`,
+/// or the cwd itself when there is none (e.g. before the first publish).
+fn project_root() -> Result<std::path::PathBuf> {
+    project_root_from(None)
+}
+
+/// `project_root`, starting from `--path` when given.
+fn project_root_from(path: Option<&std::path::Path>) -> Result<std::path::PathBuf> {
+    let cwd = start_dir(path)?;
+    Ok(manifest::find_root(&cwd).unwrap_or(cwd))
+}
 
-    result
+/// Like `project_root`, but for commands that act on a product's identity (publish,
+/// remake): refuses to guess when the cwd sits inside several nested products.
+fn publish_root(path: Option<&std::path::Path>) -> Result<std::path::PathBuf> {
+    let cwd = start_dir(path)?;
+    let roots = manifest::find_all_roots(&cwd);
+    match roots.first() {
+        Some(nearest) if roots.len() > 1 && *nearest != cwd => {
+            let listed: Vec<String> = roots.iter().map(|r| format!("  {}", r.display())).collect();
+            Err(anyhow::anyhow!(
+                "Ambiguous product: this directory is inside nested baro products:\n{}\n\
+                Run from the product root you want to publish, or pass --path <dir>.",
+                listed.join("\n")
+            ))
+        }
+        Some(nearest) => Ok(nearest.clone()),
+        None => Ok(cwd),
+    }
 }
 
 fn read_readme(dir: &std::path::Path) -> Option<String> {
@@ -98,6 +351,18 @@ fn read_readme(dir: &std::path::Path) -> Option<String> {
 
 const STARTING_VERSIONS: &[&str] = &["0.0.1", "0.1.0", "1.0.0"];
 
+/// Where `--with-git-history` puts the history inside the archive.
+const HISTORY_BUNDLE_PATH: &str = ".baro/history.bundle";
+const HISTORY_BUNDLE_WARN_BYTES: i64 = 25 * 1024 * 1024;
+/// Commits kept in the history bundle, newest first.
+const HISTORY_MAX_COMMITS: usize = 100;
+/// Where the manifest's `setup` command travels inside the archive.
+const SETUP_SCRIPT_PATH: &str = ".baro/setup";
+/// Where the manifest's `dependencies` travel inside the archive, one per line.
+const DEPENDENCIES_PATH: &str = ".baro/dependencies";
+/// Where a fork's dependencies are unpacked.
+const VENDOR_DIR: &str = "vendor";
+
 struct PublishContext {
     slug: String,
     product_name: String,
@@ -107,9 +372,60 @@ struct PublishContext {
     version: String,
     changelog_text: String,
     readme: Option<String>,
+    /// Where `readme` came from when chosen with `--readme` / `readme_path` (checked by the gate)
+    readme_path: Option<String>,
+    visibility: Option<Visibility>,
+    git_tag: GitTagArgs,
+    /// Directory to validate and package: the project root, or a tree exported from `--ref`
+    source_dir: std::path::PathBuf,
+    git_ref: Option<String>,
+    with_git_history: bool,
+    /// Team namespace, when publishing on behalf of a team
+    team: Option<String>,
+    /// Preview release, hidden until promoted
+    preview: bool,
+    /// Extra files uploaded next to the archive, as (name, path)
+    assets: Vec<(String, std::path::PathBuf)>,
+    /// `--homepage` etc.; replace the product's links
+    links: types::ProductLinks,
+    /// Detected from the build file and git remote; only fill links the product lacks
+    detected_links: types::ProductLinks,
+    /// Normalized `--tag` values; empty leaves an existing product's tags unchanged
+    tags: Vec<String>,
     existing_manifest: Option<types::Manifest>,
 }
 
+/// The license to publish under: `--license` if given (a validated SPDX expression,
+/// warning when it contradicts the LICENSE file), else the identifier detected
+/// from the LICENSE file, else MIT.
+fn resolve_license(flag: Option<String>, dir: &std::path::Path) -> Result<String> {
+    let detected = utils::detect_license(dir);
+    let flag = flag.map(|f| spdx::normalize(&f)).transpose()?;
+    Ok(match (flag, detected) {
+        (Some(flag), Some((file, Some(id)))) => {
+            // The LICENSE text can't tell `-only` from `-or-later`, so compare families
+            let family = |s: &str| s.trim_end_matches('+').trim_end_matches("-only").trim_end_matches("-or-later").to_string();
+            let mentioned = flag
+                .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+                .any(|t| family(t) == family(id));
+            if !mentioned {
+                eprintln!("  WARN: --license {} but {} looks like {}", flag, file, id);
+            }
+            flag
+        }
+        (Some(flag), _) => flag,
+        (None, Some((file, Some(id)))) => {
+            progress!("  License: {} (detected from {})", id, file);
+            id.to_string()
+        }
+        (None, Some((file, None))) => {
+            eprintln!("  WARN: could not identify the license in {}; using MIT. Pass --license to set it.", file);
+            "MIT".to_string()
+        }
+        (None, None) => "MIT".to_string(),
+    })
+}
+
 /// Shared publish steps: gate → package → create/find product → upload → confirm → manifest → track
 async fn execute_publish(
     client: &api::BaroClient,
@@ -119,86 +435,279 @@ async fn execute_publish(
 ) -> Result<()> {
     // 1. Run publish gate
     let categories = client.list_categories().await?;
-    let gate = publish_gate::run(
-        cwd,
+    let mut gate = publish_gate::run(
+        &ctx.source_dir,
         &ctx.version,
         ctx.product_desc.as_deref(),
         &ctx.category_slug,
         &categories.categories,
     );
+    if ctx.with_git_history {
+        if !git::is_repo(cwd) {
+            return Err(anyhow::anyhow!("--with-git-history requires a git repository"));
+        }
+        let target = ctx.git_ref.as_deref().unwrap_or("HEAD");
+        gate.failures.extend(publish_gate::check_git_history(&git::history_paths(cwd, target, HISTORY_MAX_COMMITS)?));
+        gate.passed = gate.failures.is_empty();
+    }
+    if let Some(ref path) = ctx.readme_path {
+        if let Some(failure) = publish_gate::check_readme(path, ctx.readme.as_deref().unwrap_or("")) {
+            gate.failures.push(failure);
+            gate.passed = false;
+        }
+    }
+
+    // Fail fast on a version that already exists or goes backwards
+    let my_products = client.list_owned_products(ctx.team.as_deref()).await?;
+    let existing_product = my_products.products.iter().find(|p| p.slug == ctx.slug);
+    if existing_product.is_some() {
+        let releases = client.list_releases(namespace, &ctx.slug).await?;
+        let versions: Vec<String> = releases.releases.into_iter().map(|r| r.version).collect();
+        if let Some(failure) = publish_gate::check_release_version(&ctx.version, &versions) {
+            gate.failures.push(failure);
+            gate.passed = false;
+        }
+    }
     if !gate.passed {
         eprintln!("Publish gate failed:\n");
         for f in &gate.failures {
             eprintln!("  ERROR: {}", f.message);
             eprintln!("  Fix: {}\n", f.ai_fix_prompt);
         }
-        std::process::exit(1);
+        return Err(anyhow::anyhow!("Publish gate failed ({} problem(s))", gate.failures.len()));
     }
     for w in &gate.warnings {
         eprintln!("  WARN: {}", w.message);
     }
 
     // 2. Package
-    println!("Packaging...");
-    let (archive_bytes, hash) = packaging::create_archive(cwd)?;
+    progress!("Packaging...");
+    for nested in packaging::find_nested_products(&ctx.source_dir) {
+        eprintln!("  Skipping nested product: {}/", nested.display());
+    }
+    // Extra files travel under .baro/, which packaging otherwise leaves out
+    let mut extras: Vec<(&str, std::path::PathBuf)> = Vec::new();
+    if ctx.with_git_history {
+        let bundle = std::env::temp_dir().join(format!("baro-history-{}.bundle", uuid::Uuid::new_v4()));
+        git::create_bundle(cwd, ctx.git_ref.as_deref().unwrap_or("HEAD"), HISTORY_MAX_COMMITS, &bundle)?;
+        let bundle_size = std::fs::metadata(&bundle).map(|m| m.len() as i64).unwrap_or(0);
+        progress!("  Git history: {}", utils::format_bytes(bundle_size));
+        if bundle_size > HISTORY_BUNDLE_WARN_BYTES {
+            eprintln!(
+                "  WARN: git history is large ({}). Consumers download it with every fork.",
+                utils::format_bytes(bundle_size)
+            );
+        }
+        extras.push((HISTORY_BUNDLE_PATH, bundle));
+    }
+    if let Some(setup) = ctx.existing_manifest.as_ref().and_then(|m| m.setup.as_deref()) {
+        let script = std::env::temp_dir().join(format!("baro-setup-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&script, setup)?;
+        progress!("  Setup script: {}", setup);
+        extras.push((SETUP_SCRIPT_PATH, script));
+    }
+    let dependencies = ctx.existing_manifest.as_ref().map(|m| m.dependencies.as_slice()).unwrap_or_default();
+    if !dependencies.is_empty() {
+        for dep in dependencies {
+            parse_product_ref(dep).with_context(|| format!("Invalid dependency '{}' in .baro/manifest.json", dep))?;
+        }
+        let list = std::env::temp_dir().join(format!("baro-dependencies-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&list, dependencies.join("\n"))?;
+        progress!("  Dependencies: {}", dependencies.join(", "));
+        extras.push((DEPENDENCIES_PATH, list));
+    }
+    let extra_refs: Vec<(&str, &std::path::Path)> = extras.iter().map(|(name, path)| (*name, path.as_path())).collect();
+    let result = packaging::create_archive_with_extras(&ctx.source_dir, &extra_refs);
+    for (_, path) in &extras {
+        let _ = std::fs::remove_file(path);
+    }
+    let (archive_bytes, hash) = result?;
     let size = archive_bytes.len() as i64;
-    println!(
+    progress!(
         "  Archive: {} ({})",
         utils::format_bytes(size),
         &hash[..12]
     );
 
+    // 2b. Check the archive against the size limit and storage quota the
+    // registry reports, before uploading (team limits are enforced server-side)
+    if ctx.team.is_none() {
+        let me = client.get_me().await?.user;
+        if let Some(limit) = me.max_archive_bytes {
+            if let Some(f) = publish_gate::check_archive_size(size, limit, &me.personal_tier) {
+                let largest: Vec<String> = packaging::largest_files(&ctx.source_dir, 5)
+                    .into_iter()
+                    .map(|(path, bytes)| format!("    {} ({})", path.display(), utils::format_bytes(bytes as i64)))
+                    .collect();
+                return Err(anyhow::anyhow!(
+                    "{}\n  Fix: {}\n  Largest files:\n{}",
+                    f.message, f.ai_fix_prompt, largest.join("\n")
+                ));
+            }
+        }
+        if let Some(limit) = me.storage_limit_bytes {
+            if let Some(f) = publish_gate::check_quota(size, me.storage_used_bytes, limit, &me.personal_tier) {
+                return Err(anyhow::anyhow!("{}\n  Fix: {}", f.message, f.ai_fix_prompt));
+            }
+        }
+    }
+
     // 3. Create or find product
-    let my_products = client.list_my_products().await?;
-    let existing_product = my_products.products.iter().find(|p| p.slug == ctx.slug);
     let product_id = if let Some(ep) = existing_product {
+        if let Some(v) = ctx.visibility {
+            client.set_visibility(namespace, &ctx.slug, v.as_str()).await?;
+            progress!("Visibility set to {}", v.as_str());
+        }
+        // Detected links never replace ones set with `baro edit`
+        let links = ctx.links.changed_from(&ep.links).or(ctx.detected_links.missing_from(&ep.links));
+        // --tag replaces the product's tags; without it they are left alone
+        let tags = Some(ctx.tags.clone()).filter(|t| !t.is_empty() && *t != ep.tags);
+        if !links.is_empty() || tags.is_some() {
+            client.update_product(&ep.id, &types::UpdateProductRequest { links, tags, ..Default::default() }).await?;
+            progress!("Updated product links and tags");
+        }
         ep.id.clone()
     } else {
         let desc = ctx.product_desc.as_ref().ok_or_else(|| anyhow::anyhow!(
             "Description required (50+ chars) for first publish. Use --description or add to your Cargo.toml/package.json."
         ))?;
-        println!("Creating product {}/{}...", namespace, ctx.slug);
+        progress!("Creating product {}/{}...", namespace, ctx.slug);
         let created = client
-            .create_product(&ctx.slug, &ctx.product_name, desc, &ctx.category_slug, &ctx.license)
+            .create_product(&types::CreateProductRequest {
+                slug: &ctx.slug,
+                name: &ctx.product_name,
+                description: desc,
+                category_slug: &ctx.category_slug,
+                license: &ctx.license,
+                visibility: ctx.visibility.map(|v| v.as_str()),
+                is_private: ctx.visibility == Some(Visibility::Private),
+                team: ctx.team.as_deref(),
+                links: ctx.links.clone().or(ctx.detected_links.clone()),
+                tags: &ctx.tags,
+            })
             .await?;
         created.product.id.clone()
     };
 
     // 4. Create release
-    println!("Uploading v{}...", ctx.version);
+    let signing_key = signing::load()?;
+    let signature = signing_key.as_ref().map(|key| {
+        progress!("  Signed with key {}", &signing::public_key_hex(key)[..12]);
+        signing::sign_hash(key, &hash)
+    });
+    progress!("Uploading v{}...", ctx.version);
     let release = client
-        .create_release(namespace, &ctx.slug, &ctx.version, &ctx.changelog_text, size, &hash, ctx.readme.as_deref())
+        .create_release(namespace, &ctx.slug, &types::CreateReleaseRequest {
+            version: &ctx.version,
+            changelog: &ctx.changelog_text,
+            file_size_bytes: size,
+            file_hash_sha256: &hash,
+            readme: ctx.readme.as_deref(),
+            signature: signature.as_deref(),
+            preview: ctx.preview,
+        })
         .await?;
+    let issued_at = std::time::Instant::now();
 
     // 5. Upload to R2
     client
-        .upload_to_r2(&release.upload_url, &archive_bytes)
+        .upload_release(&release, issued_at, &archive_bytes)
         .await?;
 
+    // 5a. Upload extra assets (prebuilt binaries etc.)
+    for (name, path) in &ctx.assets {
+        let data = std::fs::read(path).with_context(|| format!("Failed to read asset {}", path.display()))?;
+        let asset_hash = packaging::sha256_hex(&data);
+        client
+            .upload_asset(&release.release_id, &types::CreateAssetRequest {
+                name,
+                file_size_bytes: data.len() as i64,
+                file_hash_sha256: &asset_hash,
+            }, &data)
+            .await?;
+        progress!("  Asset {} ({})", name, utils::format_bytes(data.len() as i64));
+    }
+
+    // 5b. Attach an SBOM of locked dependencies, when there are lockfiles
+    // (best-effort, like provenance: the upload is already done)
+    let components = sbom::collect_components(&ctx.source_dir);
+    if !components.is_empty() {
+        let bom = sbom::cyclonedx(&ctx.product_name, &ctx.version, &components);
+        match client.attach_sbom(&release.release_id, &bom).await {
+            Ok(_) => progress!("  SBOM attached ({} components)", components.len()),
+            Err(e) => eprintln!("  WARN: SBOM not attached: {:#}", e),
+        }
+    }
+
+    // 5c. Attach signed build provenance when publishing from CI (best-effort:
+    // failing here would leave the uploaded release unconfirmed)
+    let target = ctx.git_ref.as_deref().unwrap_or("HEAD");
+    if let Some(ci) = provenance::detect_ci(|k| std::env::var(k).ok()) {
+        match signing_key {
+            Some(ref key) => {
+                let archive_name = format!("{}-{}.tar.gz", ctx.slug, ctx.version);
+                let attached = match provenance::build(ci, cwd, target, &archive_name, &hash)
+                    .and_then(|p| provenance::sign(key, &p))
+                {
+                    Ok(signed) => client.attach_provenance(&release.release_id, &signed).await.map(|_| ()),
+                    Err(e) => Err(e),
+                };
+                match attached {
+                    Ok(()) => progress!("  Provenance attached"),
+                    Err(e) => eprintln!("  WARN: provenance not attached: {:#}", e),
+                }
+            }
+            None => eprintln!("  WARN: running in CI without a signing key; no provenance attached. Run `baro keygen` and provide the key to CI."),
+        }
+    }
+
     // 6. Confirm
     let confirm = client.confirm_release(&release.release_id).await?;
 
-    println!(
+    progress!(
         "\nPublished {}/{}@{} ({})",
         namespace, ctx.slug, ctx.version,
         utils::format_bytes(size)
     );
     match confirm.review_status.as_deref() {
-        Some("published") => println!("Status: published"),
-        Some("unlisted") => println!("Status: unlisted (not visible in browse)"),
-        Some("pending_review") => println!("Status: pending_review (admin approval required)"),
-        Some(s) => println!("Status: {}", s),
-        None => println!("Status: pending_review (admin approval required)"),
+        _ if ctx.preview => {
+            progress!("Status: preview (not public)");
+            if let Some(ref url) = confirm.preview_url {
+                progress!("Preview link: {}", url);
+            }
+            if let Some(ref token) = confirm.preview_token {
+                progress!(
+                    "Reviewers can fork with: baro fork {}/{}@{} --preview-token {}",
+                    namespace, ctx.slug, ctx.version, token
+                );
+            }
+            progress!("When approved: baro promote {}", ctx.version);
+        }
+        Some("published") => progress!("Status: published"),
+        Some("unlisted") => progress!("Status: unlisted (not visible in browse)"),
+        Some("pending_review") => progress!("Status: pending_review (admin approval required)"),
+        Some(s) => progress!("Status: {}", s),
+        None => progress!("Status: pending_review (admin approval required)"),
     }
 
+    // 6b. Tag the release in git (best-effort: the release is already live)
+    tag_release(cwd, &ctx.version, target, &ctx.git_tag);
+
     // 7. Write/update manifest
     let updated_manifest = types::Manifest {
         origin: ctx.existing_manifest.as_ref().and_then(|m| m.origin.clone()),
         cloned_at: ctx.existing_manifest.as_ref().and_then(|m| m.cloned_at.clone()),
         file_hash: ctx.existing_manifest.as_ref().and_then(|m| m.file_hash.clone()),
+        origin_version: ctx.existing_manifest.as_ref().filter(|m| m.origin.is_some()).map(|m| m.forked_version().to_string()),
+        origin_release_id: ctx.existing_manifest.as_ref().and_then(|m| m.origin_release_id.clone()),
+        remotes: ctx.existing_manifest.as_ref().map(|m| m.remotes.clone()).unwrap_or_default(),
+        ignored_versions: ctx.existing_manifest.as_ref().map(|m| m.ignored_versions.clone()).unwrap_or_default(),
+        pinned_version: ctx.existing_manifest.as_ref().and_then(|m| m.pinned_version.clone()),
         slug: Some(ctx.slug.clone()),
         product_id: Some(product_id.clone()),
         publisher: Some(namespace.to_string()),
+        setup: ctx.existing_manifest.as_ref().and_then(|m| m.setup.clone()),
+        dependencies: ctx.existing_manifest.as_ref().map(|m| m.dependencies.clone()).unwrap_or_default(),
         version: ctx.version.clone(),
     };
     manifest::write(cwd, &updated_manifest)?;
@@ -211,34 +720,222 @@ async fn execute_publish(
                 .track_remake(origin_parts[0], origin_parts[1], &product_id, &updated_manifest.version)
                 .await
             {
-                Ok(_) => println!("Remake tracked from {}", origin),
+                Ok(_) => progress!("Remake tracked from {}", origin),
                 Err(e) => eprintln!("Warning: could not track fork: {}", e),
             }
         }
     }
 
+    if JSON_OUTPUT.load(Ordering::Relaxed) {
+        let review_status = match confirm.review_status {
+            _ if ctx.preview => "preview".to_string(),
+            Some(s) => s,
+            None => "pending_review".to_string(),
+        };
+        let result = types::PublishResult {
+            schema_version: 1,
+            product_id,
+            namespace: namespace.to_string(),
+            product_url: config::product_url(namespace, &ctx.slug),
+            slug: ctx.slug,
+            version: ctx.version,
+            release_id: release.release_id,
+            archive_size_bytes: size,
+            sha256: hash,
+            review_status,
+            preview_url: confirm.preview_url,
+        };
+        println!("{}", serde_json::to_string(&result)?);
+    }
+
     Ok(())
 }
 
-async fn cmd_publish(
-    version: String,
-    changelog: Option<String>,
-    category: Option<String>,
-    name_flag: Option<String>,
-    description_flag: Option<String>,
-    license: String,
-) -> Result<()> {
+fn tag_release(cwd: &std::path::Path, version: &str, target: &str, flags: &GitTagArgs) {
+    let settings = config::settings();
+    let push = flags.push_tag || settings.git_tag_push.unwrap_or(false);
+    let create = push || flags.git_tag || settings.git_tag.unwrap_or(false);
+    if !create || !git::is_repo(cwd) {
+        return;
+    }
+
+    let tag = format!("v{}", version);
+    if git::tag_exists(cwd, &tag) {
+        eprintln!("Warning: git tag {} already exists, not re-tagging", tag);
+        return;
+    }
+    if let Err(e) = git::create_tag(cwd, &tag, &format!("Release {}", version), target) {
+        eprintln!("Warning: could not create git tag {}: {}", tag, e);
+        return;
+    }
+    progress!("Tagged {}", tag);
+
+    if push {
+        match git::push_tag(cwd, &tag) {
+            Ok(()) => progress!("Pushed {} to origin", tag),
+            Err(e) => eprintln!("Warning: could not push git tag {}: {}", tag, e),
+        }
+    }
+}
+
+/// Release notes from conventional commits between the previous release tag and `until`.
+fn changelog_from_git_log(
+    cwd: &std::path::Path,
+    previous_version: Option<&str>,
+    until: &str,
+) -> Result<String> {
+    if !git::is_repo(cwd) {
+        return Err(anyhow::anyhow!("--changelog-from-git requires a git repository"));
+    }
+    let since = previous_version
+        .map(|v| format!("v{}", v))
+        .filter(|tag| git::tag_exists(cwd, tag));
+    match since {
+        Some(ref tag) => progress!("Changelog from commits since {}", tag),
+        None => progress!("No tag for the previous release; changelog from full history"),
+    }
+    let subjects = git::commit_subjects(cwd, since.as_deref(), until)?;
+    let text = utils::changelog_from_commits(&subjects);
+    if text.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No release-worthy commits found for the changelog. Pass --changelog \"...\" instead."
+        ));
+    }
+    Ok(text)
+}
+
+/// Decide who a release is published as. An explicit team wins; otherwise a
+/// manifest publisher that is not the caller is taken to be a team. Team
+/// namespaces require membership. Returns (namespace, team).
+async fn resolve_namespace(
+    client: &api::BaroClient,
+    team: Option<&str>,
+    recorded_publisher: Option<&str>,
+) -> Result<(String, Option<String>)> {
+    let team = match team {
+        Some(t) => t.to_string(),
+        None => {
+            let me = client.get_me().await?;
+            match recorded_publisher {
+                Some(p) if p != me.user.username => p.to_string(),
+                _ => return Ok((me.user.username, None)),
+            }
+        }
+    };
+
+    let teams = client.list_my_teams().await?;
+    match teams.teams.iter().find(|t| t.slug == team) {
+        Some(membership) if membership.role != "viewer" => Ok((team.clone(), Some(team))),
+        Some(_) => Err(anyhow::anyhow!(
+            "You are a viewer on team '{}' and cannot publish. Ask a team admin for publish access.",
+            team
+        )),
+        None => {
+            let available: Vec<&str> = teams.teams.iter().map(|t| t.slug.as_str()).collect();
+            Err(anyhow::anyhow!(
+                "You are not a member of team '{}'.{}",
+                team,
+                if available.is_empty() {
+                    String::new()
+                } else {
+                    format!(" Your teams: {}", available.join(", "))
+                }
+            ))
+        }
+    }
+}
+
+async fn cmd_publish(args: PublishArgs) -> Result<()> {
+    if args.output == OutputFormat::Json {
+        JSON_OUTPUT.store(true, Ordering::Relaxed);
+    }
+    if args.workspace {
+        return cmd_publish_workspace(args).await;
+    }
+    let PublishArgs {
+        version,
+        changelog,
+        changelog_from_git,
+        category,
+        name: name_flag,
+        description: description_flag,
+        license,
+        visibility,
+        links,
+        tags,
+        readme: readme_flag,
+        git_tag,
+        team,
+        allow_dirty,
+        git_ref,
+        with_git_history,
+        preview,
+        path,
+        workspace: _,
+        assets,
+        output: _,
+    } = args;
+    let version = version.ok_or_else(|| anyhow::anyhow!("--version is required"))?;
+
+    let cwd = publish_root(path.as_deref())?;
+    let assets = packaging::asset_names(&assets)?;
+
+    // Don't tar up a home directory or filesystem root by accident
+    if let Some(reason) = packaging::suspicious_root(&cwd, dirs::home_dir().as_deref()) {
+        let question = format!("WARNING: {}; this doesn't look like a product root. Package it anyway?", reason);
+        if !wizard::confirm(&question, "Run from the product directory or pass --path <dir>; add --yes only if this really is the product root.")? {
+            progress!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    // With --ref, package a clean export of that commit instead of the working tree
+    let exported = match git_ref {
+        Some(ref r) => {
+            if !git::is_repo(&cwd) {
+                return Err(anyhow::anyhow!("--ref requires a git repository"));
+            }
+            let tree = git::export_ref(&cwd, r)?;
+            progress!("Publishing from git ref {}", r);
+            Some(tree)
+        }
+        None => None,
+    };
+    let source_dir = exported
+        .as_ref()
+        .map(|t| t.path().to_path_buf())
+        .unwrap_or_else(|| cwd.clone());
+
+    // Refuse to publish a dirty git tree so the archive matches a commit
+    if exported.is_none() && !allow_dirty && git::is_repo(&cwd) {
+        let dirty = git::dirty_files(&cwd)?;
+        if !dirty.is_empty() {
+            let shown: Vec<String> = dirty.iter().take(10).map(|l| format!("  {}", l)).collect();
+            let more = if dirty.len() > 10 {
+                format!("\n  ... and {} more", dirty.len() - 10)
+            } else {
+                String::new()
+            };
+            return Err(anyhow::anyhow!(
+                "Working tree has uncommitted changes:\n{}{}\n\
+                Commit or stash them first, or pass --allow-dirty to publish anyway.",
+                shown.join("\n"),
+                more
+            ));
+        }
+    }
+
     let token = auth::get_token().await?;
     let client = api::BaroClient::new(&token);
 
-    // 1. Get publisher info
-    let me = client.get_me().await?;
-    println!("Publishing as {}...", me.user.username);
-
-    // 2. Read manifest for product identity
-    let cwd = std::env::current_dir()?;
+    // 1. Read manifest for product identity
     let existing_manifest = manifest::read(&cwd).ok();
 
+    // 2. Resolve publishing namespace (user or team)
+    let recorded_publisher = existing_manifest.as_ref().and_then(|m| m.publisher.as_deref());
+    let (namespace, team) = resolve_namespace(&client, team.as_deref(), recorded_publisher).await?;
+    progress!("Publishing as {}...", namespace);
+
     // Block publish on unpublished forks — direct to remake
     if let Some(ref m) = existing_manifest {
         if m.origin.is_some() && m.product_id.is_none() {
@@ -274,28 +971,55 @@ async fn cmd_publish(
     };
 
     // 3. Extract metadata from build files or flags
-    let (detected_name, detected_desc) = utils::detect_metadata(&cwd);
+    let (detected_name, detected_desc) = utils::detect_metadata(&source_dir);
     let product_name = name_flag
         .or(detected_name)
         .unwrap_or_else(|| slug.clone());
-    let product_desc = description_flag.or(detected_desc);
+    let mut product_desc = description_flag.or(detected_desc);
+    let mut license = license;
+    let links = utils::validate_links(links.links(), true)?;
+    let detected_links = utils::validate_links(utils::detect_links(&source_dir), false)?;
+    let tags = utils::normalize_tags(&tags)?;
 
     // 4. Resolve category
     let category_slug = match &category {
         Some(c) => c.clone(),
         None => {
-            let my_products = client.list_my_products().await?;
+            let my_products = client.list_owned_products(team.as_deref()).await?;
             match my_products.products.iter().find(|p| p.slug == slug) {
                 Some(existing) => existing
                     .category
                     .as_ref()
                     .map(|c| c.slug.clone())
                     .unwrap_or_else(|| "developer-tools".to_string()),
+                // First publish on a terminal: ask for what's missing instead of failing
+                None if wizard::is_interactive() => {
+                    progress!("First publish of {}: a few questions (Ctrl-C to cancel).\n", slug);
+                    let category = wizard::choose_category(&client.list_categories_cached().await?)?;
+                    let mut flags = vec![("version", version.clone()), ("category", category.clone())];
+                    if product_desc.is_none() {
+                        let desc = wizard::prompt_description()?;
+                        flags.push(("description", desc.clone()));
+                        product_desc = Some(desc);
+                    }
+                    if license.is_none() && utils::detect_license(&source_dir).is_none() {
+                        let chosen = wizard::choose_license()?;
+                        flags.push(("license", chosen.clone()));
+                        license = Some(chosen);
+                    }
+                    let flags: Vec<(&str, &str)> = flags.iter().map(|(f, v)| (*f, v.as_str())).collect();
+                    progress!("\nNext time (or in CI):\n  {}\n", wizard::equivalent_command(&flags));
+                    category
+                }
                 None => {
+                    let available = match client.list_categories_cached().await {
+                        Ok(categories) => utils::format_category_list(&categories),
+                        Err(_) => "  (could not fetch categories — see https://baro-sync.com)".to_string(),
+                    };
                     return Err(anyhow::anyhow!(
                         "Category required for first publish. Use --category <slug>.\n\
-                        Available: developer-tools, productivity, ai-agents, data-tools, \
-                        devops, design-tools, communication, education, finance, other"
+                        Available:\n{}",
+                        available
                     ));
                 }
             }
@@ -303,16 +1027,36 @@ async fn cmd_publish(
     };
 
     // 5. Resolve changelog
+    let changelog = match changelog {
+        Some(cl) => Some(cl),
+        None if changelog_from_git => Some(changelog_from_git_log(
+            &cwd,
+            existing_manifest.as_ref().map(|m| m.version.as_str()),
+            git_ref.as_deref().unwrap_or("HEAD"),
+        )?),
+        None => None,
+    };
     let changelog_text = match changelog {
         Some(cl) => cl,
-        None => utils::read_changelog(&cwd, &version)
+        None => utils::read_changelog(&source_dir, &version)?
             .unwrap_or_else(|| format!("Release {}", version)),
     };
 
-    // 6. Read README for product page
-    let readme = read_readme(&cwd);
+    // 6. Read README for product page: --readme, then readme_path from config
+    // (when this product has that file), then detection
+    let readme_path = readme_flag.or_else(|| {
+        config::settings().readme_path.map(|p| source_dir.join(p)).filter(|p| p.is_file())
+    });
+    let readme = match readme_path {
+        Some(ref path) => Some(
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read README {}", path.display()))?,
+        ),
+        None => read_readme(&source_dir),
+    };
+    let readme_path = readme_path.map(|p| p.display().to_string());
+    let license = resolve_license(license, &source_dir)?;
 
-    execute_publish(&client, &me.user.username, &cwd, PublishContext {
+    execute_publish(&client, &namespace, &cwd, PublishContext {
         slug,
         product_name,
         product_desc,
@@ -321,19 +1065,76 @@ async fn cmd_publish(
         version,
         changelog_text,
         readme,
+        readme_path,
+        visibility: visibility.level(),
+        git_tag,
+        source_dir,
+        git_ref,
+        with_git_history,
+        team,
+        preview,
+        assets,
+        links,
+        detected_links,
+        tags,
         existing_manifest,
     }).await
 }
 
-async fn cmd_remake(
-    version: String,
-    slug_flag: Option<String>,
-    changelog: Option<String>,
-    category: String,
-    name_flag: Option<String>,
-    description_flag: Option<String>,
-    license: String,
-) -> Result<()> {
+/// Publish each workspace member in turn (same flags, per-member version and
+/// category), continuing past failures and summarizing at the end.
+async fn cmd_publish_workspace(args: PublishArgs) -> Result<()> {
+    let root = start_dir(args.path.as_deref())?;
+    let ws = workspace::load(&root)?;
+    progress!("Publishing {} workspace member(s)", ws.members.len());
+
+    let mut results = Vec::new();
+    for member in &ws.members {
+        progress!("\n=== {} @ {} ===", member.path, member.version);
+        let member_args = PublishArgs {
+            version: Some(member.version.clone()),
+            category: member.category.clone().or_else(|| args.category.clone()),
+            path: Some(member.dir(&root)),
+            workspace: false,
+            ..args.clone()
+        };
+        let result = Box::pin(cmd_publish(member_args)).await;
+        if let Err(ref e) = result {
+            eprintln!("Error: {:#}", e);
+        }
+        results.push((member, result));
+    }
+
+    let width = ws.members.iter().map(|m| m.path.len()).max().unwrap_or(0).max("MEMBER".len());
+    progress!("\n{:<width$}  {:<10}  RESULT", "MEMBER", "VERSION", width = width);
+    for (member, result) in &results {
+        let outcome = match result {
+            Ok(()) => "published".to_string(),
+            Err(e) => format!("failed: {}", utils::truncate_str(&e.to_string().replace('\n', " "), 60)),
+        };
+        progress!("{:<width$}  {:<10}  {}", member.path, member.version, outcome, width = width);
+    }
+
+    let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+    if failed > 0 {
+        return Err(anyhow::anyhow!("{} of {} workspace member(s) failed to publish", failed, results.len()));
+    }
+    Ok(())
+}
+
+async fn cmd_remake(args: RemakeArgs) -> Result<()> {
+    let RemakeArgs {
+        version,
+        slug: slug_flag,
+        changelog,
+        category,
+        name: name_flag,
+        description: description_flag,
+        license,
+        visibility,
+        git_tag,
+    } = args;
+
     let token = auth::get_token().await?;
     let client = api::BaroClient::new(&token);
 
@@ -342,7 +1143,7 @@ async fn cmd_remake(
     println!("Remaking as {}...", me.user.username);
 
     // 2. Read manifest — require fork origin
-    let cwd = std::env::current_dir()?;
+    let cwd = publish_root(None)?;
     let existing_manifest = manifest::read(&cwd).ok();
 
     let manifest = existing_manifest.as_ref().ok_or_else(|| {
@@ -386,12 +1187,8 @@ async fn cmd_remake(
     // 6. Self-fork confirmation
     let origin_parts: Vec<&str> = origin.splitn(2, '/').collect();
     if origin_parts.len() == 2 && origin_parts[0] == me.user.username {
-        eprint!("You're remaking your own product ({}). Continue? [Y/n] ", origin);
-        std::io::Write::flush(&mut std::io::stderr())?;
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-        let input = input.trim().to_lowercase();
-        if !input.is_empty() && input != "y" && input != "yes" {
+        let question = format!("You're remaking your own product ({}). Continue?", origin);
+        if !wizard::confirm(&question, "Pass --yes to remake your own product.")? {
             println!("Cancelled.");
             return Ok(());
         }
@@ -407,14 +1204,15 @@ async fn cmd_remake(
     // 8. Resolve changelog
     let changelog_text = match changelog {
         Some(cl) => cl,
-        None => utils::read_changelog(&cwd, &version)
+        None => utils::read_changelog(&cwd, &version)?
             .unwrap_or_else(|| format!("Release {}", version)),
     };
 
     // 9. Read README
     let readme = read_readme(&cwd);
+    let license = resolve_license(license, &cwd)?;
 
-    println!("Remaking from {} → {}/{}...", origin, me.user.username, slug);
+    println!("Remaking from {} {} {}/{}...", origin, ui::arrow(), me.user.username, slug);
 
     execute_publish(&client, &me.user.username, &cwd, PublishContext {
         slug,
@@ -425,6 +1223,19 @@ async fn cmd_remake(
         version,
         changelog_text,
         readme,
+        readme_path: None,
+        visibility: visibility.level(),
+        git_tag,
+        source_dir: cwd.clone(),
+        git_ref: None,
+        with_git_history: false,
+        team: None,
+        preview: false,
+        assets: Vec::new(),
+        // The remake's build file still points at the origin's pages
+        links: types::ProductLinks::default(),
+        detected_links: types::ProductLinks::default(),
+        tags: Vec::new(),
         existing_manifest,
     }).await
 }
@@ -435,70 +1246,93 @@ struct ForkResult {
     username: String,
     slug: String,
     size_bytes: i64,
+    asset_count: usize,
+    setup: Option<String>,
+    dependencies: Vec<String>,
 }
 
-/// Core fork implementation. Returns metadata about the fork.
-async fn fork_impl(product: &str, dir_override: Option<&str>) -> Result<ForkResult> {
-    // Parse user/slug[@version]
-    let (user_slug, version) = if let Some(idx) = product.rfind('@') {
-        (&product[..idx], Some(&product[idx + 1..]))
-    } else {
-        (product, None)
+/// `user/slug` ids to draw "did you mean" suggestions from: forks and saved
+/// searches seen locally, plus the registry's results for `q` (or its most
+/// downloaded products).
+async fn known_products(client: &api::BaroClient, q: Option<&str>) -> Vec<String> {
+    let mut known = upstream_cache::origins();
+    known.extend(saved_search::known_results());
+    if let Ok(found) = client.list_products(q, &types::ProductFilters::default(), "downloads", 50, 1, false).await {
+        known.extend(found.products.iter().filter_map(|p| {
+            Some(format!("{}/{}", p.publisher.as_ref()?.username, p.slug))
+        }));
+    }
+    known.sort();
+    known.dedup();
+    known
+}
+
+/// `get_product`, adding "did you mean" suggestions when the product isn't found.
+/// Candidates come from local caches (upstream checks, saved searches) and a
+/// registry search for the slug.
+async fn get_product_or_suggest(client: &api::BaroClient, username: &str, slug: &str) -> Result<types::Product> {
+    let err = match client.get_product(username, slug).await {
+        Ok(p) => return Ok(p),
+        // Only a missing product is worth suggesting alternatives for
+        Err(e) if error::status(&e) != Some(404) => return Err(e),
+        Err(e) => e,
     };
+    let known = known_products(client, Some(slug)).await;
+    let candidates: Vec<&str> = known.iter().map(String::as_str).collect();
+    let wanted = format!("{}/{}", username, slug);
+    let suggestions = utils::closest_matches(&wanted, &candidates, 3);
+    if suggestions.is_empty() || suggestions.contains(&wanted.as_str()) {
+        Err(err)
+    } else {
+        Err(err.context(format!("{} not found. Did you mean: {}?", wanted, suggestions.join(", "))))
+    }
+}
 
-    let parts: Vec<&str> = user_slug.splitn(2, '/').collect();
-    if parts.len() != 2 {
+/// Core fork implementation. Returns metadata about the fork.
+async fn fork_impl(
+    product: &str,
+    dir_override: Option<&str>,
+    preview_token: Option<&str>,
+    with_assets: bool,
+    force: bool,
+    require_signature: bool,
+) -> Result<ForkResult> {
+    let (username, slug, version) = parse_product_ref(product)?;
+
+    // Check the destination before downloading anything. Like `git clone`,
+    // an existing directory is fine as long as it is empty.
+    let dest_name = dir_override.unwrap_or(slug);
+    let dest = std::path::Path::new(dest_name);
+    let replace = dest.exists() && !(dest.is_dir() && std::fs::read_dir(dest)?.next().is_none());
+    if replace && !force {
         return Err(anyhow::anyhow!(
-            "Invalid product identifier. Use: user/product or user/product@version"
+            "Destination '{}' already exists and is not an empty directory. Choose another (baro fork {} <dir>) or pass --force to replace it.",
+            dest_name, product
         ));
     }
-    let (username, slug) = (parts[0], parts[1]);
-
-    // Require authentication
-    let token = match auth::get_token().await {
-        Ok(t) => t,
-        Err(_) => {
-            eprint!("Login required to fork. Open browser to sign up? [Y/n] ");
-            std::io::Write::flush(&mut std::io::stderr())?;
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input)?;
-            let input = input.trim().to_lowercase();
-            if input.is_empty() || input == "y" || input == "yes" {
-                auth::login().await?;
-                auth::get_token().await?
-            } else {
-                return Err(anyhow::anyhow!(
-                    "Run 'baro login' to authenticate (instant with GitHub)"
-                ));
+    if replace {
+        if !dest.is_dir() {
+            return Err(anyhow::anyhow!("'{}' is a file, not a directory; --force only replaces directories", dest_name));
+        }
+        if std::env::current_dir().ok().and_then(|cwd| cwd.canonicalize().ok()) == dest.canonicalize().ok() {
+            return Err(anyhow::anyhow!("Can't replace the current directory; run from its parent or fork elsewhere"));
+        }
+        if !manifest::path(dest).exists() {
+            let question = format!("'{}' isn't a baro fork. Replace it and everything in it?", dest_name);
+            if !wizard::confirm(&question, "Pass --yes with --force to replace it anyway.")? {
+                return Err(anyhow::anyhow!("Cancelled; '{}' left unchanged", dest_name));
             }
         }
-    };
-    let client = api::BaroClient::new(&token);
-
-    // Get product info
-    let product_info = client.get_product(username, slug).await?;
-    let target_version = match version {
-        Some(v) => v.to_string(),
-        None => product_info
-            .latest_version
-            .ok_or_else(|| anyhow::anyhow!("No published releases for {}/{}", username, slug))?,
-    };
+    }
 
-    // Get download URL
+    let (client, target_version, download) = fork_download(username, slug, version, preview_token).await?;
     println!("Forking {}/{}@{}...", username, slug, target_version);
-    let download = client
-        .get_download(username, slug, &target_version)
-        .await?;
 
-    // Download file from R2
-    let bytes = client.download_from_r2(&download.download_url).await?;
-
-    // Verify hash
-    use sha2::{Sha256, Digest};
-    let mut hasher = Sha256::new();
-    hasher.update(&bytes);
-    let actual_hash = format!("{:x}", hasher.finalize());
+    // Stream to a temp file, hashing as it arrives
+    let archive_path = download_temp_path(&download);
+    let (size_bytes, actual_hash) = client.download_to_file(&download.download_url, &archive_path).await?;
     if actual_hash != download.file_hash_sha256 {
+        let _ = std::fs::remove_file(&archive_path);
         return Err(anyhow::anyhow!(
             "Hash mismatch! Expected: {}, got: {}",
             download.file_hash_sha256,
@@ -506,102 +1340,860 @@ async fn fork_impl(product: &str, dir_override: Option<&str>) -> Result<ForkResu
         ));
     }
 
-    // Extract
-    let dest_name = dir_override.unwrap_or(slug);
-    let dest = std::path::Path::new(dest_name);
-    if dest.exists() {
-        return Err(anyhow::anyhow!(
-            "Directory '{}' already exists. Remove it first or use --dir to specify a different location.",
-            dest_name
-        ));
+    // Verify the publisher's signature over the hash, when the release is signed
+    match download.signature {
+        Some(ref signature) => {
+            let keys = client.list_signing_keys(username).await?;
+            if keys.keys.is_empty() {
+                if require_signature {
+                    let _ = std::fs::remove_file(&archive_path);
+                    return Err(anyhow::anyhow!(
+                        "{}/{}@{} is signed, but {} has no registered signing key to check it against; not extracting (--require-signature)",
+                        username, slug, target_version, username
+                    ));
+                }
+                eprintln!("Warning: release is signed but {} has no registered signing key", username);
+            } else if let Some(key) = keys.keys.iter().find(|k| signing::verify_hash(&k.public_key, &actual_hash, signature)) {
+                println!(
+                    "  Signature verified: signed by {} (key {})",
+                    username,
+                    key.public_key.get(..12).unwrap_or(&key.public_key)
+                );
+            } else {
+                let _ = std::fs::remove_file(&archive_path);
+                return Err(anyhow::anyhow!(
+                    "Signature verification failed for {}/{}@{}. The archive may have been tampered with.",
+                    username, slug, target_version
+                ));
+            }
+        }
+        None if require_signature => {
+            let _ = std::fs::remove_file(&archive_path);
+            return Err(anyhow::anyhow!(
+                "{}/{}@{} is not signed; not extracting it (--require-signature)",
+                username, slug, target_version
+            ));
+        }
+        None => {}
     }
-    packaging::extract_archive(&bytes, dest)?;
 
-    // Write manifest
-    let m = types::Manifest {
+    // Extract into a staging dir next to the destination, then move it into place
+    let mut m = types::Manifest {
         origin: Some(format!("{}/{}", username, slug)),
         version: target_version.clone(),
         cloned_at: Some(chrono::Utc::now().to_rfc3339()),
         file_hash: Some(actual_hash),
+        origin_version: Some(target_version.clone()),
+        origin_release_id: download.release_id.clone(),
+        remotes: Vec::new(),
+        ignored_versions: Vec::new(),
+        pinned_version: None,
         slug: None,
         product_id: None,
         publisher: None,
+        setup: None,
+        dependencies: Vec::new(),
+    };
+    if let Some(parent) = dest.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    // A replaced destination is assembled beside it, never inside
+    let staging = if replace { staging_sibling(dest) } else { staging_dir_for(dest) };
+    let staged = packaging::extract_archive_file(&archive_path, &staging).and_then(|_| {
+        m.setup = take_extra(&staging, SETUP_SCRIPT_PATH).map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        m.dependencies = take_extra(&staging, DEPENDENCIES_PATH)
+            .map(|deps| deps.lines().map(str::trim).filter(|l| !l.is_empty()).map(String::from).collect())
+            .unwrap_or_default();
+        manifest::write(&staging, &m)?;
+        baseline::record(&staging, &target_version)
+    });
+    let _ = std::fs::remove_file(&archive_path);
+    let staged = match staged {
+        Ok(()) if with_assets => download_assets(&client, username, slug, &target_version, &staging).await,
+        Ok(()) => Ok(0),
+        Err(e) => Err(e),
+    };
+    let placed = |n| if replace { swap_into_place(&staging, dest) } else { move_into_place(&staging, dest) }.map(|_| n);
+    let asset_count = match staged.and_then(placed) {
+        Ok(n) => n,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(e);
+        }
     };
-    manifest::write(dest, &m)?;
 
     Ok(ForkResult {
         dest_dir: dest_name.to_string(),
         version: target_version,
         username: username.to_string(),
         slug: slug.to_string(),
-        size_bytes: bytes.len() as i64,
+        size_bytes: size_bytes as i64,
+        asset_count,
+        setup: m.setup,
+        dependencies: m.dependencies,
     })
 }
 
-async fn cmd_fork(product: &str, dir_override: Option<&str>) -> Result<()> {
-    let result = fork_impl(product, dir_override).await?;
+/// Read and remove a file the publisher's manifest put in the archive
+/// (setup command, dependencies); its contents move into the fork's manifest.
+fn take_extra(dir: &std::path::Path, name: &str) -> Option<String> {
+    let path = dir.join(name);
+    let contents = std::fs::read_to_string(&path).ok()?;
+    let _ = std::fs::remove_file(&path);
+    Some(contents)
+}
+
+/// Where a release archive is downloaded to. Named by the expected hash so that
+/// rerunning an interrupted command resumes the same partial download.
+fn download_temp_path(download: &types::DownloadResponse) -> std::path::PathBuf {
+    let hash_name: String = download.file_hash_sha256.chars().filter(|c| c.is_ascii_hexdigit()).take(64).collect();
+    std::env::temp_dir().join(format!("baro-download-{}.tar.gz", hash_name))
+}
+
+/// A release archive downloaded to a temp file and checked against its hash,
+/// for reading without extracting it. Removed on drop.
+struct DownloadedArchive {
+    path: std::path::PathBuf,
+    size: u64,
+}
+
+impl DownloadedArchive {
+    /// Stream through the tar.gz from the start; each call reads it afresh.
+    fn open(&self) -> Result<impl std::io::Read> {
+        let file = std::fs::File::open(&self.path).with_context(|| format!("Failed to open {}", self.path.display()))?;
+        Ok(std::io::BufReader::new(file))
+    }
+}
+
+impl Drop for DownloadedArchive {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+async fn download_archive(client: &api::BaroClient, download: &types::DownloadResponse) -> Result<DownloadedArchive> {
+    let path = download_temp_path(download);
+    let (size, hash) = client.download_to_file(&download.download_url, &path).await?;
+    let archive = DownloadedArchive { path, size };
+    if hash != download.file_hash_sha256 {
+        return Err(anyhow::anyhow!("Hash mismatch! Expected: {}, got: {}", download.file_hash_sha256, hash));
+    }
+    Ok(archive)
+}
+
+/// Resolve and fetch the download for a release: anonymously when not logged
+/// in, falling back to the login prompt if the registry answers 401/403
+/// (private products, anonymous rate limits). Returns the client to keep using.
+async fn fork_download(
+    username: &str,
+    slug: &str,
+    version: Option<&str>,
+    preview_token: Option<&str>,
+) -> Result<(api::BaroClient, String, types::DownloadResponse)> {
+    let (client, anonymous) = match auth::get_token().await {
+        Ok(token) => (api::BaroClient::new(&token), false),
+        Err(_) => (api::BaroClient::anonymous(), true),
+    };
+    match fetch_release(&client, username, slug, version, preview_token).await {
+        Ok((v, download)) => Ok((client, v, download)),
+        Err(e) if anonymous && matches!(error::status(&e), Some(401 | 403)) => {
+            eprintln!("{}/{} needs a login: {}", username, slug, e);
+            let client = login_client().await?;
+            let (v, download) = fetch_release(&client, username, slug, version, preview_token).await?;
+            Ok((client, v, download))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Client for an authenticated download, offering to log in first if needed.
+async fn login_client() -> Result<api::BaroClient> {
+    let token = match auth::get_token().await {
+        Ok(t) => t,
+        Err(_) => {
+            let login_hint = "Run 'baro login' first (instant with GitHub).";
+            // Nobody can finish a browser login in CI, so --yes doesn't start one there
+            if !wizard::is_interactive() {
+                return Err(anyhow::anyhow!("Login required to fork. {}", login_hint));
+            }
+            if wizard::confirm("Login required to fork. Open browser to sign up?", login_hint)? {
+                auth::login().await?;
+                auth::get_token().await?
+            } else {
+                return Err(anyhow::anyhow!(
+                    "Run 'baro login' to authenticate (instant with GitHub)"
+                ));
+            }
+        }
+    };
+    Ok(api::BaroClient::new(&token))
+}
+
+/// The requested version (or the product's latest release) and its download.
+async fn fetch_release(
+    client: &api::BaroClient,
+    username: &str,
+    slug: &str,
+    version: Option<&str>,
+    preview_token: Option<&str>,
+) -> Result<(String, types::DownloadResponse)> {
+    let product_info = get_product_or_suggest(client, username, slug).await?;
+    if let Some(notice) = product_info.deprecation_notice(&format!("{}/{}", username, slug)) {
+        eprintln!("{}", notice);
+    }
+    let target_version = match version {
+        Some(v) => v.to_string(),
+        None => product_info
+            .latest_version
+            .ok_or_else(|| anyhow::anyhow!("No published releases for {}/{}", username, slug))?,
+    };
+    let download = client.get_download(username, slug, &target_version, preview_token).await?;
+    Ok((target_version, download))
+}
+
+/// Lines of the README shown by `fork --list`.
+const LIST_README_LINES: usize = 15;
 
+/// `baro fork --list`: download the release to a temp file, verify it, and
+/// show what forking it would unpack.
+async fn cmd_fork_list(product: &str, preview_token: Option<&str>) -> Result<()> {
+    let (username, slug, version) = parse_product_ref(product)?;
+    let (client, target_version, download) = fork_download(username, slug, version, preview_token).await?;
+    let archive = download_archive(&client, &download).await?;
+
+    let files = packaging::list_archive(archive.open()?)?;
+    let unpacked: u64 = files.iter().map(|f| f.size).sum();
+    println!(
+        "{}/{}@{}: {} files, {} unpacked ({} download)\n",
+        username,
+        slug,
+        target_version,
+        files.len(),
+        utils::format_bytes(unpacked as i64),
+        utils::format_bytes(archive.size as i64)
+    );
+    for line in packaging::render_tree(&files) {
+        println!("  {}", line);
+    }
+    if let Some(readme) = packaging::read_archive_readme(archive.open()?)? {
+        println!("\nREADME:");
+        let lines: Vec<&str> = readme.lines().collect();
+        for line in lines.iter().take(LIST_README_LINES) {
+            println!("  {}", line);
+        }
+        if lines.len() > LIST_README_LINES {
+            println!("  ... ({} more lines)", lines.len() - LIST_README_LINES);
+        }
+    }
+    println!("\nNothing was written. Fork it with: baro fork {}/{}@{}", username, slug, target_version);
+    Ok(())
+}
+
+/// Where `fork --assets` puts a release's extra assets (outside the packaged tree).
+const ASSETS_DIR: &str = ".baro/assets";
+
+/// Download and verify a release's extra assets into `<dir>/.baro/assets/`.
+async fn download_assets(
+    client: &api::BaroClient,
+    username: &str,
+    slug: &str,
+    version: &str,
+    dir: &std::path::Path,
+) -> Result<usize> {
+    let assets = client.list_assets(username, slug, version).await?.assets;
+    if assets.is_empty() {
+        println!("  No extra assets in this release");
+        return Ok(0);
+    }
+    let assets_dir = dir.join(ASSETS_DIR);
+    std::fs::create_dir_all(&assets_dir)?;
+    for asset in &assets {
+        // Names come from the registry; never let one escape the assets directory
+        if asset.name.contains(['/', '\\']) || asset.name.starts_with('.') {
+            return Err(anyhow::anyhow!("Refusing asset with unsafe name '{}'", asset.name));
+        }
+        let dest = assets_dir.join(&asset.name);
+        let (_, hash) = client.download_to_file(&asset.download_url, &dest).await?;
+        if hash != asset.file_hash_sha256 {
+            return Err(anyhow::anyhow!(
+                "Hash mismatch for asset {}! Expected: {}, got: {}",
+                asset.name, asset.file_hash_sha256, hash
+            ));
+        }
+        println!("  Asset {} ({})", asset.name, utils::format_bytes(asset.file_size_bytes));
+    }
+    Ok(assets.len())
+}
+
+/// Move an assembled fork to `dest`: a rename, or when `dest` is an existing
+/// empty directory (holding only the staging dir), a rename of each top-level entry into it.
+fn move_into_place(staging: &std::path::Path, dest: &std::path::Path) -> Result<()> {
+    if !dest.exists() {
+        std::fs::rename(staging, dest)?;
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(staging)? {
+        let entry = entry?;
+        std::fs::rename(entry.path(), dest.join(entry.file_name()))?;
+    }
+    std::fs::remove_dir(staging)?;
+    Ok(())
+}
+
+/// Replace the non-empty directory `dest` with `staging`: move the old tree
+/// aside, move the new one in, then delete the old one. If the second move
+/// fails, the old tree is put back.
+fn swap_into_place(staging: &std::path::Path, dest: &std::path::Path) -> Result<()> {
+    let aside = staging_sibling(dest);
+    let old = aside.with_file_name(format!("{}-old", aside.file_name().unwrap_or_default().to_string_lossy()));
+    std::fs::rename(dest, &old).with_context(|| format!("Failed to move {} aside", dest.display()))?;
+    if let Err(e) = std::fs::rename(staging, dest) {
+        let _ = std::fs::rename(&old, dest);
+        return Err(anyhow::Error::from(e).context(format!("Failed to replace {}", dest.display())));
+    }
+    if let Err(e) = std::fs::remove_dir_all(&old) {
+        eprintln!("Warning: replaced {}, but could not remove the old copy at {}: {}", dest.display(), old.display(), e);
+    }
+    Ok(())
+}
+
+/// Hidden directory used to assemble a fork before it appears under its real
+/// name: a sibling of `dest`, or inside it when `dest` is an existing empty directory.
+fn staging_dir_for(dest: &std::path::Path) -> std::path::PathBuf {
+    if dest.is_dir() {
+        return dest.join(format!(".baro-partial-{}", uuid::Uuid::new_v4()));
+    }
+    staging_sibling(dest)
+}
+
+fn staging_sibling(dest: &std::path::Path) -> std::path::PathBuf {
+    let name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "fork".to_string());
+    let parent = dest.parent().unwrap_or_else(|| std::path::Path::new(""));
+    parent.join(format!(".{}.baro-partial-{}", name, uuid::Uuid::new_v4()))
+}
+
+async fn cmd_fork(args: ForkArgs) -> Result<()> {
+    let dest = args.dest.as_deref().or(args.dir.as_deref());
+    let result = fork_impl(
+        &args.product,
+        dest,
+        args.preview_token.as_deref(),
+        args.assets,
+        args.force,
+        args.require_signature,
+    )
+    .await?;
+
+    let shown = match result.dest_dir.as_str() {
+        "." => "./".to_string(),
+        d if d.starts_with(['.', '/']) => format!("{}/", d.trim_end_matches('/')),
+        d => format!("./{}/", d.trim_end_matches('/')),
+    };
     println!(
-        "Forked {}/{}@{} → ./{}/  ({})",
+        "Forked {}/{}@{} {} {}  ({})",
         result.username,
         result.slug,
         result.version,
-        result.dest_dir,
+        ui::arrow(),
+        shown,
         utils::format_bytes(result.size_bytes)
     );
+    if result.asset_count > 0 {
+        println!("Assets: {}/{}/ ({} file(s))", result.dest_dir, ASSETS_DIR, result.asset_count);
+    }
+    if std::path::Path::new(&result.dest_dir).join(HISTORY_BUNDLE_PATH).exists() {
+        println!("Includes git history: git clone {}/{} <dir>", result.dest_dir, HISTORY_BUNDLE_PATH);
+    }
+    if !result.dependencies.is_empty() {
+        if args.no_deps {
+            println!("Dependencies not fetched (--no-deps): {}", result.dependencies.join(", "));
+        } else {
+            fetch_dependencies(&result, args.require_signature).await?;
+        }
+    }
+    if let Some(ref setup) = result.setup {
+        run_fork_setup(&result.dest_dir, setup, args.run_setup, args.no_setup)?;
+    }
     println!();
     println!("Next steps:");
-    println!("  1. Read README.md for setup instructions");
+    if result.setup.is_none() {
+        println!("  1. Read README.md for setup instructions");
+    } else {
+        println!("  1. Read README.md for anything the setup script doesn't cover");
+    }
     println!("  2. Build and run the project");
     println!("  3. Customize with AI — ask what to change");
+    println!("  4. After trying it: baro rate {}/{} <1-5>", result.username, result.slug);
+
+    Ok(())
+}
+
+/// Fork a product's dependencies, and theirs, into `<fork>/vendor/<slug>`.
+/// Each product is fetched once, at the version first asked for; a
+/// dependency already unpacked there (from an earlier run) is kept.
+async fn fetch_dependencies(root: &ForkResult, require_signature: bool) -> Result<()> {
+    let vendor = std::path::Path::new(&root.dest_dir).join(VENDOR_DIR);
+    let mut seen: std::collections::BTreeMap<String, String> = std::collections::BTreeMap::new();
+    seen.insert(format!("{}/{}", root.username, root.slug), format!("{}/{}@{}", root.username, root.slug, root.version));
+    let mut used_dirs: Vec<String> = Vec::new();
+    let mut queue: std::collections::VecDeque<String> = root.dependencies.iter().cloned().collect();
+    println!("\nDependencies:");
+    while let Some(dep) = queue.pop_front() {
+        let (username, slug, _) = parse_product_ref(&dep)?;
+        let key = format!("{}/{}", username, slug);
+        if let Some(first) = seen.get(&key) {
+            if *first != dep && !first.starts_with(&format!("{}@", dep)) {
+                eprintln!("  Note: {} is also required as {}; keeping {}", key, dep, first);
+            }
+            continue;
+        }
+        seen.insert(key, dep.clone());
+
+        // Two publishers' products with the same slug get distinct directories
+        let dir_name = if used_dirs.iter().any(|d| d == slug) { format!("{}-{}", username, slug) } else { slug.to_string() };
+        used_dirs.push(dir_name.clone());
+        let target = vendor.join(&dir_name);
+        if manifest::path(&target).exists() {
+            println!("  {} already in {}", dep, target.display());
+            continue;
+        }
+        let target = target.to_string_lossy().to_string();
+        let result = fork_impl(&dep, Some(&target), None, false, false, require_signature)
+            .await
+            .with_context(|| format!("Failed to fetch dependency {}", dep))?;
+        println!("  {}/{}@{} {} {}/", result.username, result.slug, result.version, ui::arrow(), target);
+        queue.extend(result.dependencies);
+    }
+    Ok(())
+}
 
+/// Offer to run a fork's setup command. It is code from the download, so only
+/// `--run-setup` or an explicit yes on a terminal runs it (`--yes` does not).
+fn run_fork_setup(dir: &str, setup: &str, run: bool, skip: bool) -> Result<()> {
+    println!("\nSetup script: {}", setup);
+    let run = run
+        || (!skip && wizard::is_interactive() && wizard::confirm_explicit("Run it now in the fork's directory?")?);
+    if !run {
+        println!("Not run. To run it later: cd {} && {}", dir, setup);
+        return Ok(());
+    }
+    let status = if cfg!(windows) {
+        std::process::Command::new("cmd").args(["/C", setup]).current_dir(dir).status()
+    } else {
+        std::process::Command::new("sh").args(["-c", setup]).current_dir(dir).status()
+    }
+    .context("Failed to start the setup script")?;
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "Setup script failed ({}). The fork is in place at {}; fix the problem and run: {}",
+            status, dir, setup
+        ));
+    }
+    println!("Setup complete.");
     Ok(())
 }
 
-async fn cmd_search(query: &str, category: Option<&str>, sort: &str, limit: u32) -> Result<()> {
+async fn cmd_search(args: SearchArgs) -> Result<()> {
+    let SearchArgs {
+        query,
+        category,
+        tags,
+        publisher,
+        exclude_publishers,
+        min_rating,
+        min_forks,
+        remakes_of,
+        sort,
+        limit,
+        page,
+        explain_ranking,
+        save,
+        saved,
+        diff,
+        interactive,
+        table,
+        wide,
+        offline,
+    } = args;
+
+    if interactive && !(wizard::is_interactive() && std::io::stdout().is_terminal()) {
+        return Err(anyhow::anyhow!("--interactive needs a terminal; drop it to print the results"));
+    }
+    // The full-screen browser doesn't work with screen readers or dumb terminals
+    let interactive = if interactive && ui::accessible() {
+        eprintln!("Accessible mode: listing results instead of opening the interactive browser.");
+        false
+    } else {
+        interactive
+    };
+    if min_rating.is_some_and(|r| !(1.0..=5.0).contains(&r)) {
+        return Err(anyhow::anyhow!("--min-rating is an average star rating from 1 to 5"));
+    }
+    if diff && saved.is_none() {
+        return Err(anyhow::anyhow!("--diff compares against a saved search's last run; use it with --saved <name>"));
+    }
+
+    // A saved search supplies the query and filters
+    let previous = saved.as_deref().map(saved_search::get).transpose()?;
+    let search = match previous {
+        Some(ref prev) => prev.clone(),
+        None => saved_search::SavedSearch {
+            query: query.unwrap_or_default(),
+            category,
+            tags: utils::normalize_tags(&tags)?,
+            // `@alice` reads naturally too
+            publisher: publisher.map(|p| p.trim_start_matches('@').to_string()),
+            exclude_publishers: exclude_publishers.iter().map(|p| p.trim_start_matches('@').to_string()).collect(),
+            min_rating,
+            min_forks,
+            remakes_of,
+            sort,
+            limit,
+            last_results: Vec::new(),
+            last_run_at: None,
+        },
+    };
+
     let client = api::BaroClient::anonymous();
-    let resp = client
-        .list_products(Some(query), category, sort, limit, 1)
-        .await?;
+    if let Some(ref origin) = search.remakes_of {
+        let (username, slug, version) = parse_product_ref(origin)?;
+        if version.is_some() {
+            return Err(anyhow::anyhow!("--remakes-of covers every version; pass {}/{} without @version", username, slug));
+        }
+        // A typo would otherwise just find no remakes
+        get_product_or_suggest(&client, username, slug).await?;
+    }
+    if let Some(category) = search.category.as_ref().filter(|_| !offline) {
+        // An unknown category just returns nothing; say so, with suggestions
+        if let Ok(categories) = client.list_categories_cached().await {
+            if !categories.iter().any(|c| &c.slug == category) {
+                let slugs: Vec<&str> = categories.iter().map(|c| c.slug.as_str()).collect();
+                let suggestions = utils::closest_matches(category, &slugs, 3);
+                let hint = if suggestions.is_empty() {
+                    format!("Available:\n{}", utils::format_category_list(&categories))
+                } else {
+                    format!("Did you mean: {}?", suggestions.join(", "))
+                };
+                return Err(anyhow::anyhow!("Unknown category '{}'. {}", category, hint));
+            }
+        }
+    }
+    let filters = types::ProductFilters {
+        category: search.category.as_deref(),
+        tags: &search.tags,
+        publisher: search.publisher.as_deref(),
+        exclude_publishers: &search.exclude_publishers,
+        min_rating: search.min_rating,
+        min_forks: search.min_forks,
+        remakes_of: search.remakes_of.as_deref(),
+    };
+    // The registry may not apply the thresholds itself; apply them again here
+    let mut below_threshold = 0;
+    let mut keep_popular = |resp: &mut types::ProductsResponse| {
+        let before = resp.products.len();
+        resp.products.retain(|p| utils::meets_thresholds(p.stats.as_ref(), search.min_rating, search.min_forks));
+        below_threshold += before - resp.products.len();
+    };
+    let (client, search, filters) = (&client, &search, &filters);
+    let fetch_page = |page: u32| async move {
+        let path = api::products_path(
+            Some(search.query.as_str()).filter(|q| !q.is_empty()),
+            filters,
+            &search.sort,
+            search.limit,
+            page,
+            explain_ranking,
+        );
+        let (resp, age) = client.list_products_cached(&path, offline).await?;
+        if let Some(age) = age {
+            eprintln!("(cached {})", ui::relative(age));
+        }
+        Ok::<_, anyhow::Error>(resp)
+    };
+    let mut page = page;
+    let mut resp = fetch_page(page).await?;
+    keep_popular(&mut resp);
+
+    let result_ids: Vec<String> = resp
+        .products
+        .iter()
+        .map(|p| format!("{}/{}", p.publisher.as_ref().map(|r| r.username.as_str()).unwrap_or("?"), p.slug))
+        .collect();
+    let new_ids = match previous {
+        Some(ref prev) if diff => saved_search::new_results(&prev.last_results, &result_ids),
+        _ => Vec::new(),
+    };
+    if let Some(name) = save.as_deref().or(saved.as_deref()) {
+        saved_search::put(name, saved_search::SavedSearch {
+            last_results: result_ids.clone(),
+            last_run_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..search.clone()
+        })?;
+        if save.is_some() {
+            println!("Saved search '{}'. Re-run with: baro search --saved {}\n", name, name);
+        }
+    }
 
     if resp.products.is_empty() {
-        println!("No products found matching '{}'", query);
+        if below_threshold > 0 {
+            println!("None of the {} results on page {} meet --min-rating/--min-forks", below_threshold, page);
+            if u64::from(page) * u64::from(search.limit.max(1)) < resp.total {
+                println!("Next page: add --page {}", page + 1);
+            }
+        } else if page > 1 && resp.total > 0 {
+            println!("No results on page {} ({} in total)", page, resp.total);
+        } else if let Some(ref origin) = search.remakes_of {
+            println!("No published remakes of {}{}", origin, if search.query.is_empty() { String::new() } else { format!(" matching '{}'", search.query) });
+        } else {
+            let mut what = "No products found".to_string();
+            if !search.query.is_empty() {
+                what.push_str(&format!(" matching '{}'", search.query));
+            }
+            if let Some(ref category) = search.category {
+                what.push_str(&format!(" in {}", category));
+            }
+            if !search.tags.is_empty() {
+                what.push_str(&format!(" tagged {}", search.tags.join(", ")));
+            }
+            if let Some(ref publisher) = search.publisher {
+                what.push_str(&format!(" by {}", publisher));
+            }
+            println!("{}", what);
+            if !search.query.is_empty() && !offline {
+                let known = known_products(client, None).await;
+                let candidates: Vec<&str> = known.iter().map(String::as_str).collect();
+                let suggestions = utils::closest_products(&search.query, &candidates, 3);
+                if !suggestions.is_empty() {
+                    let quoted: Vec<String> = suggestions.iter().map(|s| format!("`{}`", s)).collect();
+                    println!("Did you mean {}?", quoted.join(", "));
+                }
+            }
+        }
+        return Ok(());
+    }
+    if interactive {
+        let mut title = match search.query.as_str() {
+            "" => "baro search".to_string(),
+            q => format!("baro search '{}'", q),
+        };
+        if let Some(ref publisher) = search.publisher {
+            title.push_str(&format!(" by {}", publisher));
+        }
+        let entries = resp.products.iter().map(browse::Entry::from_product).collect();
+        let mut browser = browse::Browser::new(title, entries, resp.total);
+        let step = {
+            let screen = browse::Screen::enter()?;
+            let mut previews = std::collections::HashMap::new();
+            loop {
+                let (width, height) = screen.size();
+                let Some(entry) = browser.selected() else { break browse::Step::Quit };
+                let (id, version) = (entry.id.clone(), entry.version.clone());
+                if !previews.contains_key(&id) {
+                    screen.draw(&browser.render("Loading README...", width, height))?;
+                    let preview = search_preview(client, &id, version.as_deref()).await;
+                    previews.insert(id.clone(), preview);
+                }
+                screen.draw(&browser.render(&previews[&id], width, height))?;
+                match browser.handle(screen.read_key()?) {
+                    browse::Step::Redraw => {}
+                    browse::Step::LoadMore => {
+                        page += 1;
+                        let mut more = fetch_page(page).await?;
+                        keep_popular(&mut more);
+                        browser.extend(more.products.iter().map(browse::Entry::from_product).collect());
+                    }
+                    browse::Step::Open(id) => {
+                        let (username, slug) = id.split_once('/').unwrap_or(("", &id));
+                        let url = config::product_url(username, slug);
+                        browser.status = match open::that(&url) {
+                            Ok(()) => format!("Opened {}", url),
+                            Err(_) => format!("Couldn't start a browser: {}", url),
+                        };
+                    }
+                    step => break step,
+                }
+            }
+        };
+        // The screen is restored before forking prints anything
+        if let browse::Step::Fork(product) = step {
+            return cmd_fork(ForkArgs {
+                product,
+                dest: None,
+                dir: None,
+                preview_token: None,
+                assets: false,
+                force: false,
+                list: false,
+                require_signature: false,
+                no_deps: false,
+                run_setup: false,
+                no_setup: false,
+            })
+            .await;
+        }
         return Ok(());
     }
 
-    for p in &resp.products {
-        let pub_name = p
-            .publisher
-            .as_ref()
-            .map(|r| r.username.as_str())
-            .unwrap_or("?");
-        let cat_name = p
-            .category
-            .as_ref()
-            .map(|c| c.slug.as_str())
-            .unwrap_or("?");
-        let ver = p.latest_version.as_deref().unwrap_or("-");
-        let desc = utils::truncate_str(&p.description, 60);
-
-        println!("{}/{:<20} v{:<8} [{}]", pub_name, p.slug, ver, cat_name);
-        println!("  {}", desc);
-
-        if let Some(ref stats) = p.stats {
-            let forks = stats.fork_count.unwrap_or(0);
-            let rating = stats
-                .avg_rating
-                .map(|r| format!("{:.1}/5", r))
-                .unwrap_or_else(|| "-".to_string());
-            let rc = stats.rating_count.unwrap_or(0);
-            println!("  Forks: {}  Rating: {} ({})  Updated: {}", forks, rating, rc, &p.updated_at[..10]);
+    let print_page = |products: &[types::Product], new_ids: &[&str]| {
+        if table {
+            print_product_table(&products.iter().collect::<Vec<_>>(), None, wide);
+            println!();
+            return;
         }
-        println!();
+        for p in products {
+            let id = format!("{}/{}", p.publisher.as_ref().map(|r| r.username.as_str()).unwrap_or("?"), p.slug);
+            print_search_result(p, new_ids.contains(&id.as_str()), explain_ranking);
+        }
+    };
+    if let Some(ref origin) = search.remakes_of {
+        println!("Remakes of {}:\n", origin);
+    }
+    print_page(&resp.products, &new_ids);
+    // Further pages on request; the first one is what --saved/--diff remember
+    let limit = u64::from(search.limit.max(1));
+    let first_page = page;
+    while u64::from(page) * limit < resp.total && wizard::prompt_more(u64::from(page) * limit, resp.total)? {
+        page += 1;
+        resp = fetch_page(page).await?;
+        if resp.products.is_empty() {
+            break;
+        }
+        keep_popular(&mut resp);
+        print_page(&resp.products, &[]);
+    }
+
+    let first = u64::from(first_page - 1) * limit + 1;
+    let last = (u64::from(page) * limit).min(resp.total);
+    let excluded = match search.exclude_publishers.as_slice() {
+        [] => String::new(),
+        hidden => format!(", hiding {}", hidden.join(", ")),
+    };
+    let hidden = match below_threshold {
+        0 => String::new(),
+        n => format!(", {} below --min-rating/--min-forks hidden", n),
+    };
+    println!("Found {} results (showing {}-{}{}{})", resp.total, first, last, excluded, hidden);
+    if last < resp.total && !wizard::is_interactive() {
+        println!("Next page: add --page {}", page + 1);
+    }
+    if diff {
+        let since = previous
+            .as_ref()
+            .and_then(|p| p.last_run_at.as_deref())
+            .map(|t| format!(" since {}", ui::timestamp(t)))
+            .unwrap_or_default();
+        println!("{} new{}", new_ids.len(), since);
     }
+    Ok(())
+}
 
-    println!("Found {} results (showing {})", resp.total, resp.products.len());
+/// `baro browse` without a category: what there is to browse.
+async fn cmd_categories() -> Result<()> {
+    let categories = api::BaroClient::anonymous().list_categories_cached().await?;
+    println!("Categories:\n{}\n", utils::format_category_list(&categories));
+    println!("Run `baro browse <category>` for its top products.");
     Ok(())
 }
 
+/// The README of `id` (user/slug) at `version`, as plain text for the
+/// browser's preview pane; a short note if there is none.
+async fn search_preview(client: &api::BaroClient, id: &str, version: Option<&str>) -> String {
+    let (Some((username, slug)), Some(version)) = (id.split_once('/'), version) else {
+        return "(no published releases)".to_string();
+    };
+    match client.get_readme(username, slug, version).await {
+        Ok(types::ReadmeResponse { readme: Some(readme) }) => {
+            markdown::render(&readme, markdown::Style { ansi: false, plain_symbols: ui::accessible() })
+        }
+        Ok(_) => "(no README stored for this release; `baro readme` can read it from the archive)".to_string(),
+        Err(e) => format!("(couldn't load the README: {})", e),
+    }
+}
+
+/// Products as a table, one row each; the description column is cut to the
+/// terminal width unless `wide`. With `owner` (your own products) the table
+/// also shows review status and remakes.
+fn print_product_table(products: &[&types::Product], owner: Option<&str>, wide: bool) {
+    let mut headers = vec!["PRODUCT", "VERSION", "CATEGORY"];
+    if owner.is_some() {
+        headers.extend(["STATUS", "REMAKES"]);
+    }
+    headers.extend(["FORKS", "RATING", "UPDATED", "DESCRIPTION"]);
+    let rows: Vec<Vec<String>> = products
+        .iter()
+        .map(|p| {
+            let publisher = owner.or(p.publisher.as_ref().map(|r| r.username.as_str())).unwrap_or("?");
+            let stats = p.stats.as_ref();
+            let mut row = vec![
+                format!("{}/{}", publisher, p.slug),
+                p.latest_version.clone().unwrap_or_else(|| "-".to_string()),
+                p.category.as_ref().map(|c| c.slug.clone()).unwrap_or_else(|| "?".to_string()),
+            ];
+            if owner.is_some() {
+                row.push(p.review_status.clone());
+                row.push(stats.and_then(|s| s.remake_count).unwrap_or(0).to_string());
+            }
+            row.push(stats.and_then(|s| s.fork_count).unwrap_or(0).to_string());
+            row.push(stats.and_then(|s| s.avg_rating).map(|r| format!("{:.1}/5", r)).unwrap_or_else(|| "-".to_string()));
+            row.push(ui::short_timestamp(&p.updated_at));
+            row.push(p.description.clone());
+            row
+        })
+        .collect();
+    let width = if wide { None } else { ui::terminal_size().map(|(w, _)| w) };
+    for line in ui::table(&headers, &rows, headers.len() - 1, width) {
+        println!("{}", line);
+    }
+}
+
+/// One `baro search` result: name, version, category, description, tags and stats.
+fn print_search_result(p: &types::Product, is_new: bool, explain_ranking: bool) {
+    if is_new {
+        print!("NEW ");
+    }
+    let pub_name = p
+        .publisher
+        .as_ref()
+        .map(|r| r.username.as_str())
+        .unwrap_or("?");
+    let cat_name = p
+        .category
+        .as_ref()
+        .map(|c| c.slug.as_str())
+        .unwrap_or("?");
+    let ver = p.latest_version.as_deref().unwrap_or("-");
+    let desc = utils::truncate_str(&p.description, 60);
+
+    println!("{}/{:<20} v{:<8} [{}]", pub_name, p.slug, ver, cat_name);
+    println!("  {}", desc);
+    if let Some(ref msg) = p.deprecation_message {
+        println!("  Deprecated: {}", msg);
+    }
+    if !p.tags.is_empty() {
+        println!("  Tags: {}", p.tags.join(", "));
+    }
+
+    if let Some(ref stats) = p.stats {
+        let forks = stats.fork_count.unwrap_or(0);
+        let rating = stats
+            .avg_rating
+            .map(|r| format!("{:.1}/5", r))
+            .unwrap_or_else(|| "-".to_string());
+        let rc = stats.rating_count.unwrap_or(0);
+        println!("  Forks: {}  Rating: {} ({})  Updated: {}", forks, rating, rc, ui::timestamp(&p.updated_at));
+    }
+    if explain_ranking {
+        match p.ranking {
+            Some(ref r) => println!("  Ranking: {}", utils::format_ranking(r)),
+            None => println!("  Ranking: (not provided by the registry)"),
+        }
+    }
+    println!();
+}
+
 fn validate_slug(slug: &str) -> bool {
     if slug.is_empty() {
         return false;
@@ -615,11 +2207,15 @@ fn validate_slug(slug: &str) -> bool {
     slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
 }
 
-fn cmd_init(slug_flag: Option<String>) -> Result<()> {
-    let cwd = std::env::current_dir()?;
+fn cmd_init(slug_flag: Option<String>, path: Option<&std::path::Path>) -> Result<()> {
+    let cwd = start_dir(path)?;
 
     // Check if manifest already exists
     if let Ok(m) = manifest::read(&cwd) {
+        if let Some(ref origin) = m.origin {
+            print_fork_guide(&cwd, origin, &m);
+            return Ok(());
+        }
         let slug = m.slug.as_deref().unwrap_or("(not set)");
         let publisher = m.publisher.as_deref().unwrap_or("(not published yet)");
         println!("Already initialized:");
@@ -644,9 +2240,16 @@ fn cmd_init(slug_flag: Option<String>) -> Result<()> {
         origin: None,
         cloned_at: None,
         file_hash: None,
+        origin_version: None,
+        origin_release_id: None,
+        remotes: Vec::new(),
+        ignored_versions: Vec::new(),
+        pinned_version: None,
         slug: Some(slug.clone()),
         product_id: None,
         publisher: None,
+        setup: None,
+        dependencies: Vec::new(),
         version: "0.0.0".to_string(),
     };
     manifest::write(&cwd, &m)?;
@@ -656,57 +2259,939 @@ fn cmd_init(slug_flag: Option<String>) -> Result<()> {
     Ok(())
 }
 
-async fn cmd_products(status_filter: Option<String>) -> Result<()> {
+/// What to do next in a forked directory, in place of a plain manifest dump.
+fn print_fork_guide(dir: &std::path::Path, origin: &str, m: &types::Manifest) {
+    let forked = format!("{}@{}", origin, m.forked_version());
+    let when = m.cloned_at.as_deref().map(|t| format!(" ({})", ui::timestamp(t))).unwrap_or_default();
+    println!("Already initialized: this is a fork of {}{}", forked, when);
+    if let (Some(slug), Some(publisher)) = (&m.slug, &m.publisher) {
+        println!("  Remade as {}/{} v{}", publisher, slug, m.version);
+    }
+    println!();
+    println!("Next steps:");
+    println!("  Track your changes:");
+    if git::is_repo(dir) {
+        println!("    git status                 (commit your customizations as you go)");
+    } else {
+        println!("    git init && git add -A && git commit -m {}", wizard::shell_quote(&format!("Fork of {}", forked)));
+    }
+    println!("  Check upstream:");
+    println!("    baro upstream              (is {} still the latest?)", m.forked_version());
+    println!("    baro pull                  (fetch a newer release next to this one to merge)");
+    match (&m.slug, &m.publisher) {
+        (Some(_), Some(_)) => {
+            println!("  Publish your next version:");
+            println!("    baro publish --version {}", utils::bump_last_component(&m.version));
+        }
+        _ => {
+            let slug = utils::dir_to_slug(dir);
+            println!("  Remake it as your own product:");
+            println!("    baro remake --version 1.0.0 --slug {} --category <slug> --changelog \"What you changed\"", slug);
+        }
+    }
+}
+
+async fn cmd_edit(product: Option<&str>, flags: types::UpdateProductRequest) -> Result<()> {
+    let (username, slug) = published_product(product, "Set metadata with `baro publish` instead.")?;
     let token = auth::get_token().await?;
     let client = api::BaroClient::new(&token);
-    let me = client.get_me().await?;
-    let resp = client.list_my_products().await?;
-
-    let products: Vec<&types::Product> = if let Some(ref status) = status_filter {
-        resp.products.iter().filter(|p| p.review_status == *status).collect()
-    } else {
-        resp.products.iter().collect()
+    let current = client.get_product(&username, &slug).await?;
+    let before = edit::ProductFields {
+        name: current.name.clone(),
+        description: current.description.clone(),
+        category: current.category.as_ref().map(|c| c.slug.clone()).unwrap_or_default(),
+        license: current.license.clone().unwrap_or_default(),
     };
 
-    if products.is_empty() {
-        if status_filter.is_some() {
-            println!("No products with status '{}'", status_filter.unwrap());
-        } else {
-            println!("No products yet. Run `baro publish` to get started.");
+    // No flags: edit all fields in $EDITOR, sending only what changed
+    let no_flags = flags.name.is_none() && flags.description.is_none()
+        && flags.category_slug.is_none() && flags.license.is_none() && flags.links.is_empty();
+    let mut req = if no_flags {
+        if !wizard::is_interactive() {
+            return Err(anyhow::anyhow!(
+                "Nothing to change. Pass --name, --description, --category, --license or a link flag (or run on a terminal to use $EDITOR)."
+            ));
+        }
+        let after = edit::in_editor(&before)?;
+        // The editor trims values, so compare against trimmed originals
+        let changed = |old: &str, new: String| (old.trim() != new).then_some(new);
+        types::UpdateProductRequest {
+            name: changed(&before.name, after.name),
+            description: changed(&before.description, after.description),
+            category_slug: changed(&before.category, after.category),
+            license: changed(&before.license, after.license),
+            links: types::ProductLinks::default(),
+            tags: None,
         }
+    } else {
+        flags
+    };
+    if req.name.is_none() && req.description.is_none() && req.category_slug.is_none() && req.license.is_none() && req.links.is_empty() {
+        println!("No changes.");
         return Ok(());
     }
 
-    for p in &products {
-        let cat_name = p.category.as_ref().map(|c| c.slug.as_str()).unwrap_or("?");
-        let ver = p.latest_version.as_deref().unwrap_or("-");
-        let desc = utils::truncate_str(&p.description, 60);
-
-        println!(
-            "{}/{:<20} v{:<8} [{}]  {}",
-            me.user.username, p.slug, ver, cat_name, p.review_status
-        );
-        println!("  {}", desc);
-
-        if let Some(ref stats) = p.stats {
-            let forks = stats.fork_count.unwrap_or(0);
-            let rating = stats
-                .avg_rating
-                .map(|r| format!("{:.1}/5", r))
-                .unwrap_or_else(|| "-".to_string());
-            let rc = stats.rating_count.unwrap_or(0);
-            println!("  Forks: {}  Rating: {} ({})", forks, rating, rc);
+    // Same rules as publish
+    if let Some(ref desc) = req.description {
+        if desc.len() < 50 {
+            return Err(anyhow::anyhow!("Description too short ({} chars, need 50+)", desc.len()));
         }
-        println!();
     }
+    if let Some(ref category) = req.category_slug {
+        let categories = client.list_categories_cached().await?;
+        if !categories.iter().any(|c| &c.slug == category) {
+            let slugs: Vec<&str> = categories.iter().map(|c| c.slug.as_str()).collect();
+            let suggestions = utils::closest_matches(category, &slugs, 3);
+            return Err(anyhow::anyhow!(
+                "Invalid category: '{}'.{}",
+                category,
+                if suggestions.is_empty() { String::new() } else { format!(" Did you mean: {}?", suggestions.join(", ")) }
+            ));
+        }
+    }
+    if let Some(ref license) = req.license {
+        req.license = Some(spdx::normalize(license)?);
+    }
+    req.links = utils::validate_links(std::mem::take(&mut req.links), true)?;
 
-    println!("{} product{}", products.len(), if products.len() == 1 { "" } else { "s" });
-    Ok(())
-}
-
-fn cmd_status() -> Result<()> {
-    let cwd = std::env::current_dir()?;
-    let m = manifest::read(&cwd)?;
+    let updated = client.update_product(&current.id, &req).await?;
+    println!("Updated {}/{}", username, slug);
+    if req.name.is_some() {
+        println!("  Name:        {}", updated.name);
+    }
+    if req.description.is_some() {
+        println!("  Description: {}", utils::truncate_str(&updated.description, 60));
+    }
+    if let Some(ref c) = req.category_slug {
+        println!("  Category:    {}", c);
+    }
+    if let Some(ref l) = req.license {
+        println!("  License:     {}", l);
+    }
+    for (label, url) in [("Homepage", &req.links.homepage), ("Repository", &req.links.repository), ("Docs", &req.links.documentation)] {
+        if let Some(url) = url {
+            println!("  {:<12} {}", format!("{}:", label), url);
+        }
+    }
+    Ok(())
+}
+
+/// `table` is Some(wide) with `--table`.
+async fn cmd_products(status_filter: Option<String>, sort: Option<ProductSort>, table: Option<bool>, json: bool) -> Result<()> {
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+    let me = client.get_me().await?;
+    let resp = client.list_my_products().await?;
+
+    let mut products: Vec<&types::Product> = if let Some(ref status) = status_filter {
+        resp.products.iter().filter(|p| p.review_status == *status).collect()
+    } else {
+        resp.products.iter().collect()
+    };
+    let stat = |p: &types::Product, f: fn(&types::ProductStats) -> Option<u64>| p.stats.as_ref().and_then(f).unwrap_or(0);
+    match sort {
+        Some(ProductSort::Remakes) => products.sort_by_key(|p| std::cmp::Reverse(stat(p, |s| s.remake_count))),
+        Some(ProductSort::Forks) => products.sort_by_key(|p| std::cmp::Reverse(stat(p, |s| s.fork_count))),
+        Some(ProductSort::Rating) => products.sort_by(|a, b| {
+            let rating = |p: &types::Product| p.stats.as_ref().and_then(|s| s.avg_rating).unwrap_or(0.0);
+            rating(b).total_cmp(&rating(a))
+        }),
+        // RFC 3339 timestamps sort chronologically as strings
+        Some(ProductSort::Updated) => products.sort_by(|a, b| b.updated_at.cmp(&a.updated_at)),
+        None => {}
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&products)?);
+        return Ok(());
+    }
+    if products.is_empty() {
+        if status_filter.is_some() {
+            println!("No products with status '{}'", status_filter.unwrap());
+        } else {
+            println!("No products yet. Run `baro publish` to get started.");
+        }
+        return Ok(());
+    }
+
+    if let Some(wide) = table {
+        print_product_table(&products, Some(&me.user.username), wide);
+        println!();
+    } else {
+        for p in &products {
+            let cat_name = p.category.as_ref().map(|c| c.slug.as_str()).unwrap_or("?");
+            let ver = p.latest_version.as_deref().unwrap_or("-");
+            let desc = utils::truncate_str(&p.description, 60);
+
+            println!(
+                "{}/{:<20} v{:<8} [{}]  {}",
+                me.user.username, p.slug, ver, cat_name, p.review_status
+            );
+            println!("  {}", desc);
+            println!("  Updated: {}", ui::timestamp(&p.updated_at));
+
+            if let Some(ref stats) = p.stats {
+                let forks = stats.fork_count.unwrap_or(0);
+                let rating = stats
+                    .avg_rating
+                    .map(|r| format!("{:.1}/5", r))
+                    .unwrap_or_else(|| "-".to_string());
+                let rc = stats.rating_count.unwrap_or(0);
+                let remakes = stats.remake_count.unwrap_or(0);
+                println!("  Forks: {}  Remakes: {}  Rating: {} ({})", forks, remakes, rating, rc);
+            }
+            println!();
+        }
+    }
+
+    let remakes: u64 = products.iter().map(|p| stat(p, |s| s.remake_count)).sum();
+    println!(
+        "{} product{}, {} remake{} by others",
+        products.len(),
+        if products.len() == 1 { "" } else { "s" },
+        remakes,
+        if remakes == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+async fn cmd_promote(version: &str, product: Option<&str>) -> Result<()> {
+    let (username, slug) = published_product(product, "Pass --product user/product")?;
+
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+    let resp = client.promote_release(&username, &slug, version).await?;
+
+    println!("Promoted {}/{}@{}", username, slug, version);
+    match resp.review_status.as_deref() {
+        Some("pending_review") | None => println!("Status: pending_review (admin approval required)"),
+        Some(s) => println!("Status: {}", s),
+    }
+    Ok(())
+}
+
+async fn cmd_backup(out: &std::path::Path, incremental: bool, jobs: usize) -> Result<()> {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    let token = auth::get_token().await?;
+    let client = Arc::new(api::BaroClient::new(&token));
+    let username = client.get_me().await?.user.username;
+
+    std::fs::create_dir_all(out)
+        .with_context(|| format!("Cannot create backup directory {}", out.display()))?;
+    let previous = backup::load_index(out)?;
+    if incremental && previous.is_none() {
+        println!("No previous backup in {}; running a full backup.", out.display());
+    }
+
+    let products = client.list_my_products().await?.products;
+    println!("Backing up {} product(s) for {} {} {}/", products.len(), username, ui::arrow(), out.display());
+
+    // Collect release metadata, then download with bounded concurrency
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(jobs.max(1)));
+    let mut tasks = tokio::task::JoinSet::new();
+    let mut layout: Vec<(backup::BackupProduct, Vec<String>)> = Vec::new();
+    let mut done: HashMap<(String, String), backup::BackupRelease> = HashMap::new();
+    let mut skipped = 0usize;
+
+    for p in &products {
+        let releases = client.list_releases(&username, &p.slug).await?.releases;
+        let versions: Vec<String> = releases.iter().map(|r| r.version.clone()).collect();
+        for release in releases {
+            let key = (p.slug.clone(), release.version.clone());
+            if incremental {
+                if let Some(rec) = previous.as_ref().and_then(|i| i.release(&p.slug, &release.version)) {
+                    if out.join(&rec.file).exists() {
+                        done.insert(key, rec.clone());
+                        skipped += 1;
+                        continue;
+                    }
+                }
+            }
+            let (client, semaphore) = (client.clone(), semaphore.clone());
+            let (root, username, slug) = (out.to_path_buf(), username.clone(), p.slug.clone());
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let result = backup_release(&client, &root, &username, &slug, release).await;
+                (key, result)
+            });
+        }
+        layout.push((
+            backup::BackupProduct {
+                slug: p.slug.clone(),
+                name: p.name.clone(),
+                description: p.description.clone(),
+                license: p.license.clone(),
+                category: p.category.as_ref().map(|c| c.slug.clone()),
+                visibility: Some(p.visibility().to_string()),
+                releases: Vec::new(),
+            },
+            versions,
+        ));
+    }
+
+    let (mut downloaded, mut resumed, mut failed) = (0usize, 0usize, 0usize);
+    while let Some(joined) = tasks.join_next().await {
+        let ((slug, version), result) = joined?;
+        match result {
+            Ok((record, fetched)) => {
+                if fetched {
+                    downloaded += 1;
+                    println!("  {}@{} ({})", slug, version, utils::format_bytes(record.size_bytes as i64));
+                } else {
+                    resumed += 1;
+                }
+                done.insert((slug, version), record);
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("  FAILED {}@{}: {:#}", slug, version, e);
+            }
+        }
+    }
+
+    // Index only what is actually on disk, in the registry's release order
+    let index = backup::BackupIndex {
+        publisher: username,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        products: layout
+            .into_iter()
+            .map(|(mut product, versions)| {
+                product.releases = versions
+                    .into_iter()
+                    .filter_map(|v| done.remove(&(product.slug.clone(), v)))
+                    .collect();
+                product
+            })
+            .collect(),
+    };
+    backup::save_index(out, &index)?;
+
+    println!(
+        "\nBackup complete: {} downloaded, {} already present, {} unchanged (incremental), {} failed",
+        downloaded, resumed, skipped, failed
+    );
+    if failed > 0 {
+        return Err(anyhow::anyhow!("{} release(s) failed; re-run `baro backup` to resume", failed));
+    }
+    Ok(())
+}
+
+/// Mirror one product into `out` in the backup layout. Re-runs fetch only new
+/// releases; releases that have disappeared upstream stay in the mirror.
+async fn cmd_mirror(product: &str, out: &std::path::Path) -> Result<()> {
+    let (username, slug, version) = parse_product_ref(product)?;
+    if version.is_some() {
+        return Err(anyhow::anyhow!("A mirror holds every release; pass {}/{} without @version", username, slug));
+    }
+    let client = match auth::get_token().await {
+        Ok(token) => api::BaroClient::new(&token),
+        Err(_) => api::BaroClient::anonymous(),
+    };
+
+    std::fs::create_dir_all(out)
+        .with_context(|| format!("Cannot create mirror directory {}", out.display()))?;
+    let mut index = match backup::load_index(out)? {
+        Some(index) if index.publisher != username => {
+            return Err(anyhow::anyhow!(
+                "{} already holds releases from {}; mirror {}/{} into another directory",
+                out.display(), index.publisher, username, slug
+            ));
+        }
+        Some(index) => index,
+        None => backup::BackupIndex { publisher: username.to_string(), ..Default::default() },
+    };
+    let previous = index.products.iter().find(|p| p.slug == slug).map(|p| p.releases.clone()).unwrap_or_default();
+
+    let p = get_product_or_suggest(&client, username, slug).await?;
+    let releases: Vec<types::Release> = client
+        .list_releases(username, slug)
+        .await?
+        .releases
+        .into_iter()
+        .filter(|r| !r.is_yanked())
+        .collect();
+    let upstream: Vec<String> = releases.iter().map(|r| r.version.clone()).collect();
+    println!("Mirroring {}/{}: {} release(s) {} {}/", username, slug, releases.len(), ui::arrow(), out.display());
+
+    let mut records = Vec::new();
+    let (mut downloaded, mut present, mut failed) = (0usize, 0usize, 0usize);
+    for release in releases {
+        let version = release.version.clone();
+        match backup_release(&client, out, username, slug, release).await {
+            Ok((record, fetched)) => {
+                if fetched {
+                    downloaded += 1;
+                    println!("  {}@{} ({})", slug, version, utils::format_bytes(record.size_bytes as i64));
+                } else {
+                    present += 1;
+                }
+                records.push(record);
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("  FAILED {}@{}: {:#}", slug, version, e);
+            }
+        }
+    }
+    // Keep what is already on disk: earlier copies of failed downloads, and
+    // releases since removed upstream (the reason to keep a mirror)
+    let mut gone = 0usize;
+    for old in previous {
+        if !records.iter().any(|r| r.version == old.version) && out.join(&old.file).exists() {
+            if !upstream.contains(&old.version) {
+                gone += 1;
+            }
+            records.push(old);
+        }
+    }
+
+    let entry = backup::BackupProduct {
+        slug: slug.to_string(),
+        visibility: Some(p.visibility().to_string()),
+        name: p.name,
+        description: p.description,
+        license: p.license,
+        category: p.category.map(|c| c.slug),
+        releases: records,
+    };
+    match index.products.iter_mut().find(|e| e.slug == slug) {
+        Some(existing) => *existing = entry,
+        None => index.products.push(entry),
+    }
+    index.generated_at = chrono::Utc::now().to_rfc3339();
+    backup::save_index(out, &index)?;
+
+    println!(
+        "\nMirror complete: {} downloaded, {} already present, {} no longer upstream (kept), {} failed",
+        downloaded, present, gone, failed
+    );
+    if failed > 0 {
+        return Err(anyhow::anyhow!("{} release(s) failed; re-run `baro mirror` to resume", failed));
+    }
+    Ok(())
+}
+
+/// Fetch one release into the backup, verifying its hash. Returns the index
+/// record and whether anything was downloaded (false when already present).
+async fn backup_release(
+    client: &api::BaroClient,
+    root: &std::path::Path,
+    username: &str,
+    slug: &str,
+    release: types::Release,
+) -> Result<(backup::BackupRelease, bool)> {
+    let download = client.get_download(username, slug, &release.version, None).await?;
+    let file = backup::archive_rel_path(slug, &release.version);
+    let dest = root.join(&file);
+    let record = |size_bytes: u64| backup::BackupRelease {
+        version: release.version.clone(),
+        changelog: release.changelog.clone(),
+        created_at: release.created_at.clone(),
+        file: file.clone(),
+        sha256: download.file_hash_sha256.clone(),
+        size_bytes,
+    };
+
+    if backup::is_complete(&dest, &download.file_hash_sha256) {
+        let size = std::fs::metadata(&dest).map(|m| m.len()).unwrap_or(0);
+        return Ok((record(size), false));
+    }
+
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // An interrupted download leaves `<file>.part`, which the next run resumes
+    let (size, hash) = client.download_to_file(&download.download_url, &dest).await?;
+    if hash != download.file_hash_sha256 {
+        let _ = std::fs::remove_file(&dest);
+        return Err(anyhow::anyhow!(
+            "Hash mismatch! Expected: {}, got: {}",
+            download.file_hash_sha256, hash
+        ));
+    }
+    Ok((record(size), true))
+}
+
+/// Replay a `baro backup` into a registry: create missing products, then publish
+/// each release's original archive under its original version, oldest first.
+/// Versions the target already has are skipped, so an interrupted restore can be re-run.
+async fn cmd_restore(dir: &std::path::Path, registry: Option<&str>) -> Result<()> {
+    let index = backup::load_index(dir)?
+        .ok_or_else(|| anyhow::anyhow!("No index.json in {}; is this a `baro backup` directory?", dir.display()))?;
+
+    // Never hand the baro session to another registry
+    let foreign = registry.filter(|url| url.trim_end_matches('/') != config::api_base_url().trim_end_matches('/'));
+    let token = match std::env::var("BARO_REGISTRY_TOKEN").ok().filter(|t| !t.is_empty()) {
+        Some(token) => token,
+        None => match foreign {
+            Some(url) => {
+                return Err(anyhow::anyhow!(
+                    "Set BARO_REGISTRY_TOKEN to an API token for {}; your baro login is only sent to {}",
+                    url, config::api_base_url()
+                ));
+            }
+            None => auth::get_token().await?,
+        },
+    };
+    let client = match registry {
+        Some(url) => api::BaroClient::with_registry(&token, url),
+        None => api::BaroClient::new(&token),
+    };
+    let username = client.get_me().await?.user.username;
+    let signing_key = signing::load()?;
+    println!(
+        "Restoring {} product(s) from {}'s backup as {}...",
+        index.products.len(), index.publisher, username
+    );
+
+    let owned = client.list_owned_products(None).await?.products;
+    let (mut published, mut present, mut failed) = (0usize, 0usize, 0usize);
+    for product in &index.products {
+        println!("\n{}", product.slug);
+        let existing: Vec<String> = if owned.iter().any(|p| p.slug == product.slug) {
+            client.list_releases(&username, &product.slug).await?
+                .releases.into_iter().map(|r| r.version).collect()
+        } else {
+            let Some(category) = product.category.as_deref() else {
+                eprintln!("  SKIPPED: backup has no category for {}", product.slug);
+                failed += product.releases.len();
+                continue;
+            };
+            // Backups from before visibility was recorded don't say; don't guess public
+            let visibility = product.visibility.as_deref().unwrap_or("private");
+            client
+                .create_product(&types::CreateProductRequest {
+                    slug: &product.slug,
+                    name: &product.name,
+                    description: &product.description,
+                    category_slug: category,
+                    license: product.license.as_deref().unwrap_or("MIT"),
+                    visibility: Some(visibility),
+                    is_private: visibility == "private",
+                    team: None,
+                    links: types::ProductLinks::default(),
+                    tags: &[],
+                })
+                .await?;
+            println!("  Created {}/{} ({})", username, product.slug, visibility);
+            if product.visibility.is_none() {
+                println!("  The backup doesn't record visibility; change it with `baro visibility public --product {}/{}`", username, product.slug);
+            }
+            Vec::new()
+        };
+
+        for release in product.releases_oldest_first() {
+            if existing.contains(&release.version) {
+                present += 1;
+                continue;
+            }
+            match restore_release(&client, dir, &username, &product.slug, release, signing_key.as_ref()).await {
+                Ok(()) => {
+                    published += 1;
+                    println!("  {}@{} ({})", product.slug, release.version, utils::format_bytes(release.size_bytes as i64));
+                }
+                Err(e) => {
+                    failed += 1;
+                    eprintln!("  FAILED {}@{}: {:#}", product.slug, release.version, e);
+                }
+            }
+        }
+    }
+
+    println!(
+        "\nRestore complete: {} published, {} already present, {} failed",
+        published, present, failed
+    );
+    if failed > 0 {
+        return Err(anyhow::anyhow!("{} release(s) not restored; fix the errors and re-run `baro restore`", failed));
+    }
+    Ok(())
+}
+
+/// Publish one backed-up archive as-is, after checking it against the recorded hash.
+async fn restore_release(
+    client: &api::BaroClient,
+    root: &std::path::Path,
+    username: &str,
+    slug: &str,
+    release: &backup::BackupRelease,
+    signing_key: Option<&ed25519_dalek::SigningKey>,
+) -> Result<()> {
+    let path = root.join(&release.file);
+    let bytes = std::fs::read(&path).with_context(|| format!("Missing archive {}", path.display()))?;
+    if !backup::is_complete(&path, &release.sha256) {
+        return Err(anyhow::anyhow!("{} does not match its recorded sha256; re-run `baro backup`", release.file));
+    }
+    let hash = release.sha256.as_str();
+    let readme = packaging::read_archive_readme(bytes.as_slice())?;
+    let signature = signing_key.map(|key| signing::sign_hash(key, hash));
+    let created = client
+        .create_release(username, slug, &types::CreateReleaseRequest {
+            version: &release.version,
+            changelog: release.changelog.as_deref().unwrap_or(""),
+            file_size_bytes: bytes.len() as i64,
+            file_hash_sha256: hash,
+            readme: readme.as_deref(),
+            signature: signature.as_deref(),
+            preview: false,
+        })
+        .await?;
+    client.upload_release(&created, std::time::Instant::now(), &bytes).await?;
+    client.confirm_release(&created.release_id).await?;
+    Ok(())
+}
+
+async fn cmd_info(product: &str, sbom: bool) -> Result<()> {
+    let (username, slug, version) = parse_product_ref(product)?;
+    let client = match auth::get_token().await {
+        Ok(token) => api::BaroClient::new(&token),
+        Err(_) => api::BaroClient::anonymous(),
+    };
+    let p = get_product_or_suggest(&client, username, slug).await?;
+    let version = version.map(String::from).or(p.latest_version.clone());
+
+    if sbom {
+        let version = version
+            .ok_or_else(|| anyhow::anyhow!("No published releases for {}/{}", username, slug))?;
+        let bom = client.get_sbom(username, slug, &version).await.map_err(|e| {
+            anyhow::anyhow!("No SBOM available for {}/{}@{}: {}", username, slug, version, e)
+        })?;
+        println!("{}", serde_json::to_string_pretty(&bom)?);
+        return Ok(());
+    }
+
+    println!("{}/{} — {}", username, p.slug, p.name);
+    println!("  {}", p.description);
+    if let Some(ref publisher) = p.publisher {
+        match publisher.display_name {
+            Some(ref name) => println!("  Publisher: {} ({})", name, publisher.username),
+            None => println!("  Publisher: {}", publisher.username),
+        }
+    }
+    println!("  Version:  {}", version.as_deref().unwrap_or("-"));
+    println!("  License:  {}", p.license.as_deref().unwrap_or("-"));
+    if let Some(ref c) = p.category {
+        println!("  Category: {}", c.name);
+    }
+    if !p.tags.is_empty() {
+        println!("  Tags:     {}", p.tags.join(", "));
+    }
+    for (label, url) in [("Homepage:", &p.links.homepage), ("Source:", &p.links.repository), ("Docs:", &p.links.documentation)] {
+        if let Some(url) = url {
+            println!("  {:<10}{}", label, url);
+        }
+    }
+    if let Some(ref stats) = p.stats {
+        let rating = stats
+            .avg_rating
+            .map(|r| format!("{:.1}/5", r))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "  Forks: {}  Remakes: {}  Rating: {} ({})",
+            stats.fork_count.unwrap_or(0), stats.remake_count.unwrap_or(0), rating, stats.rating_count.unwrap_or(0)
+        );
+    }
+    println!("  Updated:  {}", ui::timestamp(&p.updated_at));
+
+    // Release history, newest first; a failure here shouldn't hide the product
+    if let Ok(resp) = client.list_releases(username, slug).await {
+        let mut releases = resp.releases;
+        releases.sort_by(|a, b| utils::compare_versions(&b.version, &a.version));
+        if !releases.is_empty() {
+            println!("\nReleases:");
+        }
+        for r in releases.iter().take(INFO_RELEASES) {
+            let marker = if version.as_deref() == Some(r.version.as_str()) { "*" } else { " " };
+            let size = r.file_size_bytes.map(utils::format_bytes).unwrap_or_else(|| "-".to_string());
+            println!("  {} {:<12} {:>9}  {}", marker, r.version, size, ui::timestamp(&r.created_at));
+        }
+        if releases.len() > INFO_RELEASES {
+            println!("  ... {} older (baro releases {}/{})", releases.len() - INFO_RELEASES, username, slug);
+        }
+    }
+    Ok(())
+}
+
+fn cmd_open(product: Option<&str>) -> Result<()> {
+    let (username, slug) = match product {
+        Some(p) => {
+            let (username, slug, _) = parse_product_ref(p)?;
+            (username.to_string(), slug.to_string())
+        }
+        None => {
+            let m = manifest::read(&project_root()?)?;
+            match (m.publisher, m.slug, m.origin) {
+                (Some(publisher), Some(slug), _) => (publisher, slug),
+                (_, _, Some(origin)) => {
+                    let (username, slug, _) = parse_product_ref(&origin)?;
+                    (username.to_string(), slug.to_string())
+                }
+                _ => return Err(anyhow::anyhow!("This product isn't published or forked yet. Pass a product: baro open user/product")),
+            }
+        }
+    };
+    let url = config::product_url(&username, &slug);
+    println!("Opening {}", url);
+    if open::that(&url).is_err() {
+        println!("Couldn't start a browser; open the link above.");
+    }
+    Ok(())
+}
+
+async fn cmd_releases(product: &str, json: bool) -> Result<()> {
+    let (username, slug, version) = parse_product_ref(product)?;
+    if version.is_some() {
+        return Err(anyhow::anyhow!("`baro releases` lists every version; pass {}/{} without @version", username, slug));
+    }
+    let client = match auth::get_token().await {
+        Ok(token) => api::BaroClient::new(&token),
+        Err(_) => api::BaroClient::anonymous(),
+    };
+    let mut releases = match client.list_releases(username, slug).await {
+        Ok(resp) => resp.releases,
+        // Unknown product: let the lookup add "did you mean" suggestions
+        Err(e) if error::status(&e) == Some(404) => {
+            get_product_or_suggest(&client, username, slug).await?;
+            return Err(e);
+        }
+        Err(e) => return Err(e),
+    };
+    releases.sort_by(|a, b| utils::compare_versions(&b.version, &a.version));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&releases)?);
+        return Ok(());
+    }
+    if releases.is_empty() {
+        println!("{}/{} has no releases yet.", username, slug);
+        return Ok(());
+    }
+    println!("{}/{}: {} release{}", username, slug, releases.len(), if releases.len() == 1 { "" } else { "s" });
+    for r in &releases {
+        let size = r.file_size_bytes.map(utils::format_bytes).unwrap_or_else(|| "-".to_string());
+        let status = match r.status.as_deref() {
+            None | Some("published") => String::new(),
+            Some(s) => format!("  [{}]", s.replace('_', " ")),
+        };
+        println!("  {:<12} {:>9}  {}{}", r.version, size, ui::timestamp(&r.created_at), status);
+        if let Some(cl) = r.changelog.as_deref().and_then(|cl| cl.lines().find(|l| !l.trim().is_empty())) {
+            println!("  {:<12} {}", "", utils::truncate_str(cl.trim(), 80));
+        }
+    }
+    Ok(())
+}
+
+/// `baro releases --mine`: the owner's view, including releases the public
+/// list leaves out (unfinished uploads, pending or rejected review).
+async fn cmd_own_releases(product: Option<&str>, json: bool) -> Result<()> {
+    let (username, slug) = published_product(product, "Pass a product: baro releases user/product --mine")?;
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+    let mut releases = match client.list_own_releases(&username, &slug).await {
+        Ok(resp) => resp.releases,
+        Err(e) if error::status(&e) == Some(403) => {
+            return Err(e.context(format!("{}/{} isn't yours; drop --mine for its public releases", username, slug)));
+        }
+        Err(e) => return Err(e),
+    };
+    releases.sort_by(|a, b| utils::compare_versions(&b.version, &a.version));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&releases)?);
+        return Ok(());
+    }
+    if releases.is_empty() {
+        println!("{}/{} has no releases yet.", username, slug);
+        return Ok(());
+    }
+    let label = |s: Option<&str>| s.map(|s| s.replace('_', " ")).unwrap_or_else(|| "-".to_string());
+    let rows: Vec<Vec<String>> = releases
+        .iter()
+        .map(|r| {
+            vec![
+                r.version.clone(),
+                label(r.status.as_deref().or(Some("published"))),
+                label(r.upload_status.as_deref()),
+                label(r.review_status.as_deref()),
+                r.file_size_bytes.map(utils::format_bytes).unwrap_or_else(|| "-".to_string()),
+                ui::short_timestamp(&r.created_at),
+            ]
+        })
+        .collect();
+    println!("{}/{}: {} release{}\n", username, slug, releases.len(), if releases.len() == 1 { "" } else { "s" });
+    for line in ui::table(&["VERSION", "STATUS", "UPLOAD", "REVIEW", "SIZE", "CREATED"], &rows, 0, None) {
+        println!("{}", line);
+    }
+    let unfinished = releases.iter().filter(|r| r.upload_status.as_deref().is_some_and(|s| s != "uploaded")).count();
+    if unfinished > 0 {
+        println!("\n{} upload{} didn't finish; those versions can't be forked.", unfinished, if unfinished == 1 { "" } else { "s" });
+    }
+    Ok(())
+}
+
+async fn cmd_readme(product: &str, raw: bool) -> Result<()> {
+    let (username, slug, version) = parse_product_ref(product)?;
+    let client = match auth::get_token().await {
+        Ok(token) => api::BaroClient::new(&token),
+        Err(_) => api::BaroClient::anonymous(),
+    };
+    let p = get_product_or_suggest(&client, username, slug).await?;
+    let version = version
+        .map(String::from)
+        .or(p.latest_version)
+        .ok_or_else(|| anyhow::anyhow!("No published releases for {}/{}", username, slug))?;
+
+    // Older releases have no stored README; read it from the archive instead
+    let readme = match client.get_readme(username, slug, &version).await {
+        Ok(types::ReadmeResponse { readme: Some(readme) }) => Some(readme),
+        Err(e) if error::status(&e) != Some(404) => return Err(e),
+        _ => {
+            let download = client.get_download(username, slug, &version, None).await?;
+            let archive = download_archive(&client, &download).await?;
+            packaging::read_archive_readme(archive.open()?)?
+        }
+    };
+    let readme = readme.ok_or_else(|| anyhow::anyhow!("{}/{}@{} has no README", username, slug, version))?;
+
+    if raw {
+        print!("{}", readme);
+        return Ok(());
+    }
+    let style = markdown::Style {
+        ansi: std::io::stdout().is_terminal() && !ui::accessible() && std::env::var_os("NO_COLOR").is_none(),
+        plain_symbols: ui::accessible(),
+    };
+    println!("{}", markdown::render(&readme, style));
+    Ok(())
+}
+
+/// Releases listed by `baro info`.
+const INFO_RELEASES: usize = 5;
+
+async fn cmd_stats(product: Option<&str>, days: u32) -> Result<()> {
+    let (username, slug) = published_product(product, "Pass a product: baro stats user/product")?;
+
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+    let stats = client.get_product_stats(&username, &slug).await?;
+
+    println!("{}/{}: {} downloads", username, slug, stats.total_downloads);
+    match client.get_product_analytics(&username, &slug, days).await {
+        Ok(analytics) => print_analytics(&analytics, days),
+        // Registries without analytics still have the totals above
+        Err(e) if error::status(&e) == Some(404) => {}
+        Err(e) => return Err(e),
+    }
+    if stats.by_os.is_empty() && stats.by_cli_version.is_empty() {
+        println!("\nNo client hints yet (consumers share them only if they opt in).");
+        return Ok(());
+    }
+    for (title, buckets) in [("By OS", &stats.by_os), ("By CLI version", &stats.by_cli_version)] {
+        let hinted: u64 = buckets.iter().map(|b| b.count).sum();
+        if hinted == 0 {
+            continue;
+        }
+        println!("\n{} ({} opted-in downloads):", title, hinted);
+        for b in buckets {
+            println!("  {:<16} {:>6}  {:>3}%", b.value, b.count, b.count * 100 / hinted);
+        }
+    }
+    Ok(())
+}
+
+/// Rating trend entries shown by `baro stats`, most recent last.
+const RATING_TREND_POINTS: usize = 6;
+
+fn print_analytics(a: &types::ProductAnalytics, days: u32) {
+    let daily: Vec<u64> = a.daily_downloads.iter().map(|d| d.count).collect();
+    if let (Some(first), Some(last)) = (a.daily_downloads.first(), a.daily_downloads.last()) {
+        println!("\nDownloads, last {} days: {}", days, daily.iter().sum::<u64>());
+        println!("  {}  ({} {} {})", ui::sparkline(&daily), first.date, ui::arrow(), last.date);
+    }
+    if !a.versions.is_empty() {
+        let mut versions: Vec<&types::VersionAnalytics> = a.versions.iter().collect();
+        versions.sort_by(|x, y| utils::compare_versions(&y.version, &x.version));
+        let rows: Vec<Vec<String>> = versions
+            .iter()
+            .map(|v| vec![v.version.clone(), v.forks.to_string(), v.downloads.to_string()])
+            .collect();
+        println!();
+        for line in ui::table(&["VERSION", "FORKS", "DOWNLOADS"], &rows, 0, None) {
+            println!("  {}", line);
+        }
+    }
+    let trend: Vec<String> = a
+        .rating_trend
+        .iter()
+        .filter_map(|p| Some(format!("{} {:.1} ({})", p.period, p.avg_rating?, p.count)))
+        .collect();
+    if !trend.is_empty() {
+        let recent = &trend[trend.len().saturating_sub(RATING_TREND_POINTS)..];
+        println!("\nRating: {}", recent.join(&format!(" {} ", ui::arrow())));
+    }
+    println!("\nRemakes by others: {}", a.remake_count);
+}
+
+/// `baro stats --all`: every product you publish, one row each.
+async fn cmd_stats_all(days: u32) -> Result<()> {
+    use std::sync::Arc;
+
+    let token = auth::get_token().await?;
+    let client = Arc::new(api::BaroClient::new(&token));
+    let me = client.get_me().await?;
+    let products = client.list_my_products().await?.products;
+    if products.is_empty() {
+        println!("No products yet. Run `baro publish` to get started.");
+        return Ok(());
+    }
+
+    let mut tasks = tokio::task::JoinSet::new();
+    for (i, p) in products.iter().enumerate() {
+        let (client, username, slug) = (client.clone(), me.user.username.clone(), p.slug.clone());
+        tasks.spawn(async move { (i, client.get_product_analytics(&username, &slug, days).await) });
+    }
+    let mut analytics: Vec<Option<types::ProductAnalytics>> = products.iter().map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        match joined? {
+            (i, Ok(a)) => analytics[i] = Some(a),
+            (_, Err(e)) if error::status(&e) == Some(404) => {}
+            (_, Err(e)) => return Err(e),
+        }
+    }
+
+    let downloads_header = format!("DOWNLOADS ({}D)", days);
+    let rows: Vec<Vec<String>> = products
+        .iter()
+        .zip(&analytics)
+        .map(|(p, a)| {
+            let stats = p.stats.as_ref();
+            let daily: Vec<u64> = a.iter().flat_map(|a| a.daily_downloads.iter().map(|d| d.count)).collect();
+            vec![
+                format!("{}/{}", me.user.username, p.slug),
+                p.latest_version.clone().unwrap_or_else(|| "-".to_string()),
+                a.as_ref().map(|_| daily.iter().sum::<u64>().to_string()).unwrap_or_else(|| "-".to_string()),
+                ui::sparkline(&daily),
+                stats.and_then(|s| s.fork_count).unwrap_or(0).to_string(),
+                stats.and_then(|s| s.remake_count).unwrap_or(0).to_string(),
+                stats.and_then(|s| s.avg_rating).map(|r| format!("{:.1}/5", r)).unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+    let headers = ["PRODUCT", "VERSION", downloads_header.as_str(), "TREND", "FORKS", "REMAKES", "RATING"];
+    for line in ui::table(&headers, &rows, 3, ui::terminal_size().map(|(w, _)| w)) {
+        println!("{}", line);
+    }
+    if analytics.iter().all(Option::is_none) {
+        println!("\nThe registry doesn't provide download history; totals are in `baro stats <product>`.");
+    }
+    Ok(())
+}
+
+fn cmd_status(path: Option<&std::path::Path>) -> Result<()> {
+    let cwd = project_root_from(path)?;
+    let m = manifest::read(&cwd)?;
 
     // Show publish identity if present
     if let Some(ref slug) = m.slug {
@@ -720,9 +3205,12 @@ fn cmd_status() -> Result<()> {
 
     // Show fork origin if present
     if let Some(ref origin) = m.origin {
-        println!("Origin:  {}", origin);
+        println!("Origin:  {}@{}", origin, m.forked_version());
         if let Some(ref cloned_at) = m.cloned_at {
-            println!("Forked:  {}", cloned_at);
+            println!("Forked:  {}", ui::timestamp(cloned_at));
+        }
+        if let Some(b) = baseline::read(&cwd)? {
+            println!("Changes: {} since {}", b.changes(&packaging::read_tree(&cwd)?).summary(), b.version);
         }
     }
 
@@ -734,44 +3222,781 @@ fn cmd_status() -> Result<()> {
     Ok(())
 }
 
-async fn cmd_upstream() -> Result<()> {
-    let cwd = std::env::current_dir()?;
+fn cmd_status_json(path: Option<&std::path::Path>) -> Result<()> {
+    let cwd = project_root_from(path)?;
+    let present = manifest::path(&cwd).exists();
+    let (m, error) = match manifest::read(&cwd) {
+        Ok(m) => (Some(m), None),
+        Err(e) if present => (None, Some(format!("{:#}", e))),
+        Err(_) => (None, None),
+    };
+
+    let origin = m.as_ref().and_then(|m| {
+        let origin = m.origin.clone()?;
+        let cached = upstream_cache::get(&origin);
+        let since_fork = baseline::read(&cwd).ok().flatten().and_then(|b| {
+            let changes = b.changes(&packaging::read_tree(&cwd).ok()?);
+            Some(types::ForkChanges {
+                base_version: b.version,
+                modified: changes.modified.len(),
+                added: changes.added.len(),
+                removed: changes.removed.len(),
+            })
+        });
+        Some(types::OriginStatus {
+            since_fork,
+            forked_at: m.cloned_at.clone(),
+            up_to_date: cached.as_ref().map(|c| utils::compare_versions(&c.latest_version, m.forked_version()).is_le()),
+            upstream_latest: cached.as_ref().map(|c| c.latest_version.clone()),
+            upstream_checked_at: cached.map(|c| c.checked_at),
+            origin,
+        })
+    });
+
+    let local_changes = if git::is_repo(&cwd) {
+        git::dirty_files(&cwd).ok().map(|files| types::LocalChanges {
+            source: "git".to_string(),
+            changed_files: files.len(),
+        })
+    } else {
+        None
+    };
+
+    let report = types::StatusReport {
+        schema_version: 1,
+        manifest: types::ManifestHealth {
+            present,
+            valid: m.is_some(),
+            error,
+        },
+        product: m.as_ref().and_then(|m| {
+            let slug = m.slug.as_ref()?;
+            Some(format!("{}/{}", m.publisher.as_deref().unwrap_or("?"), slug))
+        }),
+        product_id: m.as_ref().and_then(|m| m.product_id.clone()),
+        version: m.as_ref().map(|m| m.version.clone()),
+        origin,
+        local_changes,
+        auth: auth::auth_state().to_string(),
+    };
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+fn cmd_notify(action: NotifyAction) -> Result<()> {
+    match action {
+        NotifyAction::Enable { path } => {
+            let dir = std::fs::canonicalize(project_root_from(path.as_deref())?)?;
+            let m = manifest::read(&dir)?;
+            let origin = m.origin.ok_or_else(|| {
+                anyhow::anyhow!("{} is not a fork; only forks have an upstream to watch", dir.display())
+            })?;
+            if notify::enable(&dir, &origin)? {
+                println!("Watching {} for {}: new releases are announced after your next baro commands.", origin, dir.display());
+            } else {
+                println!("Already watching {} for {}", origin, dir.display());
+            }
+        }
+        NotifyAction::Disable { path } => {
+            let dir = std::fs::canonicalize(project_root_from(path.as_deref())?)?;
+            if notify::disable(&dir)? {
+                println!("Stopped watching upstream for {}", dir.display());
+            } else {
+                println!("{} was not being watched", dir.display());
+            }
+        }
+        NotifyAction::Status => {
+            let subs = notify::subscriptions();
+            if subs.is_empty() {
+                println!("No forks watched. Run `baro notify enable` in a fork.");
+                return Ok(());
+            }
+            for sub in subs {
+                let forked = manifest::read(&sub.dir).map(|m| m.forked_version().to_string());
+                let latest = upstream_cache::get(&sub.origin)
+                    .map(|e| format!("latest {} (checked {})", e.latest_version, ui::timestamp(&e.checked_at)))
+                    .unwrap_or_else(|| "not checked yet".to_string());
+                match forked {
+                    Ok(v) => println!("{}  {}@{}  {}", sub.dir.display(), sub.origin, v, latest),
+                    Err(_) => println!("{}  {}  (project missing; `baro notify disable {}`)", sub.dir.display(), sub.origin, sub.dir.display()),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `user/product` of a tracked upstream, split.
+fn split_origin(origin: &str) -> Result<(&str, &str)> {
+    origin
+        .split_once('/')
+        .ok_or_else(|| anyhow::anyhow!("Invalid origin in manifest: {}", origin))
+}
+
+/// `--remote <name>` for the upstream commands, when the chosen upstream isn't `origin`.
+fn remote_flag(up: &types::Remote) -> String {
+    if up.name == types::PRIMARY_REMOTE {
+        String::new()
+    } else {
+        format!(" --remote {}", up.name)
+    }
+}
+
+async fn cmd_upstream(remote: Option<&str>) -> Result<()> {
+    let cwd = project_root()?;
     let m = manifest::read(&cwd)?;
+    let up = m.upstream(remote)?;
+    let origin = up.origin.as_str();
+    let (username, slug) = split_origin(origin)?;
 
-    let origin = m.origin.as_deref().ok_or_else(|| {
-        anyhow::anyhow!("No fork origin in manifest. This product was not forked.")
-    })?;
-    let parts: Vec<&str> = origin.splitn(2, '/').collect();
-    if parts.len() != 2 {
-        return Err(anyhow::anyhow!("Invalid origin in manifest: {}", origin));
+    let client = api::BaroClient::anonymous();
+    let releases = client.list_releases(username, slug).await?;
+    let forked = up.version.as_str();
+    let offered = upstream::apply_policy(&releases.releases, &up);
+    let relation = upstream::relation(forked, up.release_id.as_deref(), &offered);
+
+    if let Some(latest) = releases.releases.iter().filter(|r| !r.is_yanked()).max_by(|a, b| utils::compare_versions(&a.version, &b.version)) {
+        upstream_cache::record(origin, &latest.version);
+    }
+
+    if up.name != types::PRIMARY_REMOTE {
+        println!("{} ({})", up.name, origin);
+    }
+    // Best-effort: the release list above is what the check needs
+    if let Some(notice) = client.get_product(username, slug).await.ok().and_then(|p| p.deprecation_notice(origin)) {
+        println!("{}", notice);
+    }
+    println!("{}", relation.summary(forked));
+    if let Some(ref pin) = up.pinned_version {
+        println!("Pinned to {} (baro upstream pin --clear{} to follow the latest)", pin, remote_flag(&up));
+    }
+    if !up.ignored_versions.is_empty() {
+        println!("Ignoring: {}", up.ignored_versions.join(", "));
+    }
+    if let Some(target) = relation.pull_target() {
+        // Everything released since the fork, or just the replacement for a re-release
+        let changes = match relation {
+            upstream::Relation::Rereleased { current } => vec![current],
+            _ => upstream::newer_releases(forked, &offered),
+        };
+        println!();
+        for release in &changes {
+            println!("{} ({})", release.version, ui::timestamp(&release.created_at));
+            match release.changelog.as_deref().map(str::trim).filter(|cl| !cl.is_empty()) {
+                Some(cl) => cl.lines().for_each(|line| println!("  {}", line)),
+                None => println!("  (no changelog)"),
+            }
+            println!();
+        }
+        println!(
+            "Run: baro upstream pull{} (merge in place) or baro pull (side by side) to get {}",
+            remote_flag(&up),
+            target.version
+        );
+    }
+    if remote.is_none() && !m.remotes.is_empty() {
+        let names: Vec<&str> = m.remotes.iter().map(|r| r.name.as_str()).collect();
+        println!("Also tracking: {} (check with baro upstream --remote <name>)", names.join(", "));
+    }
+
+    Ok(())
+}
+
+/// `baro upstream set`: rewrite the origin (or a named remote) after checking
+/// that the product and release exist.
+async fn cmd_upstream_set(product: &str, remote: Option<&str>) -> Result<()> {
+    let cwd = project_root()?;
+    let mut m = manifest::read(&cwd)?;
+    let name = remote.unwrap_or(types::PRIMARY_REMOTE);
+    let previous = match m.upstream(remote) {
+        Ok(up) => Some(up),
+        // A product without an origin gets one; unknown remotes stay an error
+        Err(_) if name == types::PRIMARY_REMOTE => None,
+        Err(e) => return Err(e.context("Add it first with `baro upstream remote add`")),
+    };
+    let (username, slug, version) = parse_product_ref(product)?;
+
+    // Keep the current base version when the new origin has it too
+    let version = match (version, &previous) {
+        (Some(v), _) => Some(v.to_string()),
+        (None, Some(prev)) => {
+            let releases = api::BaroClient::anonymous().list_releases(username, slug).await?.releases;
+            releases
+                .iter()
+                .any(|r| !r.is_yanked() && r.version == prev.version)
+                .then(|| prev.version.clone())
+        }
+        (None, None) => None,
+    };
+    let (version, download, files) = fetch_release_files(username, slug, version.as_deref()).await?;
+
+    let origin = format!("{}/{}", username, slug);
+    if name == types::PRIMARY_REMOTE {
+        m.origin = Some(origin.clone());
+        if m.cloned_at.is_none() {
+            m.cloned_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+    } else if let Some(r) = m.remotes.iter_mut().find(|r| r.name == name) {
+        r.origin = origin.clone();
+    }
+    m.advance(name, &version, download.release_id, Some(download.file_hash_sha256));
+    manifest::write(&cwd, &m)?;
+    if name == types::PRIMARY_REMOTE {
+        baseline::write(&cwd, &version, &files)?;
+    }
+
+    match previous {
+        Some(prev) => println!("{}: {}@{} {} {}@{}", name, prev.origin, prev.version, ui::arrow(), origin, version),
+        None => println!("Origin set to {}@{}", origin, version),
+    }
+    let local = diff::compare_trees(&files, &packaging::read_tree(&cwd)?);
+    println!("Against that release: {}", local.summary());
+    Ok(())
+}
+
+/// `baro upstream pin [VERSION | --clear]`
+async fn cmd_upstream_pin(version: Option<&str>, remote: Option<&str>) -> Result<()> {
+    let cwd = project_root()?;
+    let mut m = manifest::read(&cwd)?;
+    let up = m.upstream(remote)?;
+    let Some(version) = version else {
+        m.set_policy(&up.name, None, up.ignored_versions.clone());
+        manifest::write(&cwd, &m)?;
+        println!("Unpinned {}: following its latest release again", up.origin);
+        return Ok(());
+    };
+
+    let (username, slug) = split_origin(&up.origin)?;
+    let releases = api::BaroClient::anonymous().list_releases(username, slug).await?.releases;
+    if !releases.iter().any(|r| !r.is_yanked() && utils::compare_versions(&r.version, version).is_eq()) {
+        return Err(anyhow::anyhow!("{} has no release {}; see `baro releases {}`", up.origin, version, up.origin));
+    }
+    m.set_policy(&up.name, Some(version.to_string()), up.ignored_versions.clone());
+    manifest::write(&cwd, &m)?;
+    println!("Pinned {} to {}: newer releases won't be offered", up.origin, version);
+    if utils::compare_versions(version, &up.version).is_le() {
+        println!("This project already has {}, so there is nothing to pull until you unpin.", up.version);
+    }
+    Ok(())
+}
+
+/// `baro upstream ignore VERSION [--undo]`
+fn cmd_upstream_ignore(version: &str, undo: bool, remote: Option<&str>) -> Result<()> {
+    let cwd = project_root()?;
+    let mut m = manifest::read(&cwd)?;
+    let up = m.upstream(remote)?;
+    let mut ignored = up.ignored_versions.clone();
+    let listed = ignored.iter().any(|v| v == version);
+    match (undo, listed) {
+        (false, true) => println!("{} is already ignored", version),
+        (false, false) => {
+            ignored.push(version.to_string());
+            println!("Ignoring {}@{}", up.origin, version);
+        }
+        (true, true) => {
+            ignored.retain(|v| v != version);
+            println!("{}@{} will be offered again", up.origin, version);
+        }
+        (true, false) => println!("{} was not ignored", version),
+    }
+    m.set_policy(&up.name, up.pinned_version.clone(), ignored);
+    manifest::write(&cwd, &m)?;
+    Ok(())
+}
+
+async fn cmd_upstream_remote(action: RemoteAction) -> Result<()> {
+    let cwd = project_root()?;
+    let mut m = manifest::read(&cwd)?;
+    match action {
+        RemoteAction::Add { name, product } => {
+            if name == types::PRIMARY_REMOTE || m.remotes.iter().any(|r| r.name == name) {
+                return Err(anyhow::anyhow!("An upstream named '{}' already exists", name));
+            }
+            if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                return Err(anyhow::anyhow!("Invalid upstream name '{}': use letters, digits, - and _", name));
+            }
+            let (username, slug, version) = parse_product_ref(&product)?;
+            let (_, version, download) = fork_download(username, slug, version, None).await?;
+            m.remotes.push(types::Remote {
+                name: name.clone(),
+                origin: format!("{}/{}", username, slug),
+                version: version.clone(),
+                release_id: download.release_id,
+                file_hash: Some(download.file_hash_sha256),
+                ignored_versions: Vec::new(),
+                pinned_version: None,
+            });
+            manifest::write(&cwd, &m)?;
+            println!("Tracking {}/{}@{} as '{}'", username, slug, version, name);
+            println!("Check it with: baro upstream --remote {}", name);
+        }
+        RemoteAction::Remove { name } => {
+            if name == types::PRIMARY_REMOTE {
+                return Err(anyhow::anyhow!("'origin' is the fork's own origin and can't be removed"));
+            }
+            let before = m.remotes.len();
+            m.remotes.retain(|r| r.name != name);
+            if m.remotes.len() == before {
+                return Err(m.upstream(Some(&name)).unwrap_err());
+            }
+            manifest::write(&cwd, &m)?;
+            println!("Stopped tracking '{}'", name);
+        }
+        RemoteAction::List => {
+            let primary = m.upstream(None).ok();
+            if primary.is_none() && m.remotes.is_empty() {
+                println!("No upstreams: this product was not forked.");
+            }
+            for up in primary.iter().chain(&m.remotes) {
+                println!("{:<12} {}@{}", up.name, up.origin, up.version);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `baro upstream --recursive`: check every fork under `dir`, fetching each
+/// origin's releases once, a few at a time.
+async fn cmd_upstream_recursive(dir: &std::path::Path, jobs: usize) -> Result<()> {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    let mut forks: Vec<(String, types::Manifest)> = Vec::new();
+    let mut unreadable = 0usize;
+    for relative in packaging::find_products(dir) {
+        let name = if relative.as_os_str().is_empty() { ".".to_string() } else { relative.display().to_string() };
+        match manifest::read(&dir.join(&relative)) {
+            Ok(m) if m.origin.is_some() => forks.push((name, m)),
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("  Skipping {}: {:#}", name, e);
+                unreadable += 1;
+            }
+        }
+    }
+    if forks.is_empty() {
+        println!("No forks found under {}", dir.display());
+        return Ok(());
+    }
+    forks.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let client = Arc::new(api::BaroClient::anonymous());
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(jobs.max(1)));
+    let mut tasks = tokio::task::JoinSet::new();
+    let origins: std::collections::BTreeSet<String> = forks.iter().filter_map(|(_, m)| m.origin.clone()).collect();
+    progress!("Checking {} fork(s) of {} origin(s)...", forks.len(), origins.len());
+    for origin in origins {
+        let (client, semaphore) = (client.clone(), semaphore.clone());
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = match origin.split_once('/') {
+                Some((username, slug)) => client.list_releases(username, slug).await.map(|r| r.releases),
+                None => Err(anyhow::anyhow!("Invalid origin in manifest: {}", origin)),
+            };
+            (origin, result)
+        });
+    }
+    let mut releases: BTreeMap<String, Result<Vec<types::Release>>> = BTreeMap::new();
+    while let Some(joined) = tasks.join_next().await {
+        let (origin, result) = joined?;
+        if let Ok(ref list) = result {
+            if let Some(latest) = list.iter().filter(|r| !r.is_yanked()).max_by(|a, b| utils::compare_versions(&a.version, &b.version)) {
+                upstream_cache::record(&origin, &latest.version);
+            }
+        }
+        releases.insert(origin, result);
+    }
+
+    let mut behind = 0usize;
+    let rows: Vec<[String; 4]> = forks
+        .iter()
+        .map(|(name, m)| {
+            let origin = m.origin.clone().unwrap_or_default();
+            let status = match &releases[&origin] {
+                Ok(list) => {
+                    let offered = match m.upstream(None) {
+                        Ok(up) => upstream::apply_policy(list, &up),
+                        Err(_) => list.clone(),
+                    };
+                    let relation = upstream::relation(m.forked_version(), m.origin_release_id.as_deref(), &offered);
+                    if relation.pull_target().is_some() {
+                        behind += 1;
+                        format!("{} {}", ui::arrow(), relation.label())
+                    } else {
+                        relation.label()
+                    }
+                }
+                Err(e) => format!("error: {}", e),
+            };
+            [name.clone(), origin, m.forked_version().to_string(), status]
+        })
+        .collect();
+    let rows: Vec<Vec<String>> = rows.into_iter().map(Vec::from).collect();
+    for line in ui::table(&["PROJECT", "ORIGIN", "FORKED", "STATUS"], &rows, 3, None) {
+        println!("{}", line);
+    }
+
+    let failed = releases.values().filter(|r| r.is_err()).count();
+    println!();
+    println!("{} of {} fork(s) have upstream updates", behind, forks.len());
+    if behind > 0 {
+        println!("Run `baro upstream pull` in a project to merge its update.");
+    }
+    let problems: Vec<String> = [(failed, "origin(s) could not be checked"), (unreadable, "manifest(s) could not be read")]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, what)| format!("{} {}", n, what))
+        .collect();
+    if !problems.is_empty() {
+        return Err(anyhow::anyhow!("{}", problems.join(", ")));
+    }
+    Ok(())
+}
+
+/// Download and verify a release, returning its version, download record and files.
+async fn fetch_release_files(
+    username: &str,
+    slug: &str,
+    version: Option<&str>,
+) -> Result<(String, types::DownloadResponse, std::collections::BTreeMap<String, Vec<u8>>)> {
+    let (client, version, download) = fork_download(username, slug, version, None).await?;
+    let archive = download_archive(&client, &download).await?;
+    let files = packaging::read_archive_files(archive.open()?)?;
+    Ok((version, download, files))
+}
+
+/// Context lines around each change in `upstream diff --patch`.
+const PATCH_CONTEXT: usize = 3;
+
+/// `baro upstream diff`: compare the working tree with the forked release (or
+/// the latest one) file by file.
+async fn cmd_upstream_diff(latest: bool, patch: bool, remote: Option<&str>) -> Result<()> {
+    let cwd = project_root()?;
+    let up = manifest::read(&cwd)?.upstream(remote)?;
+    let origin = up.origin.as_str();
+    let (username, slug) = split_origin(origin)?;
+    let version = if latest {
+        None
+    } else {
+        Some(up.version.as_str())
+    };
+
+    progress!("Downloading {}...", origin);
+    let (version, _, upstream_files) = fetch_release_files(username, slug, version).await?;
+    let local_files = packaging::read_tree(&cwd)?;
+    let changes = diff::compare_trees(&upstream_files, &local_files);
+
+    if changes.is_empty() {
+        println!("No changes against {}@{}", origin, version);
+        return Ok(());
+    }
+
+    println!("Changes against {}@{}:", origin, version);
+    for path in &changes.modified {
+        let summary = match (diff::as_text(&upstream_files[path]), diff::as_text(&local_files[path])) {
+            (Some(old), Some(new)) => match diff::line_diff(old, new) {
+                Some(edits) => {
+                    let (added, removed) = diff::counts(&edits);
+                    format!("(+{} -{})", added, removed)
+                }
+                None => "(too large to diff)".to_string(),
+            },
+            _ => "(binary)".to_string(),
+        };
+        println!("  M {} {}", path, summary);
+    }
+    for path in &changes.added {
+        println!("  A {}", path);
+    }
+    for path in &changes.removed {
+        println!("  D {}", path);
+    }
+    println!("{}", changes.summary());
+
+    if patch {
+        let empty = Vec::new();
+        let paths = changes.modified.iter().chain(&changes.added).chain(&changes.removed);
+        for path in paths {
+            let old = upstream_files.get(path).unwrap_or(&empty);
+            let new = local_files.get(path).unwrap_or(&empty);
+            let old_name = if upstream_files.contains_key(path) { format!("a/{}", path) } else { "/dev/null".to_string() };
+            let new_name = if local_files.contains_key(path) { format!("b/{}", path) } else { "/dev/null".to_string() };
+            println!();
+            match (diff::as_text(old), diff::as_text(new)) {
+                (Some(old), Some(new)) => match diff::line_diff(old, new) {
+                    Some(edits) => print!("{}", diff::unified(&edits, &old_name, &new_name, PATCH_CONTEXT)),
+                    None => println!("{}: too large to diff", path),
+                },
+                _ => println!("Binary file {} differs", path),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `baro upstream pull`: three-way merge of the latest upstream release into
+/// the working tree, using the forked release as the common base.
+async fn cmd_upstream_pull(dry_run: bool, remote: Option<&str>) -> Result<()> {
+    let cwd = project_root()?;
+    let mut m = manifest::read(&cwd)?;
+    let up = m.upstream(remote)?;
+    let origin = up.origin.clone();
+    let (username, slug) = split_origin(&origin)?;
+    let primary = up.name == types::PRIMARY_REMOTE;
+
+    let releases = api::BaroClient::anonymous().list_releases(username, slug).await?;
+    let forked = up.version.clone();
+    let offered = upstream::apply_policy(&releases.releases, &up);
+    let relation = upstream::relation(&forked, up.release_id.as_deref(), &offered);
+    println!("{}", relation.summary(&forked));
+    let Some(target) = relation.pull_target() else {
+        return Ok(());
+    };
+    let target_version = target.version.clone();
+
+    progress!("Downloading {}@{} and {}@{}...", origin, forked, origin, target_version);
+    let (_, base_download, base) = fetch_release_files(username, slug, Some(&forked)).await?;
+    let (new_version, download, theirs) = fetch_release_files(username, slug, Some(&target_version)).await?;
+    let mine = packaging::read_tree(&cwd)?;
+    let mut actions = if up.file_hash.as_ref().is_some_and(|h| *h != base_download.file_hash_sha256) {
+        // The forked release was re-released: only the recorded hashes remain
+        let recorded = baseline::read(&cwd)?.filter(|b| primary && b.version == forked).ok_or_else(|| {
+            anyhow::anyhow!(
+                "{}@{} was re-released after you forked it, so there is no common base to merge from.\n\
+                 Run `baro pull` to fetch the new release next to this project and merge by hand.",
+                origin,
+                forked
+            )
+        })?;
+        eprintln!("{}@{} was re-released; merging from the file hashes recorded at fork time.", origin, forked);
+        merge::plan_from_hashes(&recorded.files, &mine, &theirs)
+    } else {
+        merge::plan(&base, &mine, &theirs)
+    };
+    // `mine` leaves out ignored and excluded files, which may still be on disk
+    let untracked = merge::protect_untracked(&mut actions, &mine, |path| cwd.join(path).exists());
+
+    let mut conflicted = Vec::new();
+    for (path, action) in &actions {
+        match action {
+            merge::Action::Update(_) if mine.contains_key(path) => println!("  U {}", path),
+            merge::Action::Update(_) => println!("  A {}", path),
+            merge::Action::Delete => println!("  D {}", path),
+            merge::Action::Merged(_) => println!("  M {} (merged with local changes)", path),
+            merge::Action::Conflict(_, n) => {
+                println!("  C {} ({} conflict{})", path, n, if *n == 1 { "" } else { "s" });
+                conflicted.push(path.as_str());
+            }
+            merge::Action::Unmergeable(theirs) => {
+                let why = match (mine.contains_key(path), theirs) {
+                    _ if untracked.contains(path) => {
+                        format!("added upstream, but an ignored local file is in the way; upstream's version saved as {}.upstream", path)
+                    }
+                    (true, Some(_)) => format!("can't merge; upstream's version saved as {}.upstream", path),
+                    (true, None) => "changed locally, deleted upstream; local version kept".to_string(),
+                    (false, _) => format!("deleted locally, changed upstream; upstream's version saved as {}.upstream", path),
+                };
+                println!("  C {} ({})", path, why);
+                conflicted.push(path.as_str());
+            }
+        }
+    }
+    if actions.is_empty() {
+        println!("  No file changes (upstream's changes are already here)");
+    }
+
+    if dry_run {
+        println!("Dry run: nothing written.");
+        return Ok(());
+    }
+    if !actions.is_empty()
+        && !wizard::confirm(
+            &format!("Apply {} change{} to {}?", actions.len(), if actions.len() == 1 { "" } else { "s" }, cwd.display()),
+            "Re-run with --yes to apply, or --dry-run to preview",
+        )?
+    {
+        return Err(anyhow::anyhow!("Cancelled"));
+    }
+
+    apply_merge(&cwd, &actions)?;
+
+    // The fork now descends from the new release
+    m.advance(&up.name, &new_version, download.release_id.clone(), Some(download.file_hash_sha256.clone()));
+    manifest::write(&cwd, &m)?;
+    if primary {
+        baseline::write(&cwd, &new_version, &theirs)?;
+    }
+    upstream_cache::record(&origin, &new_version);
+
+    println!("Merged {}@{} into this fork.", origin, new_version);
+    if !conflicted.is_empty() {
+        println!();
+        println!("Resolve the conflicts in:");
+        for path in &conflicted {
+            println!("  {}", path);
+        }
+        println!("Edit out the <<<<<<< local / ======= / >>>>>>> upstream markers, and compare");
+        println!("any .upstream copies with your version before deleting them.");
+    }
+    Ok(())
+}
+
+/// Write merge `actions` into the project at `cwd`. Unmergeable paths keep
+/// the local file, with upstream's copy (if any) saved as `<path>.upstream`.
+fn apply_merge(cwd: &std::path::Path, actions: &std::collections::BTreeMap<String, merge::Action>) -> Result<()> {
+    for (path, action) in actions {
+        let target = cwd.join(path);
+        let contents = match action {
+            merge::Action::Update(c) | merge::Action::Merged(c) | merge::Action::Conflict(c, _) => Some((target, c)),
+            merge::Action::Unmergeable(Some(c)) => Some((cwd.join(format!("{}.upstream", path)), c)),
+            merge::Action::Unmergeable(None) => None,
+            merge::Action::Delete => {
+                std::fs::remove_file(&target).with_context(|| format!("Failed to remove {}", path))?;
+                None
+            }
+        };
+        if let Some((dest, c)) = contents {
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&dest, c).with_context(|| format!("Failed to write {}", dest.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// `baro upstream rebase`: fork the latest upstream release next to the project,
+/// replay the local changes (forked release → working tree) onto it, and on
+/// success bring the result back into the project. Files the archive never
+/// carries (`.git`, ignored files, `.env`) stay where they are; where upstream
+/// now ships one of them, its copy is saved as `<path>.upstream`.
+async fn cmd_upstream_rebase(remote: Option<&str>) -> Result<()> {
+    let cwd = project_root()?;
+    let mut m = manifest::read(&cwd)?;
+    let up = m.upstream(remote)?;
+    let origin = up.origin.clone();
+    let (username, slug) = split_origin(&origin)?;
+
+    let releases = api::BaroClient::anonymous().list_releases(username, slug).await?;
+    let forked = up.version.clone();
+    let offered = upstream::apply_policy(&releases.releases, &up);
+    let relation = upstream::relation(&forked, up.release_id.as_deref(), &offered);
+    println!("{}", relation.summary(&forked));
+    let Some(target) = relation.pull_target() else {
+        return Ok(());
+    };
+    let target_version = target.version.clone();
+
+    progress!("Downloading {}@{}...", origin, forked);
+    let (_, base_download, base) = fetch_release_files(username, slug, Some(&forked)).await?;
+    if up.file_hash.as_ref().is_some_and(|h| *h != base_download.file_hash_sha256) {
+        return Err(anyhow::anyhow!(
+            "{}@{} was re-released after you forked it, so your changes can't be told apart from its contents.\n\
+             Run `baro upstream pull{}` to merge from the recorded file hashes instead.",
+            origin,
+            forked,
+            remote_flag(&up)
+        ));
+    }
+    let mine = packaging::read_tree(&cwd)?;
+    let local = diff::compare_trees(&base, &mine);
+    if local.is_empty() {
+        println!("No local changes since {}; `baro upstream pull{}` updates this fork directly.", forked, remote_flag(&up));
     }
-    let (username, slug) = (parts[0], parts[1]);
 
-    let client = api::BaroClient::anonymous();
-    let releases = client.list_releases(username, slug).await?;
+    let parent = cwd.parent().ok_or_else(|| anyhow::anyhow!("Cannot determine parent directory"))?;
+    let dir_name = cwd.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| slug.to_string());
+    let rebased_name = format!("{}-rebase-{}", dir_name, target_version);
+    let rebased = parent.join(&rebased_name);
+    if rebased.exists() {
+        return Err(anyhow::anyhow!(
+            "Directory '{}' already exists from an earlier rebase. Remove it to rebase again.",
+            rebased_name
+        ));
+    }
+    let rebased_str = rebased.to_str().ok_or_else(|| anyhow::anyhow!("Path contains invalid UTF-8"))?;
+    let product_spec = format!("{}@{}", origin, target_version);
+    let result = fork_impl(&product_spec, Some(rebased_str), None, false, false, false).await?;
+    let theirs = packaging::read_tree(&rebased)?;
 
-    match releases.releases.first() {
-        Some(latest) if latest.version != m.version => {
-            println!("New version available: {} (current: {})", latest.version, m.version);
-            if let Some(ref cl) = latest.changelog {
-                let preview = utils::truncate_str(cl, 100);
-                println!("  Changelog: {}", preview);
+    println!("Reapplying {} onto {}:", local.summary(), target_version);
+    let mut rejected = 0usize;
+    for (path, change) in merge::reapply(&base, &mine, &theirs) {
+        let dest = rebased.join(&path);
+        match change {
+            merge::Reapply::Write(contents) => {
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&dest, contents).with_context(|| format!("Failed to write {}", dest.display()))?;
+                println!("  applied  {}", path);
+            }
+            merge::Reapply::Remove => {
+                std::fs::remove_file(&dest).with_context(|| format!("Failed to remove {}", dest.display()))?;
+                println!("  applied  {} (deleted)", path);
+            }
+            merge::Reapply::Reject(why, patch) => {
+                rejected += 1;
+                println!("  REJECTED {}: {}", path, why);
+                if let Some(patch) = patch {
+                    let rej = rebased.join(format!("{}.rej", path));
+                    if let Some(parent) = rej.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::write(&rej, patch)?;
+                }
             }
-            println!("  Run: baro pull");
-        }
-        Some(_) => {
-            println!("Up to date with upstream ({})", m.version);
-        }
-        None => {
-            println!("No releases found for {}", origin);
         }
     }
 
+    if rejected > 0 {
+        println!();
+        println!("Your project is unchanged. The partly rebased tree is in ../{}/:", rebased_name);
+        println!("apply the .rej diffs there by hand, or delete it to keep your current version.");
+        return Err(anyhow::anyhow!("{} change(s) could not be reapplied", rejected));
+    }
+
+    // Every change applied: bring the rebased files into the project
+    let rebased_files = packaging::read_tree(&rebased)?;
+    let mut actions: std::collections::BTreeMap<String, merge::Action> = rebased_files
+        .iter()
+        .filter(|(path, contents)| mine.get(*path) != Some(*contents))
+        .map(|(path, contents)| (path.clone(), merge::Action::Update(contents.clone())))
+        .collect();
+    actions.extend(mine.keys().filter(|p| !rebased_files.contains_key(*p)).map(|p| (p.clone(), merge::Action::Delete)));
+    // `mine` leaves out ignored and excluded files, which may still be on disk
+    let untracked = merge::protect_untracked(&mut actions, &mine, |path| cwd.join(path).exists());
+    for path in &untracked {
+        println!("  C {} (added upstream, but an ignored local file is in the way; upstream's version saved as {}.upstream)", path, path);
+    }
+    if !actions.is_empty()
+        && !wizard::confirm(
+            &format!("Apply {} change{} to {}?", actions.len(), if actions.len() == 1 { "" } else { "s" }, cwd.display()),
+            "Re-run with --yes to apply",
+        )?
+    {
+        println!("Your project is unchanged. The rebased tree is in ../{}/.", rebased_name);
+        return Err(anyhow::anyhow!("Cancelled"));
+    }
+    apply_merge(&cwd, &actions)?;
+
+    let rebased_manifest = manifest::read(&rebased)?;
+    m.advance(&up.name, &result.version, rebased_manifest.origin_release_id, rebased_manifest.file_hash);
+    manifest::write(&cwd, &m)?;
+    if up.name == types::PRIMARY_REMOTE {
+        baseline::write(&cwd, &result.version, &theirs)?;
+    }
+    upstream_cache::record(&origin, &result.version);
+    std::fs::remove_dir_all(&rebased)?;
+
+    println!("Rebased onto {}@{}", origin, result.version);
     Ok(())
 }
 
 async fn cmd_pull() -> Result<()> {
-    let cwd = std::env::current_dir()?;
+    let cwd = project_root()?;
     let m = manifest::read(&cwd)?;
 
     // 1. Require fork origin
@@ -787,21 +4012,20 @@ async fn cmd_pull() -> Result<()> {
     // 2. Check upstream for new version (no auth needed for read)
     let client = api::BaroClient::anonymous();
     let releases = client.list_releases(parts[0], slug).await?;
+    let forked = m.forked_version();
+    let offered = upstream::apply_policy(&releases.releases, &m.upstream(None)?);
+    let relation = upstream::relation(forked, m.origin_release_id.as_deref(), &offered);
 
-    let latest = match releases.releases.first() {
-        Some(latest) if latest.version != m.version => latest,
-        Some(_) => {
-            println!("Up to date with upstream ({})", m.version);
-            return Ok(());
-        }
-        None => {
-            println!("No releases found for {}", origin);
-            return Ok(());
-        }
+    if let Some(latest) = releases.releases.iter().filter(|r| !r.is_yanked()).max_by(|a, b| utils::compare_versions(&a.version, &b.version)) {
+        upstream_cache::record(origin, &latest.version);
+    }
+
+    println!("{}", relation.summary(forked));
+    let Some(latest) = relation.pull_target() else {
+        return Ok(());
     };
 
     let new_version = &latest.version;
-    println!("New version available: {} (current: {})", new_version, m.version);
     if let Some(ref cl) = latest.changelog {
         let preview = utils::truncate_str(cl, 200);
         println!("  Changelog: {}", preview);
@@ -827,13 +4051,14 @@ async fn cmd_pull() -> Result<()> {
     let sibling_str = sibling_path.to_str()
         .ok_or_else(|| anyhow::anyhow!("Path contains invalid UTF-8"))?;
 
-    let result = fork_impl(&product_spec, Some(sibling_str)).await?;
+    let result = fork_impl(&product_spec, Some(sibling_str), None, false, false, false).await?;
 
     println!(
-        "Pulled {}/{}@{} → ../{}/ ({})",
+        "Pulled {}/{}@{} {} ../{}/ ({})",
         result.username,
         result.slug,
         result.version,
+        ui::arrow(),
         sibling_name,
         utils::format_bytes(result.size_bytes)
     );
@@ -854,7 +4079,7 @@ async fn cmd_pull() -> Result<()> {
     );
     println!(
         "in ./{} (forked from {}@{}). Identify what changed upstream,",
-        current_dir_name, origin, m.version
+        current_dir_name, origin, forked
     );
     println!("then apply those changes to my project while preserving my customizations.");
     println!("Show me a summary of conflicts if any of my modified files were also changed upstream.");
@@ -863,6 +4088,637 @@ async fn cmd_pull() -> Result<()> {
     Ok(())
 }
 
+async fn cmd_health(include_tests: bool, timeout_secs: u64) -> Result<()> {
+    let cwd = project_root()?;
+    let checks = health::detect_checks(&cwd, include_tests);
+    if checks.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No build commands detected (looked for Cargo.toml, package.json scripts, go.mod, pyproject.toml, Makefile)."
+        ));
+    }
+
+    let timeout = std::time::Duration::from_secs(timeout_secs);
+    let mut failed = Vec::new();
+    for check in &checks {
+        println!("Running {} ({})...", check.command_line(), check.label);
+        let outcome = health::run_check(&cwd, check, timeout).await;
+        if outcome.passed {
+            println!("  OK");
+            continue;
+        }
+        println!("  FAILED{}", if outcome.timed_out { " (timeout)" } else { "" });
+        for line in &outcome.summary {
+            println!("    {}", line);
+        }
+        failed.push((check, outcome));
+    }
+
+    println!();
+    if failed.is_empty() {
+        println!("Healthy: {} check{} passed", checks.len(), if checks.len() == 1 { "" } else { "s" });
+        return Ok(());
+    }
+
+    println!("{} of {} checks failed.", failed.len(), checks.len());
+    println!();
+    println!("To fix, ask your AI assistant:");
+    println!();
+    println!("---");
+    for (check, outcome) in &failed {
+        println!("`{}` fails with:", check.command_line());
+        for line in &outcome.summary {
+            println!("  {}", line);
+        }
+    }
+    println!("Fix these errors without undoing my intended changes.");
+    println!("---");
+
+    if let Ok(m) = manifest::read(&cwd) {
+        if let Some(ref origin) = m.origin {
+            println!();
+            println!("Or start over from a clean copy:");
+            println!("  baro fork {}@{} --dir {}-clean", origin, m.forked_version(), utils::dir_to_slug(&cwd));
+        }
+    }
+
+    Err(anyhow::anyhow!("{} of {} checks failed", failed.len(), checks.len()))
+}
+
+/// Split `user/product[@version]`.
+fn parse_product_ref(product: &str) -> Result<(&str, &str, Option<&str>)> {
+    let (user_slug, version) = match product.rfind('@') {
+        Some(idx) => (&product[..idx], Some(&product[idx + 1..])),
+        None => (product, None),
+    };
+    match user_slug.split_once('/') {
+        Some((user, slug)) if !user.is_empty() && !slug.is_empty() => Ok((user, slug, version)),
+        _ => Err(anyhow::anyhow!(
+            "Invalid product identifier. Use: user/product or user/product@version"
+        )),
+    }
+}
+
+/// `user/product` from `product`, or the published product in the current directory.
+/// `unpublished_hint` is appended to the error when the product was never published.
+fn published_product(product: Option<&str>, unpublished_hint: &str) -> Result<(String, String)> {
+    if let Some(p) = product {
+        let (username, slug, _) = parse_product_ref(p)?;
+        return Ok((username.to_string(), slug.to_string()));
+    }
+    let m = manifest::read(&project_root()?)?;
+    match (m.publisher, m.slug) {
+        (Some(publisher), Some(slug)) => Ok((publisher, slug)),
+        _ => Err(anyhow::anyhow!(
+            "This product has not been published yet. {}",
+            unpublished_hint
+        )),
+    }
+}
+
+async fn cmd_verify(product: Option<&str>, check_provenance: bool) -> Result<()> {
+    // Default to the fork in the current directory, checking its recorded hash too
+    let local = match product {
+        Some(_) => None,
+        None => Some(manifest::read(&project_root()?)?),
+    };
+    let product = match (product, &local) {
+        (Some(p), _) => p.to_string(),
+        (None, Some(m)) => {
+            let origin = m.origin.as_deref().ok_or_else(|| {
+                anyhow::anyhow!("No fork origin in manifest. Pass a product: baro verify user/product@version")
+            })?;
+            format!("{}@{}", origin, m.forked_version())
+        }
+        (None, None) => unreachable!(),
+    };
+    let (username, slug, version) = parse_product_ref(&product)?;
+
+    // Anyone who could fork the release can verify it
+    let (client, version, download) = fork_download(username, slug, version, None).await?;
+    println!("Verifying {}/{}@{}...", username, slug, version);
+    let hash = download.file_hash_sha256;
+    if let Some(recorded) = local.as_ref().and_then(|m| m.file_hash.as_deref()) {
+        if recorded != hash {
+            return Err(anyhow::anyhow!(
+                "This fork was made from a different archive ({}) than the published release ({})",
+                &recorded[..recorded.len().min(12)], &hash[..hash.len().min(12)]
+            ));
+        }
+        println!("  Fork matches published archive ({})", &hash[..hash.len().min(12)]);
+    }
+
+    let keys: Vec<String> = client
+        .list_signing_keys(username)
+        .await?
+        .keys
+        .into_iter()
+        .map(|k| k.public_key)
+        .collect();
+    match download.signature {
+        Some(ref sig) if keys.iter().any(|k| signing::verify_hash(k, &hash, sig)) => {
+            println!("  Signature verified ({})", username);
+        }
+        Some(_) => {
+            return Err(anyhow::anyhow!(
+                "Signature verification failed for {}/{}@{}",
+                username, slug, version
+            ));
+        }
+        None => println!("  Release is not signed"),
+    }
+
+    if check_provenance {
+        let signed = client
+            .get_provenance(username, slug, &version)
+            .await
+            .map_err(|e| anyhow::anyhow!("No provenance available for {}/{}@{}: {}", username, slug, version, e))?;
+        let p = provenance::verify(&signed, &keys, &hash)?;
+        println!("  Provenance verified");
+        println!("    Builder: {} ({})", p.predicate.builder.id, p.predicate.builder.ci);
+        if let Some(ref workflow) = p.predicate.builder.workflow {
+            println!("    Workflow: {}", workflow);
+        }
+        if let Some(ref repo) = p.predicate.source.repository {
+            println!("    Source: {}", repo);
+        }
+        println!("    Commit: {}", p.predicate.source.commit);
+        println!("    Built: {}", p.predicate.built_at);
+    }
+
+    Ok(())
+}
+
+async fn cmd_keygen(force: bool) -> Result<()> {
+    if signing::key_exists() && !force {
+        return Err(anyhow::anyhow!(
+            "A signing key already exists. Use --force to replace it (releases signed with the old key stay verifiable only while it remains registered)."
+        ));
+    }
+
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+
+    let key = signing::generate()?;
+    let public_key = signing::public_key_hex(&key);
+    client.register_signing_key(&public_key).await?;
+    signing::save(&key)?;
+
+    println!("Generated ed25519 signing key");
+    println!("  Public key: {}", public_key);
+    println!("Future releases will be signed automatically on publish.");
+    Ok(())
+}
+
+async fn cmd_slug_check(slug: &str) -> Result<()> {
+    if !validate_slug(slug) {
+        let suggestion = utils::slugify(slug);
+        let hint = if validate_slug(&suggestion) {
+            format!(" Try '{}'.", suggestion)
+        } else {
+            String::new()
+        };
+        return Err(anyhow::anyhow!(
+            "Invalid slug '{}'. Must be lowercase alphanumeric with hyphens, not starting/ending with hyphen.{}",
+            slug, hint
+        ));
+    }
+
+    let (client, username) = match auth::get_token().await {
+        Ok(token) => {
+            let client = api::BaroClient::new(&token);
+            let username = client.get_me().await?.user.username;
+            (client, Some(username))
+        }
+        Err(_) => (api::BaroClient::anonymous(), None),
+    };
+    let status = client.check_slug(slug).await?;
+
+    let free_for_you = status.available_for_you.unwrap_or(status.available_globally);
+    if status.available_globally {
+        println!("'{}' is available.", slug);
+        return Ok(());
+    }
+    let used_by = if status.taken_by.is_empty() {
+        String::new()
+    } else {
+        format!(" (used by {})", status.taken_by.join(", "))
+    };
+    match (free_for_you, &username) {
+        (true, Some(user)) => println!("'{}' is free as {}/{}, but taken elsewhere{}.", slug, user, slug, used_by),
+        (true, None) => println!("'{}' is taken elsewhere{}. Log in to check your namespace.", slug, used_by),
+        (false, _) => {
+            println!("'{}' is taken in your namespace{}.", slug, used_by);
+            let mut free = Vec::new();
+            for candidate in utils::slug_alternatives(slug, username.as_deref().unwrap_or("")) {
+                if free.len() == 3 {
+                    break;
+                }
+                let ok = client.check_slug(&candidate).await
+                    .map(|s| s.available_for_you.unwrap_or(s.available_globally))
+                    .unwrap_or(false);
+                if ok {
+                    free.push(candidate);
+                }
+            }
+            if !free.is_empty() {
+                println!("Available instead: {}", free.join(", "));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cmd_snapshot(action: SnapshotAction) -> Result<()> {
+    let root = project_root()?;
+
+    match action {
+        SnapshotAction::Save { message } => {
+            let snap = snapshot::save(&root, message.as_deref())?;
+            println!("Saved snapshot {} ({} files)", snap.id, snap.files.len());
+        }
+        SnapshotAction::List => {
+            let snapshots = snapshot::list(&root)?;
+            if snapshots.is_empty() {
+                println!("No snapshots yet. Run `baro snapshot save` to create one.");
+                return Ok(());
+            }
+            for snap in &snapshots {
+                println!(
+                    "{:<18} {:>5} files  {}",
+                    snap.id,
+                    snap.files.len(),
+                    snap.message.as_deref().unwrap_or("")
+                );
+            }
+        }
+        SnapshotAction::Restore { id, paths } => {
+            let target = snapshot::load(&root, &id)?;
+            let backup = snapshot::save(&root, Some(&format!("before restoring {}", id)))?;
+            let report = snapshot::restore(&root, &target, &paths)?;
+
+            for path in &report.written {
+                println!("  restored {}", path);
+            }
+            for path in &report.removed {
+                println!("  removed  {}", path);
+            }
+            if report.written.is_empty() && report.removed.is_empty() {
+                println!("Already matches snapshot {}", id);
+            } else {
+                println!(
+                    "Restored {} file{} from {}",
+                    report.written.len() + report.removed.len(),
+                    if report.written.len() + report.removed.len() == 1 { "" } else { "s" },
+                    id
+                );
+            }
+            println!("Previous state saved as {} (undo: baro snapshot restore {})", backup.id, backup.id);
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_visibility(level: Option<Visibility>, product: Option<&str>) -> Result<()> {
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+
+    let (username, slug) = published_product(
+        product,
+        "Use --private or --unlisted on `baro publish` instead.",
+    )?;
+
+    let current = client.get_product(&username, &slug).await?;
+    let Some(level) = level else {
+        println!("{}/{} is {}", username, slug, current.visibility());
+        return Ok(());
+    };
+    if current.visibility() == level.as_str() {
+        println!("{}/{} is already {}", username, slug, level.as_str());
+        return Ok(());
+    }
+    client.set_visibility(&username, &slug, level.as_str()).await?;
+    println!("{}/{} is now {} (was {}); its releases are unchanged", username, slug, level.as_str(), current.visibility());
+    Ok(())
+}
+
+/// `message` deprecates the product; None (`--undo`) lifts it.
+async fn cmd_deprecate(product: Option<&str>, message: Option<&str>) -> Result<()> {
+    let (username, slug) = published_product(product, "Pass a product: baro deprecate user/product")?;
+    if message.is_some_and(|m| m.trim().is_empty()) {
+        return Err(anyhow::anyhow!("Say why in --message, e.g. \"superseded by {}/other\"", username));
+    }
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+    client.set_deprecation(&username, &slug, message.map(str::trim)).await?;
+    match message {
+        Some(m) => {
+            println!("{}/{} is now deprecated: {}", username, slug, m.trim());
+            println!("Its releases can still be forked; `baro fork` and `baro upstream` show your message.");
+        }
+        None => println!("{}/{} is no longer deprecated", username, slug),
+    }
+    Ok(())
+}
+
+/// "★★★★☆" for 4 of 5, or "4/5" in accessible mode.
+fn stars(rating: u8) -> String {
+    let rating = rating.min(5) as usize;
+    if ui::accessible() {
+        format!("{}/5", rating)
+    } else {
+        format!("{}{}", "★".repeat(rating), "☆".repeat(5 - rating))
+    }
+}
+
+/// `baro reviews`: one product's reviews, or every product's without one.
+async fn cmd_reviews(product: Option<&str>, unanswered: bool) -> Result<()> {
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+    let products = match published_product(product, "") {
+        Ok(p) => vec![p],
+        // Outside a published product: all of yours
+        Err(_) if product.is_none() => {
+            let me = client.get_me().await?;
+            let mine = client.list_my_products().await?.products;
+            mine.into_iter().map(|p| (me.user.username.clone(), p.slug)).collect()
+        }
+        Err(e) => return Err(e),
+    };
+
+    let mut shown = 0;
+    for (username, slug) in &products {
+        let reviews = client.list_reviews(username, slug).await?.reviews;
+        let listed: Vec<&types::Review> = reviews.iter().filter(|r| !unanswered || r.reply.is_none()).collect();
+        if listed.is_empty() && products.len() > 1 {
+            continue;
+        }
+        let avg = if reviews.is_empty() {
+            "-".to_string()
+        } else {
+            format!("{:.1}", reviews.iter().map(|r| f64::from(r.rating)).sum::<f64>() / reviews.len() as f64)
+        };
+        println!("{}/{}: {} review{}, average {}", username, slug, reviews.len(), if reviews.len() == 1 { "" } else { "s" }, avg);
+        for r in listed {
+            let author = r.author.as_ref().map(|a| a.username.as_str()).unwrap_or("anonymous");
+            println!("\n  {}  {}  {}  (id {})", stars(r.rating), author, ui::short_timestamp(&r.created_at), r.id);
+            if let Some(body) = r.body.as_deref().filter(|b| !b.trim().is_empty()) {
+                body.lines().for_each(|line| println!("    {}", line));
+            }
+            if let Some(ref reply) = r.reply {
+                println!("    {} You replied {}: {}", ui::arrow(), ui::short_timestamp(&reply.created_at), reply.body);
+            }
+            shown += 1;
+        }
+        println!();
+    }
+    if shown == 0 {
+        println!("{}", if unanswered { "No unanswered reviews." } else { "No reviews yet." });
+    } else {
+        println!("Reply with: baro reviews reply <id> --message \"...\"");
+    }
+    Ok(())
+}
+
+async fn cmd_review_reply(review_id: &str, message: &str) -> Result<()> {
+    if message.trim().is_empty() {
+        return Err(anyhow::anyhow!("The reply is empty; pass it with --message"));
+    }
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+    let review = match client.reply_to_review(review_id, message.trim()).await {
+        Err(e) if error::status(&e) == Some(404) => {
+            return Err(e.context(format!("No review {} on your products (ids are listed by `baro reviews`)", review_id)));
+        }
+        result => result?,
+    };
+    println!("Replied to the {} review {}", stars(review.rating), review.id);
+    Ok(())
+}
+
+async fn cmd_rate(product: &str, rating: u8, review: Option<&str>) -> Result<()> {
+    let (username, slug, version) = parse_product_ref(product)?;
+    if version.is_some() {
+        return Err(anyhow::anyhow!("Ratings are per product, not per release; drop the @version"));
+    }
+    if review.is_some_and(|r| r.trim().is_empty()) {
+        return Err(anyhow::anyhow!("The review is empty; leave out --review to rate without one"));
+    }
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+    let rated = match client.rate_product(username, slug, rating, review.map(str::trim)).await {
+        Err(e) if error::status(&e) == Some(404) => {
+            get_product_or_suggest(&client, username, slug).await?;
+            return Err(e);
+        }
+        Err(e) if error::status(&e) == Some(403) => {
+            return Err(e.context(format!("Only products you've forked can be rated; fork it first: baro fork {}/{}", username, slug)));
+        }
+        result => result?,
+    };
+    println!("Rated {}/{} {}", username, slug, stars(rated.rating));
+    if rated.body.is_some() {
+        println!("Your review is on the product page; rating again replaces it.");
+    }
+    Ok(())
+}
+
+/// Largest media file accepted for a product page.
+const MAX_MEDIA_BYTES: i64 = 20 * 1024 * 1024;
+
+async fn cmd_media(action: MediaAction) -> Result<()> {
+    let token = auth::get_token().await?;
+    let client = api::BaroClient::new(&token);
+    let hint = "Publish it first, or pass --product user/product.";
+
+    match action {
+        MediaAction::Add { file, caption, product } => {
+            let (username, slug) = published_product(product.as_deref(), hint)?;
+            let content_type = utils::media_content_type(&file).ok_or_else(|| {
+                anyhow::anyhow!("Unsupported media type: {}. Use png, jpg, gif, webp, mp4 or webm.", file.display())
+            })?;
+            let data = std::fs::read(&file).with_context(|| format!("Failed to read {}", file.display()))?;
+            let size = data.len() as i64;
+            if size > MAX_MEDIA_BYTES {
+                return Err(anyhow::anyhow!(
+                    "{} is {}; media files are limited to {}",
+                    file.display(), utils::format_bytes(size), utils::format_bytes(MAX_MEDIA_BYTES)
+                ));
+            }
+            let filename = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let id = client
+                .upload_media(&username, &slug, &types::CreateMediaRequest {
+                    filename: &filename,
+                    content_type,
+                    file_size_bytes: size,
+                    caption: caption.as_deref(),
+                }, &data)
+                .await?;
+            println!("Added {} to {}/{} ({}, id {})", filename, username, slug, utils::format_bytes(size), id);
+        }
+        MediaAction::List { product } => {
+            let (username, slug) = published_product(product.as_deref(), hint)?;
+            let media = client.list_media(&username, &slug).await?.media;
+            if media.is_empty() {
+                println!("No media on {}/{}. Add some with: baro media add <file>", username, slug);
+                return Ok(());
+            }
+            // Full ids: `baro media remove` needs them
+            let id_width = media.iter().map(|m| m.id.len()).max().unwrap_or(0).max(2);
+            println!("{:<id_width$} {:<12} {:>10}  {:<14} CAPTION", "ID", "TYPE", "SIZE", "ADDED");
+            for m in &media {
+                println!(
+                    "{:<id_width$} {:<12} {:>10}  {:<14} {}",
+                    m.id,
+                    m.content_type,
+                    utils::format_bytes(m.file_size_bytes),
+                    ui::short_timestamp(&m.created_at),
+                    m.caption.as_deref().unwrap_or("-")
+                );
+                println!("{:id_width$} {}", "", m.url);
+            }
+        }
+        MediaAction::Remove { id, product } => {
+            let (username, slug) = published_product(product.as_deref(), hint)?;
+            client.remove_media(&username, &slug, &id).await?;
+            println!("Removed media {} from {}/{}", id, username, slug);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "moderation")]
+async fn cmd_mod(action: cli::ModAction) -> Result<()> {
+    use cli::ModAction;
+
+    let token = config::admin_token().ok_or_else(|| {
+        anyhow::anyhow!("Set BARO_ADMIN_TOKEN to an admin-scoped API token to use `baro mod`")
+    })?;
+    let client = api::BaroClient::new(&token);
+
+    match action {
+        ModAction::Queue => {
+            let queue = client.review_queue().await?;
+            if queue.releases.is_empty() {
+                println!("Review queue is empty.");
+                return Ok(());
+            }
+            for r in &queue.releases {
+                let size = r.file_size_bytes.map(utils::format_bytes).unwrap_or_else(|| "-".to_string());
+                println!(
+                    "{}  {}/{}@{}  {}  {}",
+                    r.id, r.publisher, r.product_slug, r.version, size,
+                    ui::timestamp(&r.created_at)
+                );
+            }
+            println!("\n{} pending", queue.releases.len());
+        }
+        ModAction::Approve { release, reason } => {
+            client.review_release(&release, "approve", reason.as_deref()).await?;
+            println!("Approved {}", release);
+        }
+        ModAction::Reject { release, reason } => {
+            client.review_release(&release, "reject", Some(&reason)).await?;
+            println!("Rejected {}: {}", release, reason);
+        }
+        ModAction::Inspect { release } => {
+            let download = client.admin_download(&release).await?;
+            let work = std::env::temp_dir().join(format!("baro-inspect-{}", uuid::Uuid::new_v4()));
+            let archive = work.with_extension("tar.gz");
+            let result = inspect_release(&client, &download, &archive, &work).await;
+            let _ = std::fs::remove_file(&archive);
+            let _ = std::fs::remove_dir_all(&work);
+            result?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "moderation")]
+async fn inspect_release(
+    client: &api::BaroClient,
+    download: &types::DownloadResponse,
+    archive: &std::path::Path,
+    work: &std::path::Path,
+) -> Result<()> {
+    let (size, hash) = client.download_to_file(&download.download_url, archive).await?;
+    if hash != download.file_hash_sha256 {
+        return Err(anyhow::anyhow!(
+            "Hash mismatch! Expected: {}, got: {}",
+            download.file_hash_sha256, hash
+        ));
+    }
+    packaging::extract_archive_file(archive, work)?;
+
+    // Everything in the archive, including what publishing would have excluded
+    let files = packaging::list_all_files(work)?;
+    println!("Archive: {} ({}), {} files", utils::format_bytes(size as i64), &hash[..12], files.len());
+    let mut largest: Vec<(u64, &std::path::PathBuf)> = files
+        .iter()
+        .map(|f| (std::fs::metadata(work.join(f)).map(|m| m.len()).unwrap_or(0), f))
+        .collect();
+    largest.sort_by_key(|(len, _)| std::cmp::Reverse(*len));
+    println!("Largest files:");
+    for (len, f) in largest.iter().take(5) {
+        println!("  {:>10}  {}", utils::format_bytes(*len as i64), f.display());
+    }
+
+    let audit = publish_gate::audit(work);
+    if audit.passed {
+        println!("\nAudit passed");
+    } else {
+        println!("\nAudit found {} problem(s):", audit.failures.len());
+        for f in &audit.failures {
+            println!("  - {}", f.message);
+        }
+    }
+    Ok(())
+}
+
+fn cmd_maintenance_run() {
+    let report = maintenance::run();
+    if report.is_empty() {
+        println!("Nothing to clean up.");
+        return;
+    }
+    if report.files_removed > 0 {
+        println!("Removed {} file(s), freeing {}", report.files_removed, utils::format_bytes(report.bytes_freed as i64));
+    }
+    if report.cache_entries_pruned > 0 {
+        println!("Pruned {} stale cache entr{}", report.cache_entries_pruned, if report.cache_entries_pruned == 1 { "y" } else { "ies" });
+    }
+    if report.signed_out {
+        println!("Removed credentials the server no longer accepts; run `baro login` to sign in again.");
+    }
+}
+
+async fn cmd_feedback(message: Option<&str>, crash: bool, crash_file: Option<std::path::PathBuf>) -> Result<()> {
+    let report = match (crash, crash_file) {
+        (false, None) => None,
+        (_, path) => {
+            let path = match path {
+                Some(p) => p,
+                None => crash::latest_report()?,
+            };
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read crash report {}", path.display()))?;
+            println!("{}", text.trim_end());
+            let question = format!("Send this crash report ({})?", path.display());
+            if !wizard::confirm(&question, "Pass --yes to send it without asking.")? {
+                return Err(anyhow::anyhow!("Cancelled; nothing was sent"));
+            }
+            Some(text)
+        }
+    };
+    let client = match auth::get_token().await {
+        Ok(token) => api::BaroClient::new(&token),
+        Err(_) => api::BaroClient::anonymous(),
+    };
+    client.send_feedback(message, report.as_deref()).await?;
+    println!("Thanks! Your feedback was sent.");
+    Ok(())
+}
+
 fn cmd_logout() -> Result<()> {
     let path = config::credentials_path()?;
     if path.exists() {
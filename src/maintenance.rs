@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::auth;
+use crate::config;
+use crate::crash;
+use crate::search_cache;
+use crate::upstream_cache;
+use crate::utils;
+
+const DAY: Duration = Duration::from_secs(86400);
+
+/// The automatic pass runs at most this often.
+const INTERVAL: Duration = DAY;
+/// Upstream cache entries not refreshed for this long are dropped.
+const UPSTREAM_ENTRY_MAX_AGE: Duration = Duration::from_secs(90 * 86400);
+/// Cached search results are kept for `baro search --offline` this long.
+const SEARCH_ENTRY_MAX_AGE: Duration = Duration::from_secs(30 * 86400);
+/// Crash reports kept, newest first.
+const MAX_CRASH_REPORTS: usize = 10;
+/// Interrupted downloads (`baro-download-*.part`) older than this won't be resumed.
+const PARTIAL_DOWNLOAD_MAX_AGE: Duration = Duration::from_secs(7 * 86400);
+/// Leftover `baro edit` buffers.
+const EDIT_BUFFER_MAX_AGE: Duration = DAY;
+
+/// What a maintenance pass cleaned up.
+#[derive(Debug, Default, PartialEq)]
+pub struct Report {
+    pub files_removed: usize,
+    pub bytes_freed: u64,
+    pub cache_entries_pruned: usize,
+    pub signed_out: bool,
+}
+
+impl Report {
+    pub fn is_empty(&self) -> bool {
+        *self == Report::default()
+    }
+
+    fn remove(&mut self, path: &Path) {
+        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if std::fs::remove_file(path).is_ok() {
+            self.files_removed += 1;
+            self.bytes_freed += size;
+        }
+    }
+}
+
+fn state_path() -> Option<PathBuf> {
+    config::config_dir().ok().map(|d| d.join("maintenance.json"))
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct State {
+    last_run: i64,
+}
+
+/// Start the automatic pass in the background if one is due. Like the update
+/// check, the caller waits only briefly for it; every step is a handful of
+/// file operations, so a cut-short pass just leaves the rest for next time.
+pub fn spawn_if_due() -> Option<tokio::task::JoinHandle<Report>> {
+    let path = state_path()?;
+    let now = chrono::Utc::now().timestamp();
+    let last_run = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|data| serde_json::from_str::<State>(&data).ok())
+        .map_or(0, |s| s.last_run);
+    if now.saturating_sub(last_run) < INTERVAL.as_secs() as i64 {
+        return None;
+    }
+    // Claim the run up front so concurrent commands don't all start one
+    let json = serde_json::to_string(&State { last_run: now }).ok()?;
+    utils::write_atomic(&path, json.as_bytes()).ok()?;
+    Some(tokio::task::spawn_blocking(run))
+}
+
+/// A full pass: prune the upstream and search caches, rotate crash reports, delete stale
+/// temp files and clear sessions whose refresh token was rejected.
+pub fn run() -> Report {
+    let mut report = Report::default();
+    report.cache_entries_pruned += upstream_cache::prune(UPSTREAM_ENTRY_MAX_AGE);
+    report.cache_entries_pruned += search_cache::prune(SEARCH_ENTRY_MAX_AGE);
+    if let Ok(dir) = crash::reports_dir() {
+        for path in excess_reports(&dir, MAX_CRASH_REPORTS) {
+            report.remove(&path);
+        }
+    }
+    let tmp = std::env::temp_dir();
+    for path in stale_files(&tmp, "baro-download-", PARTIAL_DOWNLOAD_MAX_AGE, SystemTime::now()) {
+        report.remove(&path);
+    }
+    for path in stale_files(&tmp, "baro-edit-", EDIT_BUFFER_MAX_AGE, SystemTime::now()) {
+        report.remove(&path);
+    }
+    report.signed_out = auth::remove_rejected_credentials();
+    report
+}
+
+/// Crash reports beyond the newest `keep`.
+fn excess_reports(dir: &Path, keep: usize) -> Vec<PathBuf> {
+    let mut reports: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("crash-")))
+        .collect();
+    // Names embed a sortable timestamp
+    reports.sort();
+    let excess = reports.len().saturating_sub(keep);
+    reports.truncate(excess);
+    reports
+}
+
+/// Files in `dir` named `<prefix>*` and last modified more than `max_age` before `now`.
+fn stale_files(dir: &Path, prefix: &str, max_age: Duration, now: SystemTime) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with(prefix))
+        .filter(|e| {
+            e.metadata()
+                .ok()
+                .filter(|m| m.is_file())
+                .and_then(|m| m.modified().ok())
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age > max_age)
+        })
+        .map(|e| e.path())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_newest_crash_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        for day in 1..=4 {
+            std::fs::write(dir.path().join(format!("crash-2024010{}T000000.000Z.txt", day)), "").unwrap();
+        }
+        std::fs::write(dir.path().join("other.txt"), "").unwrap();
+        let names: Vec<String> = excess_reports(dir.path(), 2)
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["crash-20240101T000000.000Z.txt", "crash-20240102T000000.000Z.txt"]);
+        assert!(excess_reports(dir.path(), 10).is_empty());
+    }
+
+    #[test]
+    fn stale_files_by_prefix_and_age() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("baro-download-abc.tar.gz.part"), "x").unwrap();
+        std::fs::write(dir.path().join("unrelated.part"), "x").unwrap();
+        let now = SystemTime::now();
+        assert!(stale_files(dir.path(), "baro-download-", DAY, now).is_empty());
+        let later = now + 2 * DAY;
+        let stale = stale_files(dir.path(), "baro-download-", DAY, later);
+        assert_eq!(stale.len(), 1);
+        assert!(stale[0].ends_with("baro-download-abc.tar.gz.part"));
+    }
+}
@@ -1,13 +1,27 @@
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::types::Manifest;
 
 const MANIFEST_DIR: &str = ".baro";
 const MANIFEST_FILE: &str = "manifest.json";
 
+pub fn path(dir: &Path) -> PathBuf {
+    dir.join(MANIFEST_DIR).join(MANIFEST_FILE)
+}
+
+/// Walk up from `start` to the nearest directory containing `.baro/manifest.json`.
+pub fn find_root(start: &Path) -> Option<PathBuf> {
+    start.ancestors().find(|d| path(d).is_file()).map(Path::to_path_buf)
+}
+
+/// Every ancestor of `start` (nearest first) that holds its own manifest.
+pub fn find_all_roots(start: &Path) -> Vec<PathBuf> {
+    start.ancestors().filter(|d| path(d).is_file()).map(Path::to_path_buf).collect()
+}
+
 pub fn read(dir: &Path) -> Result<Manifest> {
-    let path = dir.join(MANIFEST_DIR).join(MANIFEST_FILE);
+    let path = path(dir);
     let content = std::fs::read_to_string(&path)
         .context("Not a baro product (no .baro/manifest.json found)")?;
     let manifest: Manifest = serde_json::from_str(&content)?;
@@ -21,3 +35,84 @@ pub fn write(dir: &Path, manifest: &Manifest) -> Result<()> {
     std::fs::write(&path, serde_json::to_string_pretty(manifest)?)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn sample() -> Manifest {
+        Manifest {
+            origin: None,
+            cloned_at: None,
+            file_hash: None,
+            origin_version: None,
+            origin_release_id: None,
+            remotes: Vec::new(),
+            ignored_versions: Vec::new(),
+            pinned_version: None,
+            slug: Some("demo".to_string()),
+            product_id: None,
+            publisher: None,
+            setup: None,
+            dependencies: Vec::new(),
+            version: "0.0.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn find_root_from_nested_subdirectory() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), &sample()).unwrap();
+        let nested = dir.path().join("src/deep");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find_root(&nested), Some(dir.path().to_path_buf()));
+        assert_eq!(find_root(dir.path()), Some(dir.path().to_path_buf()));
+    }
+
+    #[test]
+    fn find_all_roots_lists_nested_products_nearest_first() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), &sample()).unwrap();
+        let inner = dir.path().join("vendor/inner");
+        write(&inner, &sample()).unwrap();
+        let nested = inner.join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find_all_roots(&nested), vec![inner.clone(), dir.path().to_path_buf()]);
+        assert_eq!(find_root(&nested), Some(inner));
+    }
+
+    #[test]
+    fn named_upstreams() {
+        let mut m = sample();
+        m.slug = None;
+        assert!(m.upstream(None).is_err());
+        m.origin = Some("alice/demo".to_string());
+        m.origin_version = Some("1.0.0".to_string());
+        m.remotes.push(crate::types::Remote {
+            name: "remake".to_string(),
+            origin: "bob/demo-plus".to_string(),
+            version: "2.0.0".to_string(),
+            release_id: None,
+            file_hash: None,
+            ignored_versions: Vec::new(),
+            pinned_version: None,
+        });
+        assert_eq!(m.upstream(None).unwrap().origin, "alice/demo");
+        assert_eq!(m.upstream(Some("origin")).unwrap().version, "1.0.0");
+        assert_eq!(m.upstream(Some("remake")).unwrap().origin, "bob/demo-plus");
+        assert!(m.upstream(Some("other")).unwrap_err().to_string().contains("known: origin, remake"));
+
+        m.advance("remake", "2.1.0", None, None);
+        m.advance("origin", "1.1.0", Some("r2".to_string()), None);
+        assert_eq!((m.remotes[0].version.as_str(), m.version.as_str(), m.forked_version()), ("2.1.0", "1.1.0", "1.1.0"));
+    }
+
+    #[test]
+    fn find_root_none_without_manifest() {
+        let dir = tempdir().unwrap();
+        assert_eq!(find_root(dir.path()), None);
+    }
+}
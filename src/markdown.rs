@@ -0,0 +1,172 @@
+/// How to draw rendered Markdown.
+#[derive(Debug, Clone, Copy)]
+pub struct Style {
+    /// ANSI bold/italic/colour (a terminal without NO_COLOR)
+    pub ansi: bool,
+    /// Plain ASCII instead of bullets, rules and quote bars (accessible mode)
+    pub plain_symbols: bool,
+}
+
+const BOLD: &str = "\x1b[1m";
+const ITALIC: &str = "\x1b[3m";
+const UNDERLINE: &str = "\x1b[4m";
+const DIM: &str = "\x1b[2m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+fn paint(code: &str, text: &str, style: Style) -> String {
+    if style.ansi {
+        format!("{}{}{}", code, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Render Markdown for the terminal: headings, lists, quotes, code blocks and
+/// rules line by line, plus inline emphasis, code spans and links. Anything
+/// else (tables, HTML) passes through as written. Control characters other
+/// than newlines and tabs are dropped, so a README can't send the terminal
+/// escape sequences of its own.
+pub fn render(markdown: &str, style: Style) -> String {
+    let markdown: String = markdown.chars().filter(|&c| !c.is_control() || c == '\n' || c == '\t').collect();
+    let mut out: Vec<String> = Vec::new();
+    let mut in_code = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            out.push(format!("    {}", paint(DIM, line, style)));
+            continue;
+        }
+        let indent = &line[..line.len() - trimmed.len()];
+        if let Some((level, text)) = heading(trimmed) {
+            let text = inline(text, Style { ansi: false, ..style });
+            let code = if level == 1 { "\x1b[1;4m" } else { BOLD };
+            out.push(paint(code, &text, style));
+            if level <= 2 && !style.ansi && !style.plain_symbols {
+                out.push((if level == 1 { "=" } else { "-" }).repeat(text.chars().count()));
+            }
+        } else if is_rule(trimmed) {
+            out.push(if style.plain_symbols { String::new() } else { "─".repeat(40) });
+        } else if let Some(item) = ["- ", "* ", "+ "].iter().find_map(|m| trimmed.strip_prefix(m)) {
+            let bullet = if style.plain_symbols { "-" } else { "•" };
+            out.push(format!("{}{} {}", indent, bullet, inline(item, style)));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let bar = if style.plain_symbols { " " } else { "│" };
+            out.push(format!("{}{} {}", indent, bar, inline(quote.trim_start(), style)));
+        } else {
+            out.push(inline(line, style));
+        }
+    }
+    out.join("\n")
+}
+
+/// `## Title` → (2, "Title")
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then(|| (level, text.trim().trim_end_matches('#').trim_end()))
+}
+
+fn is_rule(line: &str) -> bool {
+    let line = line.trim_end();
+    line.len() >= 3
+        && ['-', '*', '_'].iter().any(|&c| line.chars().all(|x| x == c || x == ' ') && line.starts_with(c))
+}
+
+/// `[label](url)` split into (label, url, rest after the closing paren).
+fn link_parts(after_bracket: &str) -> Option<(&str, &str, &str)> {
+    let close = after_bracket.find("](")?;
+    let target = &after_bracket[close + 2..];
+    let end = target.find(')')?;
+    Some((&after_bracket[..close], &target[..end], &target[end + 1..]))
+}
+
+fn inline(text: &str, style: Style) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    'scan: while let Some(c) = rest.chars().next() {
+        if let Some((alt, _, after)) = rest.strip_prefix("![").and_then(link_parts) {
+            out.push_str(&format!("[image: {}]", alt));
+            rest = after;
+            continue;
+        }
+        if let Some((label, url, after)) = rest.strip_prefix('[').and_then(link_parts) {
+            let label = inline(label, style);
+            if label == url {
+                out.push_str(&paint(UNDERLINE, url, style));
+            } else {
+                out.push_str(&format!("{} ({})", paint(UNDERLINE, &label, style), url));
+            }
+            rest = after;
+            continue;
+        }
+        if let Some(code) = rest.strip_prefix('`') {
+            if let Some(end) = code.find('`') {
+                let span = &code[..end];
+                out.push_str(&if style.ansi { paint(CYAN, span, style) } else { format!("`{}`", span) });
+                rest = &code[end + 1..];
+                continue;
+            }
+        }
+        for (delim, code) in [("**", BOLD), ("__", BOLD), ("*", ITALIC)] {
+            if let Some(inner) = rest.strip_prefix(delim) {
+                if let Some(end) = inner.find(delim).filter(|&e| e > 0 && !inner.starts_with(' ')) {
+                    out.push_str(&paint(code, &inline(&inner[..end], style), style));
+                    rest = &inner[end + delim.len()..];
+                    continue 'scan;
+                }
+            }
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAIN: Style = Style { ansi: false, plain_symbols: false };
+
+    #[test]
+    fn drops_control_characters() {
+        let md = "# Hi\x1b]0;title\x07\n\x1b[2Jtext\u{9b}31m\tend\r\n```\n\x1b[31mcode\n```";
+        let ansi = Style { ansi: true, plain_symbols: false };
+        for style in [PLAIN, ansi] {
+            let out = render(md, style);
+            assert!(!out.contains("\x1b[2J") && !out.contains("\x1b[31m") && !out.contains("\x1b]"), "{:?}", out);
+            assert!(!out.contains('\u{9b}') && !out.contains('\x07'));
+        }
+        assert_eq!(render(md, PLAIN), "Hi]0;title\n==========\n[2Jtext31m\tend\n    [31mcode");
+    }
+
+    #[test]
+    fn renders_blocks_plainly() {
+        let md = "# Demo\n\nIntro with **bold** and `code`.\n\n## Install\n- one\n  * two\n> note\n\n```sh\nbaro fork a/b\n```\n---";
+        let expected = "Demo\n====\n\nIntro with bold and `code`.\n\nInstall\n-------\n• one\n  • two\n│ note\n\n    baro fork a/b\n────────────────────────────────────────";
+        assert_eq!(render(md, PLAIN), expected);
+
+        let accessible = Style { ansi: false, plain_symbols: true };
+        assert_eq!(render("# Demo\n- one\n***", accessible), "Demo\n- one\n");
+    }
+
+    #[test]
+    fn renders_links_and_images() {
+        assert_eq!(inline("See [docs](https://x.dev) and ![logo](l.png).", PLAIN), "See docs (https://x.dev) and [image: logo].");
+        assert_eq!(inline("[https://x.dev](https://x.dev)", PLAIN), "https://x.dev");
+        // Unbalanced markers and snake_case survive
+        assert_eq!(inline("a * b, [x], my_var_name, 2*3", PLAIN), "a * b, [x], my_var_name, 2*3");
+    }
+
+    #[test]
+    fn ansi_styles() {
+        let ansi = Style { ansi: true, plain_symbols: false };
+        assert_eq!(render("## Hi", ansi), "\x1b[1mHi\x1b[0m");
+        assert_eq!(inline("*it* `c`", ansi), "\x1b[3mit\x1b[0m \x1b[36mc\x1b[0m");
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::diff::{self, Edit};
+use crate::packaging;
+
+const MARK_MINE: &str = "<<<<<<< local";
+const MARK_SPLIT: &str = "=======";
+const MARK_THEIRS: &str = ">>>>>>> upstream";
+
+/// Result of merging one text file.
+#[derive(Debug, PartialEq)]
+pub struct TextMerge {
+    pub text: String,
+    /// Regions written with conflict markers
+    pub conflicts: usize,
+}
+
+/// For every line of `base`, the index of the same line in the other side, if kept.
+fn matches(edits: &[Edit], base_len: usize) -> Vec<Option<usize>> {
+    let mut map = vec![None; base_len];
+    let (mut b, mut s) = (0, 0);
+    for e in edits {
+        match e {
+            Edit::Same(_) => {
+                map[b] = Some(s);
+                b += 1;
+                s += 1;
+            }
+            Edit::Removed(_) => b += 1,
+            Edit::Added(_) => s += 1,
+        }
+    }
+    map
+}
+
+/// "\r\n" when `text` uses CRLF line endings, otherwise "\n".
+fn line_ending(text: &str) -> &'static str {
+    if text.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// Three-way merge of `mine` and `theirs`, both changed from `base` (diff3).
+/// Lines kept by both sides anchor the merge; between anchors a change on one
+/// side wins, identical changes merge, and different changes conflict. None
+/// when a file is too large to diff.
+pub fn merge_text(base: &str, mine: &str, theirs: &str) -> Option<TextMerge> {
+    let base_lines: Vec<&str> = base.lines().collect();
+    let mine_lines: Vec<&str> = mine.lines().collect();
+    let theirs_lines: Vec<&str> = theirs.lines().collect();
+    let to_mine = matches(&diff::line_diff(base, mine)?, base_lines.len());
+    let to_theirs = matches(&diff::line_diff(base, theirs)?, base_lines.len());
+
+    let mut out: Vec<&str> = Vec::new();
+    let mut conflicts = 0;
+    let (mut b, mut m, mut t) = (0, 0, 0);
+    loop {
+        // Next base line both sides kept, or the ends of all three
+        let anchor = (b..base_lines.len()).find_map(|i| match (to_mine[i], to_theirs[i]) {
+            (Some(mi), Some(ti)) => Some((i, mi, ti)),
+            _ => None,
+        });
+        let (ab, am, at) = anchor.unwrap_or((base_lines.len(), mine_lines.len(), theirs_lines.len()));
+
+        let (o, a, c) = (&base_lines[b..ab], &mine_lines[m..am], &theirs_lines[t..at]);
+        if a == o || a == c {
+            out.extend_from_slice(c);
+        } else if c == o {
+            out.extend_from_slice(a);
+        } else {
+            conflicts += 1;
+            out.push(MARK_MINE);
+            out.extend_from_slice(a);
+            out.push(MARK_SPLIT);
+            out.extend_from_slice(c);
+            out.push(MARK_THEIRS);
+        }
+
+        if anchor.is_none() {
+            break;
+        }
+        out.push(base_lines[ab]);
+        (b, m, t) = (ab + 1, am + 1, at + 1);
+    }
+
+    // `lines()` drops the terminators: write the local file's back (upstream's
+    // when the local one is a single line)
+    let eol = if mine.contains('\n') { line_ending(mine) } else { line_ending(theirs) };
+    let mut text = out.join(eol);
+    // Keep a final newline if either side has one
+    if !text.is_empty() && (mine.ends_with('\n') || theirs.ends_with('\n')) {
+        text.push_str(eol);
+    }
+    Some(TextMerge { text, conflicts })
+}
+
+/// What merging upstream does to one path of the local tree.
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    /// Take upstream's new contents (changed or added only upstream)
+    Update(Vec<u8>),
+    /// Upstream deleted a file left untouched locally
+    Delete,
+    /// Both sides changed the text and the changes merged cleanly
+    Merged(Vec<u8>),
+    /// Both sides changed it; written with conflict markers
+    Conflict(Vec<u8>, usize),
+    /// Both sides changed a binary or oversized file, or one side deleted it
+    /// while the other changed it: the local version is kept and upstream's
+    /// (if any) saved as `<path>.upstream`
+    Unmergeable(Option<Vec<u8>>),
+}
+
+/// Merge plan for bringing `theirs` (the new upstream release) into `mine`
+/// (the local tree), both descended from `base` (the forked release). Paths
+/// that need no change are left out.
+pub fn plan(
+    base: &BTreeMap<String, Vec<u8>>,
+    mine: &BTreeMap<String, Vec<u8>>,
+    theirs: &BTreeMap<String, Vec<u8>>,
+) -> BTreeMap<String, Action> {
+    let paths: BTreeSet<&String> = base.keys().chain(mine.keys()).chain(theirs.keys()).collect();
+    let mut actions = BTreeMap::new();
+    for path in paths {
+        let (o, a, c) = (base.get(path), mine.get(path), theirs.get(path));
+        // Unchanged upstream, or both sides made the same change
+        if c == o || a == c {
+            continue;
+        }
+        let action = match (a, c) {
+            _ if a == o => match c {
+                Some(c) => Action::Update(c.clone()),
+                None => Action::Delete,
+            },
+            (Some(a), Some(c)) => {
+                let empty: &[u8] = &[];
+                let texts = (diff::as_text(o.map_or(empty, |o| o)), diff::as_text(a), diff::as_text(c));
+                match texts {
+                    (Some(o), Some(a), Some(c)) => match merge_text(o, a, c) {
+                        Some(m) if m.conflicts == 0 => Action::Merged(m.text.into_bytes()),
+                        Some(m) => Action::Conflict(m.text.into_bytes(), m.conflicts),
+                        None => Action::Unmergeable(Some(c.as_bytes().to_vec())),
+                    },
+                    _ => Action::Unmergeable(Some(c.clone())),
+                }
+            }
+            (_, c) => Action::Unmergeable(c.cloned()),
+        };
+        actions.insert(path.clone(), action);
+    }
+    actions
+}
+
+/// Merge plan when the base release can't be downloaded (it was re-released),
+/// from the file hashes recorded at fork time. Untouched files take upstream's
+/// version; files changed on both sides can't be merged line by line.
+pub fn plan_from_hashes(
+    base: &BTreeMap<String, String>,
+    mine: &BTreeMap<String, Vec<u8>>,
+    theirs: &BTreeMap<String, Vec<u8>>,
+) -> BTreeMap<String, Action> {
+    let hash = |c: Option<&Vec<u8>>| c.map(|c| packaging::sha256_hex(c));
+    let paths: BTreeSet<&String> = base.keys().chain(mine.keys()).chain(theirs.keys()).collect();
+    let mut actions = BTreeMap::new();
+    for path in paths {
+        let (o, a, c) = (base.get(path), mine.get(path), theirs.get(path));
+        if a == c || hash(c).as_ref() == o {
+            continue;
+        }
+        let action = if hash(a).as_ref() == o {
+            match c {
+                Some(c) => Action::Update(c.clone()),
+                None => Action::Delete,
+            }
+        } else {
+            Action::Unmergeable(c.cloned())
+        };
+        actions.insert(path.clone(), action);
+    }
+    actions
+}
+
+/// Updates that would write over a file `mine` doesn't include but which is on
+/// disk all the same (gitignored, or excluded from packaging) become
+/// `Unmergeable`, so the local file is kept and upstream's copy saved beside
+/// it. Returns the paths changed.
+pub fn protect_untracked(
+    actions: &mut BTreeMap<String, Action>,
+    mine: &BTreeMap<String, Vec<u8>>,
+    exists: impl Fn(&str) -> bool,
+) -> BTreeSet<String> {
+    let mut protected = BTreeSet::new();
+    for (path, action) in actions.iter_mut() {
+        if matches!(action, Action::Update(_)) && !mine.contains_key(path) && exists(path) {
+            if let Action::Update(c) = std::mem::replace(action, Action::Unmergeable(None)) {
+                *action = Action::Unmergeable(Some(c));
+            }
+            protected.insert(path.clone());
+        }
+    }
+    protected
+}
+
+/// Applying one local change to the new upstream tree.
+#[derive(Debug, PartialEq)]
+pub enum Reapply {
+    Write(Vec<u8>),
+    Remove,
+    /// Couldn't be applied: why, and the local change as a unified diff (text files)
+    Reject(String, Option<String>),
+}
+
+/// Replay the local changes (`base` → `mine`) onto `theirs`, a newer upstream
+/// tree. Unlike `plan`, the result is relative to `theirs`: paths changed only
+/// upstream are already right there and don't appear.
+pub fn reapply(
+    base: &BTreeMap<String, Vec<u8>>,
+    mine: &BTreeMap<String, Vec<u8>>,
+    theirs: &BTreeMap<String, Vec<u8>>,
+) -> BTreeMap<String, Reapply> {
+    let changes = diff::compare_trees(base, mine);
+    let local_patch = |path: &str| -> Option<String> {
+        let empty = Vec::new();
+        let old = diff::as_text(base.get(path).unwrap_or(&empty))?;
+        let new = diff::as_text(mine.get(path).unwrap_or(&empty))?;
+        Some(diff::unified(&diff::line_diff(old, new)?, &format!("a/{}", path), &format!("b/{}", path), 3))
+    };
+    let reject = |path: &str, why: &str| Reapply::Reject(why.to_string(), local_patch(path));
+
+    let mut out = BTreeMap::new();
+    for path in changes.modified.iter().chain(&changes.added) {
+        let (o, a, c) = (base.get(path), &mine[path], theirs.get(path));
+        let result = match c {
+            Some(c) if c == a => continue,
+            None if o.is_none() => Reapply::Write(a.clone()),
+            None => reject(path, "deleted upstream"),
+            Some(c) if Some(c) == o => Reapply::Write(a.clone()),
+            Some(_) if o.is_none() => reject(path, "also added upstream"),
+            Some(c) => {
+                let o = o.map_or(&[][..], |o| o);
+                match (diff::as_text(o), diff::as_text(a), diff::as_text(c)) {
+                    (Some(o), Some(a), Some(c)) => match merge_text(o, a, c) {
+                        Some(m) if m.conflicts == 0 => Reapply::Write(m.text.into_bytes()),
+                        Some(m) => reject(path, &format!("{} hunk(s) conflict with upstream changes", m.conflicts)),
+                        None => reject(path, "too large to merge"),
+                    },
+                    _ => reject(path, "binary file changed upstream"),
+                }
+            }
+        };
+        out.insert(path.clone(), result);
+    }
+    for path in &changes.removed {
+        let result = match theirs.get(path) {
+            None => continue,
+            Some(c) if Some(c) == base.get(path) => Reapply::Remove,
+            Some(_) => reject(path, "changed upstream"),
+        };
+        out.insert(path.clone(), result);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(files: &[(&str, &str)]) -> BTreeMap<String, Vec<u8>> {
+        files.iter().map(|(p, c)| (p.to_string(), c.as_bytes().to_vec())).collect()
+    }
+
+    #[test]
+    fn merges_changes_to_different_lines() {
+        let base = "a\nb\nc\nd\ne\n";
+        let mine = "a\nB\nc\nd\ne\n";
+        let theirs = "a\nb\nc\nd\nE\nf\n";
+        let m = merge_text(base, mine, theirs).unwrap();
+        assert_eq!(m, TextMerge { text: "a\nB\nc\nd\nE\nf\n".to_string(), conflicts: 0 });
+    }
+
+    #[test]
+    fn conflicting_changes_get_markers() {
+        let m = merge_text("a\nb\nc\n", "a\nmine\nc\n", "a\ntheirs\nc\n").unwrap();
+        assert_eq!(m.conflicts, 1);
+        assert_eq!(m.text, "a\n<<<<<<< local\nmine\n=======\ntheirs\n>>>>>>> upstream\nc\n");
+        // The same change on both sides is not a conflict
+        assert_eq!(merge_text("a\n", "b\n", "b\n").unwrap().conflicts, 0);
+    }
+
+    #[test]
+    fn keeps_crlf_line_endings() {
+        let m = merge_text("a\r\nb\r\nc\r\n", "A\r\nb\r\nc\r\n", "a\r\nb\r\nC\r\n").unwrap();
+        assert_eq!(m, TextMerge { text: "A\r\nb\r\nC\r\n".to_string(), conflicts: 0 });
+        let m = merge_text("a\r\n", "mine\r\n", "theirs\r\n").unwrap();
+        assert_eq!(m.text, "<<<<<<< local\r\nmine\r\n=======\r\ntheirs\r\n>>>>>>> upstream\r\n");
+    }
+
+    #[test]
+    fn plans_tree_merge() {
+        let base = tree(&[("keep.txt", "k"), ("up.txt", "1"), ("gone.txt", "x"), ("edited.txt", "x"), ("both.txt", "a\nb\nc\n")]);
+        let mine = tree(&[("keep.txt", "k"), ("up.txt", "1"), ("gone.txt", "x"), ("edited.txt", "local"), ("both.txt", "A\nb\nc\n"), ("new.txt", "n")]);
+        let theirs = tree(&[("keep.txt", "k"), ("up.txt", "2"), ("both.txt", "a\nb\nC\n"), ("added.txt", "+")]);
+        let actions = plan(&base, &mine, &theirs);
+        assert_eq!(actions["up.txt"], Action::Update(b"2".to_vec()));
+        assert_eq!(actions["added.txt"], Action::Update(b"+".to_vec()));
+        assert_eq!(actions["gone.txt"], Action::Delete);
+        assert_eq!(actions["edited.txt"], Action::Unmergeable(None));
+        assert_eq!(actions["both.txt"], Action::Merged(b"A\nb\nC\n".to_vec()));
+        assert!(!actions.contains_key("keep.txt") && !actions.contains_key("new.txt"));
+
+        let hashed = plan_from_hashes(&crate::baseline::hashes(&base), &mine, &theirs);
+        assert_eq!(hashed["up.txt"], Action::Update(b"2".to_vec()));
+        assert_eq!(hashed["gone.txt"], Action::Delete);
+        assert_eq!(hashed["both.txt"], Action::Unmergeable(Some(b"a\nb\nC\n".to_vec())));
+        assert_eq!(hashed.len(), actions.len());
+
+        // added.txt exists locally but is gitignored, so `mine` lacks it
+        let mut actions = actions;
+        let protected = protect_untracked(&mut actions, &mine, |p| p == "added.txt" || p == "up.txt");
+        assert_eq!(protected.into_iter().collect::<Vec<_>>(), vec!["added.txt"]);
+        assert_eq!(actions["added.txt"], Action::Unmergeable(Some(b"+".to_vec())));
+        assert_eq!(actions["up.txt"], Action::Update(b"2".to_vec()));
+    }
+
+    #[test]
+    fn reapplies_local_changes() {
+        let base = tree(&[("edited.txt", "a\nb\nc\n"), ("conflict.txt", "x\n"), ("removed.txt", "r"), ("upstream.txt", "1")]);
+        let mine = tree(&[("edited.txt", "A\nb\nc\n"), ("conflict.txt", "mine\n"), ("upstream.txt", "1"), ("new.txt", "n")]);
+        let theirs = tree(&[("edited.txt", "a\nb\nC\n"), ("conflict.txt", "theirs\n"), ("removed.txt", "r"), ("upstream.txt", "2")]);
+        let out = reapply(&base, &mine, &theirs);
+        assert_eq!(out["edited.txt"], Reapply::Write(b"A\nb\nC\n".to_vec()));
+        assert_eq!(out["new.txt"], Reapply::Write(b"n".to_vec()));
+        assert_eq!(out["removed.txt"], Reapply::Remove);
+        match &out["conflict.txt"] {
+            Reapply::Reject(_, Some(patch)) => assert!(patch.contains("-x\n+mine\n")),
+            other => panic!("expected a reject, got {:?}", other),
+        }
+        assert!(!out.contains_key("upstream.txt"));
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::api;
+use crate::config;
+use crate::manifest;
+use crate::ui;
+use crate::upstream_cache;
+use crate::utils;
+
+/// Subscribed origins are re-checked at most this often.
+const CHECK_INTERVAL_SECS: i64 = 6 * 3600;
+
+/// A fork that wants to hear about new upstream releases.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Subscription {
+    pub dir: PathBuf,
+    pub origin: String,
+    /// Newest upstream version already announced, so each is shown once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notified_version: Option<String>,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct State {
+    #[serde(default)]
+    forks: Vec<Subscription>,
+    #[serde(default)]
+    checked_at: i64,
+}
+
+fn state_path() -> Option<PathBuf> {
+    config::config_dir().ok().map(|d| d.join("notify.json"))
+}
+
+fn load() -> State {
+    state_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save(state: &State) -> Result<()> {
+    let path = state_path().ok_or_else(|| anyhow::anyhow!("No config directory"))?;
+    utils::write_atomic(&path, serde_json::to_string_pretty(state)?.as_bytes())?;
+    Ok(())
+}
+
+/// Subscribe the fork in `dir` to updates from `origin`. False if it already was.
+pub fn enable(dir: &Path, origin: &str) -> Result<bool> {
+    let mut state = load();
+    if let Some(existing) = state.forks.iter_mut().find(|s| s.dir == dir) {
+        let changed = existing.origin != origin;
+        existing.origin = origin.to_string();
+        save(&state)?;
+        return Ok(changed);
+    }
+    state.forks.push(Subscription { dir: dir.to_path_buf(), origin: origin.to_string(), notified_version: None });
+    // Check the new origin on the next run
+    state.checked_at = 0;
+    save(&state)?;
+    Ok(true)
+}
+
+/// Unsubscribe `dir`. False if it wasn't subscribed.
+pub fn disable(dir: &Path) -> Result<bool> {
+    let mut state = load();
+    let before = state.forks.len();
+    state.forks.retain(|s| s.dir != dir);
+    if state.forks.len() == before {
+        return Ok(false);
+    }
+    save(&state)?;
+    Ok(true)
+}
+
+pub fn subscriptions() -> Vec<Subscription> {
+    load().forks
+}
+
+/// Refresh the upstream cache for every subscribed origin in the background,
+/// if the last refresh is older than the interval. Like the update check, the
+/// caller waits only briefly; an unfinished refresh is retried next run.
+pub fn spawn_refresh_if_due() -> Option<tokio::task::JoinHandle<()>> {
+    let state = load();
+    let now = chrono::Utc::now().timestamp();
+    if state.forks.is_empty() || now.saturating_sub(state.checked_at) < CHECK_INTERVAL_SECS {
+        return None;
+    }
+    let mut origins: Vec<String> = state.forks.into_iter().map(|s| s.origin).collect();
+    origins.sort();
+    origins.dedup();
+    Some(tokio::spawn(async move {
+        let client = api::BaroClient::anonymous();
+        for origin in origins {
+            let Some((username, slug)) = origin.split_once('/') else { continue };
+            let Ok(releases) = client.list_releases(username, slug).await else { continue };
+            let latest = releases
+                .releases
+                .iter()
+                .filter(|r| !r.is_yanked())
+                .max_by(|a, b| utils::compare_versions(&a.version, &b.version));
+            if let Some(latest) = latest {
+                upstream_cache::record(&origin, &latest.version);
+            }
+        }
+        // Reload: announcements may have been recorded meanwhile
+        let mut state = load();
+        state.checked_at = now;
+        let _ = save(&state);
+    }))
+}
+
+/// Subscriptions with a newer upstream than their forked version that hasn't
+/// been announced yet, as (subscription index, forked, latest). `lookup` gives
+/// a fork's (forked version, latest upstream version).
+fn unannounced(forks: &[Subscription], lookup: impl Fn(&Subscription) -> Option<(String, String)>) -> Vec<(usize, String, String)> {
+    forks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, sub)| {
+            let (forked, latest) = lookup(sub)?;
+            let newer = utils::compare_versions(&latest, &forked).is_gt();
+            (newer && sub.notified_version.as_deref() != Some(latest.as_str())).then_some((i, forked, latest))
+        })
+        .collect()
+}
+
+/// The notice for newly seen upstream releases, from cached data only (no
+/// network), marking them announced.
+pub fn pending_notice() -> Option<String> {
+    let mut state = load();
+    let found = unannounced(&state.forks, |sub| {
+        let up = manifest::read(&sub.dir).ok()?.upstream(None).ok()?;
+        let latest = upstream_cache::get(&sub.origin)?.latest_version;
+        // Only the newest release is cached: stay quiet if it is ignored, and
+        // offer at most the pinned version
+        if up.ignored_versions.iter().any(|v| utils::compare_versions(v, &latest).is_eq()) {
+            return None;
+        }
+        let latest = match up.pinned_version {
+            Some(pin) if utils::compare_versions(&latest, &pin).is_gt() => pin,
+            _ => latest,
+        };
+        Some((up.version, latest))
+    });
+    if found.is_empty() {
+        return None;
+    }
+    let mut lines = vec![String::new(), "New upstream releases for your forks:".to_string()];
+    for (i, forked, latest) in found {
+        let sub = &mut state.forks[i];
+        lines.push(format!("  {} ({}): {} {} {}", sub.dir.display(), sub.origin, forked, ui::arrow(), latest));
+        sub.notified_version = Some(latest);
+    }
+    lines.push("  Run `baro upstream` in a project for the changelog.".to_string());
+    save(&state).ok()?;
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sub(dir: &str, notified: Option<&str>) -> Subscription {
+        Subscription { dir: PathBuf::from(dir), origin: format!("alice/{}", dir), notified_version: notified.map(String::from) }
+    }
+
+    #[test]
+    fn announces_each_new_version_once() {
+        let forks = vec![sub("behind", None), sub("announced", Some("2.0.0")), sub("current", None), sub("missing", None)];
+        let found = unannounced(&forks, |s| match s.dir.to_str()? {
+            "behind" | "announced" => Some(("1.0.0".to_string(), "2.0.0".to_string())),
+            "current" => Some(("2.0.0".to_string(), "2.0.0".to_string())),
+            _ => None,
+        });
+        assert_eq!(found, vec![(0, "1.0.0".to_string(), "2.0.0".to_string())]);
+
+        // A later release is announced even after an earlier one was
+        let found = unannounced(&forks[1..2], |_| Some(("1.0.0".to_string(), "2.1.0".to_string())));
+        assert_eq!(found.len(), 1);
+    }
+}
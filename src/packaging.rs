@@ -4,36 +4,255 @@ use flate2::write::GzEncoder;
 use flate2::Compression;
 use ignore::WalkBuilder;
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::path::Path;
 use tar::{Archive, Builder};
 
+use crate::utils;
+
 const EXCLUDED_DIRS: &[&str] = &[".git", ".baro", "target", "node_modules", ".next"];
 
-/// Create a tar.gz archive from a directory, respecting .gitignore.
-/// Returns (bytes, sha256_hex).
-pub fn create_archive(dir: &Path) -> Result<(Vec<u8>, String)> {
-    let buf = Vec::new();
-    let encoder = GzEncoder::new(buf, Compression::default());
-    let mut builder = Builder::new(encoder);
+/// Tool state and credential stores, never packaged at any depth.
+const SENSITIVE_DIRS: &[&str] = &[".aws", ".ssh", ".gnupg", ".kube", ".docker"];
+
+/// Credential files, never packaged at any depth.
+const SENSITIVE_FILES: &[&str] = &[
+    "credentials.json",
+    "service-account.json",
+    "id_rsa",
+    "id_ed25519",
+    ".netrc",
+    ".pypirc",
+];
+
+fn is_excluded_dir_name(name: &str) -> bool {
+    EXCLUDED_DIRS.contains(&name) || SENSITIVE_DIRS.contains(&name)
+}
+
+fn is_excluded_file_name(name: &str) -> bool {
+    name.starts_with(".env") || SENSITIVE_FILES.contains(&name)
+}
+
+/// A subdirectory with its own `.baro/manifest.json` is a separate product.
+fn is_nested_product(dir: &Path, root: &Path) -> bool {
+    dir != root && dir.join(".baro").join("manifest.json").is_file()
+}
 
-    let walker = WalkBuilder::new(dir)
+/// Nested products under `dir` that `create_archive` leaves out, as relative paths.
+pub fn find_nested_products(dir: &Path) -> Vec<std::path::PathBuf> {
+    let root = dir.to_path_buf();
+    WalkBuilder::new(dir)
         .hidden(false)
         .git_ignore(true)
         .git_global(false)
         .git_exclude(true)
         .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !is_excluded_dir_name(&name)
+        })
+        .build()
+        .flatten()
+        .filter(|e| e.file_type().is_some_and(|ft| ft.is_dir()))
+        .filter(|e| is_nested_product(e.path(), &root))
+        .filter_map(|e| e.path().strip_prefix(dir).ok().map(|p| p.to_path_buf()))
+        .collect()
+}
+
+/// Products at or below `dir`, outermost only: a fork's vendored
+/// dependencies belong to it. Paths are relative to `dir` ("" for `dir` itself).
+pub fn find_products(dir: &Path) -> Vec<std::path::PathBuf> {
+    if dir.join(".baro").join("manifest.json").is_file() {
+        return vec![std::path::PathBuf::new()];
+    }
+    let mut found: Vec<std::path::PathBuf> = Vec::new();
+    // The walk visits a directory before anything inside it
+    for product in find_nested_products(dir) {
+        if !found.iter().any(|outer| product.starts_with(outer)) {
+            found.push(product);
+        }
+    }
+    found
+}
+
+/// Walk the files and directories that belong in a product archive: respects
+/// .gitignore and skips excluded/sensitive entries and nested products.
+fn walk(dir: &Path) -> ignore::Walk {
+    let root = dir.to_path_buf();
+    WalkBuilder::new(dir)
+        .hidden(false)
+        .git_ignore(true)
+        .git_global(false)
+        .git_exclude(true)
+        .filter_entry(move |entry| {
             let name = entry.file_name().to_string_lossy();
             // Exclude known dirs
-            if entry.file_type().map_or(false, |ft| ft.is_dir()) {
-                return !EXCLUDED_DIRS.contains(&name.as_ref());
-            }
-            // Exclude .env* files
-            if name.starts_with(".env") {
-                return false;
+            if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                return !is_excluded_dir_name(&name) && !is_nested_product(entry.path(), &root);
             }
-            true
+            // Exclude .env* files and credentials
+            !is_excluded_file_name(&name)
         })
-        .build();
+        .build()
+}
+
+/// Relative paths of the files `create_archive` would include, sorted.
+pub fn list_files(dir: &Path) -> Result<Vec<std::path::PathBuf>> {
+    let mut files = Vec::new();
+    for entry in walk(dir) {
+        let entry = entry?;
+        if entry.file_type().is_some_and(|ft| ft.is_file()) {
+            let relative = entry
+                .path()
+                .strip_prefix(dir)
+                .context("Failed to compute relative path")?;
+            files.push(relative.to_path_buf());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Relative paths of every file under `dir`, sorted, including the ones
+/// `list_files` leaves out (ignored, excluded and sensitive files).
+#[cfg(feature = "moderation")]
+pub fn list_all_files(dir: &Path) -> Result<Vec<std::path::PathBuf>> {
+    let mut files = Vec::new();
+    for entry in WalkBuilder::new(dir).standard_filters(false).build() {
+        let entry = entry?;
+        if entry.file_type().is_some_and(|ft| ft.is_file()) {
+            files.push(entry.path().strip_prefix(dir).context("Failed to compute relative path")?.to_path_buf());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Longest file or directory name most filesystems accept (NAME_MAX).
+pub const MAX_NAME_BYTES: usize = 255;
+/// Longest path macOS can open (PATH_MAX); Linux allows 4096.
+pub const MAX_PATH_BYTES: usize = 1024;
+/// Windows' default MAX_PATH; longer paths need long-path support enabled.
+pub const WINDOWS_MAX_PATH: usize = 260;
+
+/// Split archive paths into those no forker can extract (a name over
+/// `MAX_NAME_BYTES` or a path over `MAX_PATH_BYTES`) and those that only
+/// break on Windows defaults (over `WINDOWS_MAX_PATH` characters).
+pub fn long_paths(paths: &[std::path::PathBuf]) -> (Vec<String>, Vec<String>) {
+    let (mut unextractable, mut windows) = (Vec::new(), Vec::new());
+    for path in paths {
+        let name = path.to_string_lossy().replace('\\', "/");
+        if name.len() > MAX_PATH_BYTES || name.split('/').any(|c| c.len() > MAX_NAME_BYTES) {
+            unextractable.push(name);
+        } else if name.chars().count() > WINDOWS_MAX_PATH {
+            windows.push(name);
+        }
+    }
+    (unextractable, windows)
+}
+
+/// The `n` largest files `create_archive` would include, biggest first.
+pub fn largest_files(dir: &Path, n: usize) -> Vec<(std::path::PathBuf, u64)> {
+    let mut files: Vec<(std::path::PathBuf, u64)> = walk(dir)
+        .flatten()
+        .filter(|e| e.file_type().is_some_and(|ft| ft.is_file()))
+        .filter_map(|e| {
+            let size = e.metadata().ok()?.len();
+            Some((e.path().strip_prefix(dir).ok()?.to_path_buf(), size))
+        })
+        .collect();
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    files.truncate(n);
+    files
+}
+
+/// Validate `--asset` paths and name each asset after its file name.
+pub fn asset_names(paths: &[std::path::PathBuf]) -> Result<Vec<(String, std::path::PathBuf)>> {
+    let mut assets: Vec<(String, std::path::PathBuf)> = Vec::new();
+    for path in paths {
+        if !path.is_file() {
+            return Err(anyhow::anyhow!("Asset {} is not a file", path.display()));
+        }
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow::anyhow!("Asset {} has no file name", path.display()))?;
+        if assets.iter().any(|(n, _)| *n == name) {
+            return Err(anyhow::anyhow!("Two assets are named {}; rename one before publishing", name));
+        }
+        assets.push((name, path.clone()));
+    }
+    Ok(assets)
+}
+
+/// Trees with more archivable files than this and no manifest are probably not a product.
+const LARGE_TREE_FILES: usize = 5000;
+
+/// Why `dir` looks like the wrong directory to publish from (the home directory,
+/// the filesystem root or a top-level directory, or a huge tree that was never
+/// initialized), if it does.
+pub fn suspicious_root(dir: &Path, home: Option<&Path>) -> Option<String> {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    if home.and_then(|h| h.canonicalize().ok()).is_some_and(|h| h == dir) {
+        return Some(format!("{} is your home directory", dir.display()));
+    }
+    let depth = dir.components().filter(|c| matches!(c, std::path::Component::Normal(_))).count();
+    if depth <= 1 {
+        return Some(format!("{} is at the top of the filesystem", dir.display()));
+    }
+    if !dir.join(".baro").join("manifest.json").is_file() {
+        let files = walk(&dir)
+            .flatten()
+            .filter(|e| e.file_type().is_some_and(|ft| ft.is_file()))
+            .take(LARGE_TREE_FILES + 1)
+            .count();
+        if files > LARGE_TREE_FILES {
+            return Some(format!(
+                "{} has more than {} files and no .baro/manifest.json",
+                dir.display(), LARGE_TREE_FILES
+            ));
+        }
+    }
+    None
+}
+
+/// Portable archive entry name for a relative path: `/`-separated, with no
+/// drive prefix, root, `.` or `..` segments. Backslashes count as separators
+/// so archives built on Windows unpack the same everywhere.
+fn entry_name(relative: &Path) -> Result<String> {
+    let raw = relative.to_string_lossy().replace('\\', "/");
+    let mut segments: Vec<&str> = raw.split('/').filter(|s| !s.is_empty() && *s != ".").collect();
+    // Drive prefix such as `C:` (only possible from Windows-style input)
+    if let Some(first) = segments.first() {
+        if first.len() == 2 && first.ends_with(':') && first.as_bytes()[0].is_ascii_alphabetic() {
+            segments.remove(0);
+        }
+    }
+    if segments.contains(&"..") {
+        return Err(anyhow::anyhow!("Refusing to archive path outside the product: {}", relative.display()));
+    }
+    if segments.is_empty() {
+        return Err(anyhow::anyhow!("Empty archive path: {}", relative.display()));
+    }
+    Ok(segments.join("/"))
+}
+
+/// Create a tar.gz archive from a directory, respecting .gitignore.
+/// Returns (bytes, sha256_hex).
+pub fn create_archive(dir: &Path) -> Result<(Vec<u8>, String)> {
+    create_archive_with_extras(dir, &[])
+}
+
+/// Like `create_archive`, plus extra files added verbatim as
+/// (path inside the archive, source file) pairs, bypassing the exclusion rules.
+///
+/// Entries use GNU headers: names past ustar's 100-byte field are written as
+/// GNU long-name records, which GNU tar, bsdtar and `extract_archive` all read.
+pub fn create_archive_with_extras(dir: &Path, extras: &[(&str, &Path)]) -> Result<(Vec<u8>, String)> {
+    let buf = Vec::new();
+    let encoder = GzEncoder::new(buf, Compression::default());
+    let mut builder = Builder::new(encoder);
+
+    let walker = walk(dir);
 
     for entry in walker {
         let entry = entry?;
@@ -46,43 +265,260 @@ pub fn create_archive(dir: &Path) -> Result<(Vec<u8>, String)> {
         let relative = path
             .strip_prefix(dir)
             .context("Failed to compute relative path")?;
+        let name = entry_name(relative)?;
 
         if path.is_file() {
             builder
-                .append_path_with_name(path, relative)
-                .with_context(|| format!("Failed to add file: {}", relative.display()))?;
+                .append_path_with_name(path, &name)
+                .with_context(|| format!("Failed to add file: {}", name))?;
         } else if path.is_dir() {
             builder
-                .append_dir(relative, path)
-                .with_context(|| format!("Failed to add dir: {}", relative.display()))?;
+                .append_dir(&name, path)
+                .with_context(|| format!("Failed to add dir: {}", name))?;
         }
     }
 
+    for (name, source) in extras {
+        let name = entry_name(Path::new(name))?;
+        builder
+            .append_path_with_name(source, &name)
+            .with_context(|| format!("Failed to add file: {}", name))?;
+    }
+
     let encoder = builder.into_inner()?;
     let bytes = encoder.finish()?;
 
+    let hash = sha256_hex(&bytes);
+    Ok((bytes, hash))
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(&bytes);
-    let hash = format!("{:x}", hasher.finalize());
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
 
-    Ok((bytes, hash))
+/// Extract a tar.gz archive file into a destination directory without
+/// loading it into memory.
+pub fn extract_archive_file(archive: &Path, dest: &Path) -> Result<()> {
+    let file = std::fs::File::open(archive)
+        .with_context(|| format!("Failed to open {}", archive.display()))?;
+    extract_archive_from(std::io::BufReader::new(file), dest)
 }
 
-/// Extract a tar.gz archive into a destination directory.
-pub fn extract_archive(bytes: &[u8], dest: &Path) -> Result<()> {
+fn extract_archive_from<R: std::io::Read>(reader: R, dest: &Path) -> Result<()> {
     std::fs::create_dir_all(dest)?;
-    let decoder = GzDecoder::new(bytes);
+    let decoder = GzDecoder::new(reader);
     let mut archive = Archive::new(decoder);
     archive.unpack(dest)?;
     Ok(())
 }
 
+/// The README at the root of a tar.gz archive, if it has a non-empty one.
+pub fn read_archive_readme(reader: impl std::io::Read) -> Result<Option<String>> {
+    const NAMES: &[&str] = &["README.md", "readme.md", "Readme.md", "README", "README.txt"];
+    let mut archive = Archive::new(GzDecoder::new(reader));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().trim_start_matches("./").to_string();
+        if NAMES.contains(&name.as_str()) {
+            let mut content = String::new();
+            if std::io::Read::read_to_string(&mut entry, &mut content).is_ok() && !content.trim().is_empty() {
+                return Ok(Some(content));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// A regular file in a tar.gz archive, with its unpacked size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub size: u64,
+}
+
+/// The files in a tar.gz archive, without extracting anything.
+pub fn list_archive(reader: impl std::io::Read) -> Result<Vec<ArchiveEntry>> {
+    let mut archive = Archive::new(GzDecoder::new(reader));
+    let mut files = Vec::new();
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().trim_start_matches("./").to_string();
+        files.push(ArchiveEntry { path, size: entry.size() });
+    }
+    Ok(files)
+}
+
+/// Contents of every regular file in a tar.gz archive by `/`-separated path,
+/// leaving out baro's own files under `.baro/` (history bundle, setup).
+pub fn read_archive_files(reader: impl std::io::Read) -> Result<BTreeMap<String, Vec<u8>>> {
+    let mut archive = Archive::new(GzDecoder::new(reader));
+    let mut files = BTreeMap::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        // Callers may write these paths back to disk
+        if !entry.path()?.components().all(|c| matches!(c, std::path::Component::Normal(_) | std::path::Component::CurDir)) {
+            return Err(anyhow::anyhow!("Unsafe path in archive: {}", entry.path()?.display()));
+        }
+        let path = entry.path()?.to_string_lossy().trim_start_matches("./").replace('\\', "/");
+        if path.starts_with(".baro/") {
+            continue;
+        }
+        let mut content = Vec::new();
+        std::io::Read::read_to_end(&mut entry, &mut content)?;
+        files.insert(path, content);
+    }
+    Ok(files)
+}
+
+/// Contents of the files `create_archive` would include, keyed like `read_archive_files`.
+pub fn read_tree(dir: &Path) -> Result<BTreeMap<String, Vec<u8>>> {
+    let mut files = BTreeMap::new();
+    for relative in list_files(dir)? {
+        let content = std::fs::read(dir.join(&relative))
+            .with_context(|| format!("Failed to read {}", relative.display()))?;
+        files.insert(relative.to_string_lossy().replace('\\', "/"), content);
+    }
+    Ok(files)
+}
+
+/// Indented tree lines for `files`: directories first appear as `name/`,
+/// files as `name  (size)`, siblings in path order.
+pub fn render_tree(files: &[ArchiveEntry]) -> Vec<String> {
+    let mut sorted: Vec<&ArchiveEntry> = files.iter().collect();
+    sorted.sort_by(|a, b| a.path.split('/').cmp(b.path.split('/')));
+    let mut lines = Vec::new();
+    let mut open: Vec<&str> = Vec::new();
+    for file in sorted {
+        let parts: Vec<&str> = file.path.split('/').collect();
+        let (dirs, name) = parts.split_at(parts.len() - 1);
+        let shared = open.iter().zip(dirs).take_while(|(a, b)| a == b).count();
+        open.truncate(shared);
+        for dir in &dirs[shared..] {
+            lines.push(format!("{}{}/", "  ".repeat(open.len()), dir));
+            open.push(dir);
+        }
+        lines.push(format!("{}{}  ({})", "  ".repeat(open.len()), name[0], utils::format_bytes(file.size as i64)));
+    }
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::tempdir;
     use std::fs;
 
+    fn extract_archive(bytes: &[u8], dest: &Path) -> Result<()> {
+        extract_archive_from(bytes, dest)
+    }
+
+    fn entry_names(bytes: &[u8]) -> Vec<String> {
+        let mut archive = Archive::new(GzDecoder::new(bytes));
+        archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn long_paths_round_trip() {
+        let src = tempdir().unwrap();
+        let deep: std::path::PathBuf = (0..5).map(|i| format!("node_modules_{}_{}", i, "x".repeat(50))).collect();
+        fs::create_dir_all(src.path().join(&deep)).unwrap();
+        let file = deep.join(format!("{}.js", "long-file-name-".repeat(10)));
+        fs::write(src.path().join(&file), "deep").unwrap();
+        let name = file.to_string_lossy().replace('\\', "/");
+        assert!(name.len() > 255);
+
+        let (bytes, _) = create_archive(src.path()).unwrap();
+        assert!(entry_names(&bytes).contains(&name));
+        let dest = tempdir().unwrap();
+        extract_archive(&bytes, dest.path()).unwrap();
+        assert_eq!(fs::read_to_string(dest.path().join(&file)).unwrap(), "deep");
+    }
+
+    #[test]
+    fn extracts_pax_long_names() {
+        // As written by `tar --format=pax` or bsdtar: an 'x' record carrying the full path
+        let name = format!("{}/index.js", "a".repeat(200));
+        let body = format!(" path={}\n", name);
+        let mut len = body.len() + 1;
+        while format!("{}{}", len, body).len() != len {
+            len += 1;
+        }
+        let record = format!("{}{}", len, body);
+
+        let mut builder = Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        let mut pax = tar::Header::new_ustar();
+        pax.set_path("PaxHeader").unwrap();
+        pax.set_entry_type(tar::EntryType::XHeader);
+        pax.set_size(record.len() as u64);
+        pax.set_cksum();
+        builder.append(&pax, record.as_bytes()).unwrap();
+        let mut file = tar::Header::new_ustar();
+        file.set_path("truncated").unwrap();
+        file.set_size(4);
+        file.set_mode(0o644);
+        file.set_cksum();
+        builder.append(&file, &b"deep"[..]).unwrap();
+        let bytes = builder.into_inner().unwrap().finish().unwrap();
+
+        let dest = tempdir().unwrap();
+        extract_archive(&bytes, dest.path()).unwrap();
+        assert_eq!(fs::read_to_string(dest.path().join(&name)).unwrap(), "deep");
+    }
+
+    #[test]
+    fn long_paths_split_by_limit() {
+        let paths: Vec<std::path::PathBuf> = vec![
+            "src/main.rs".into(),
+            format!("{}/x.js", "d".repeat(256)).into(),
+            format!("{}/x.js", vec!["d".repeat(200); 6].join("/")).into(),
+            format!("{}/x.js", vec!["d".repeat(100); 3].join("/")).into(),
+        ];
+        let (unextractable, windows) = long_paths(&paths);
+        assert_eq!(unextractable.len(), 2);
+        assert_eq!(windows, vec![paths[3].to_string_lossy().to_string()]);
+    }
+
+    #[test]
+    fn entry_name_normalizes_windows_paths() {
+        assert_eq!(entry_name(Path::new("src\\main.rs")).unwrap(), "src/main.rs");
+        assert_eq!(entry_name(Path::new("C:\\proj\\a.txt")).unwrap(), "proj/a.txt");
+        assert_eq!(entry_name(Path::new("./docs//guide.md")).unwrap(), "docs/guide.md");
+        assert!(entry_name(Path::new("..\\secret")).is_err());
+        assert!(entry_name(Path::new("")).is_err());
+    }
+
+    #[test]
+    fn archive_entries_are_forward_slash_and_relative() {
+        let src = tempdir().unwrap();
+        fs::create_dir_all(src.path().join("a/b")).unwrap();
+        fs::write(src.path().join("a/b/c.txt"), "x").unwrap();
+        let extra = src.path().join("bundle.tmp");
+        fs::write(&extra, "history").unwrap();
+
+        let (bytes, _) = create_archive_with_extras(src.path(), &[(".baro\\history.bundle", extra.as_path())]).unwrap();
+        let names = entry_names(&bytes);
+        assert!(names.iter().all(|n| !n.contains('\\') && !n.starts_with('/') && !n.contains(':')));
+        assert!(names.contains(&"a/b/c.txt".to_string()));
+        assert!(names.contains(&".baro/history.bundle".to_string()));
+
+        let dest = tempdir().unwrap();
+        extract_archive(&bytes, dest.path()).unwrap();
+        assert_eq!(fs::read_to_string(dest.path().join("a").join("b").join("c.txt")).unwrap(), "x");
+        assert_eq!(fs::read_to_string(dest.path().join(".baro").join("history.bundle")).unwrap(), "history");
+    }
+
     #[test]
     fn create_and_extract_roundtrip() {
         let src = tempdir().unwrap();
@@ -136,6 +572,101 @@ mod tests {
         assert!(!dest.path().join(".env.local").exists());
     }
 
+    #[test]
+    fn excludes_metadata_and_credentials_at_any_depth() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("keep.txt"), "visible").unwrap();
+        let nested = src.path().join("vendor/tool");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("lib.rs"), "// ok").unwrap();
+        fs::write(nested.join("credentials.json"), "{}").unwrap();
+        fs::write(nested.join(".env.production"), "SECRET=z").unwrap();
+        for dir_name in &[".baro", ".aws", ".ssh"] {
+            let d = nested.join(dir_name);
+            fs::create_dir_all(&d).unwrap();
+            fs::write(d.join("state"), "hidden").unwrap();
+        }
+        fs::create_dir_all(src.path().join("home/.ssh")).unwrap();
+        fs::write(src.path().join("home/.ssh/id_ed25519"), "key").unwrap();
+
+        let (bytes, _) = create_archive(src.path()).unwrap();
+        let dest = tempdir().unwrap();
+        extract_archive(&bytes, dest.path()).unwrap();
+
+        assert!(dest.path().join("keep.txt").exists());
+        assert!(dest.path().join("vendor/tool/lib.rs").exists());
+        for hidden in &[
+            "vendor/tool/credentials.json",
+            "vendor/tool/.env.production",
+            "vendor/tool/.baro",
+            "vendor/tool/.aws",
+            "vendor/tool/.ssh",
+            "home/.ssh",
+        ] {
+            assert!(!dest.path().join(hidden).exists(), "{} should be excluded", hidden);
+        }
+    }
+
+    #[test]
+    fn excludes_nested_products() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("keep.txt"), "outer").unwrap();
+        let inner = src.path().join("forks/inner");
+        fs::create_dir_all(inner.join(".baro")).unwrap();
+        fs::write(inner.join(".baro/manifest.json"), "{\"version\":\"1.0.0\"}").unwrap();
+        fs::write(inner.join("inner.txt"), "inner").unwrap();
+
+        assert_eq!(find_nested_products(src.path()), vec![std::path::PathBuf::from("forks/inner")]);
+
+        let (bytes, _) = create_archive(src.path()).unwrap();
+        let dest = tempdir().unwrap();
+        extract_archive(&bytes, dest.path()).unwrap();
+        assert!(dest.path().join("keep.txt").exists());
+        assert!(!dest.path().join("forks/inner").exists());
+    }
+
+    #[test]
+    fn finds_outermost_products() {
+        let root = tempdir().unwrap();
+        for product in ["a", "b", "b/vendor/dep", "group/c"] {
+            fs::create_dir_all(root.path().join(product).join(".baro")).unwrap();
+            fs::write(root.path().join(product).join(".baro/manifest.json"), "{\"version\":\"1.0.0\"}").unwrap();
+        }
+        let mut found = find_products(root.path());
+        found.sort();
+        assert_eq!(found, ["a", "b", "group/c"].map(std::path::PathBuf::from));
+        assert_eq!(find_products(&root.path().join("b")), vec![std::path::PathBuf::new()]);
+    }
+
+    #[test]
+    fn extras_bypass_exclusions() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("main.rs"), "fn main() {}").unwrap();
+        let side = tempdir().unwrap();
+        let extra = side.path().join("history.bundle");
+        fs::write(&extra, "bundle").unwrap();
+
+        let (bytes, _) = create_archive_with_extras(src.path(), &[(".baro/history.bundle", &extra)]).unwrap();
+        let dest = tempdir().unwrap();
+        extract_archive(&bytes, dest.path()).unwrap();
+        assert_eq!(fs::read_to_string(dest.path().join(".baro/history.bundle")).unwrap(), "bundle");
+    }
+
+    #[test]
+    fn extract_from_file_matches_in_memory() {
+        let src = tempdir().unwrap();
+        fs::write(src.path().join("hello.txt"), "world").unwrap();
+        let (bytes, _) = create_archive(src.path()).unwrap();
+
+        let side = tempdir().unwrap();
+        let archive = side.path().join("a.tar.gz");
+        fs::write(&archive, &bytes).unwrap();
+
+        let dest = tempdir().unwrap();
+        extract_archive_file(&archive, dest.path()).unwrap();
+        assert_eq!(fs::read_to_string(dest.path().join("hello.txt")).unwrap(), "world");
+    }
+
     #[test]
     fn extract_creates_dest_directory() {
         let src = tempdir().unwrap();
@@ -148,4 +679,89 @@ mod tests {
         extract_archive(&bytes, &nested).unwrap();
         assert!(nested.join("file.txt").exists());
     }
+
+    #[test]
+    fn reads_readme_from_archive() {
+        let src = tempdir().unwrap();
+        fs::create_dir_all(src.path().join("docs")).unwrap();
+        fs::write(src.path().join("docs/README.md"), "nested").unwrap();
+        fs::write(src.path().join("main.py"), "print()").unwrap();
+        let (bytes, _) = create_archive(src.path()).unwrap();
+        assert!(read_archive_readme(bytes.as_slice()).unwrap().is_none());
+
+        fs::write(src.path().join("README.md"), "# Demo").unwrap();
+        let (bytes, _) = create_archive(src.path()).unwrap();
+        assert_eq!(read_archive_readme(bytes.as_slice()).unwrap().as_deref(), Some("# Demo"));
+    }
+
+    #[test]
+    fn archive_files_match_tree() {
+        let src = tempdir().unwrap();
+        fs::create_dir_all(src.path().join("src")).unwrap();
+        fs::write(src.path().join("src/lib.rs"), "pub fn f() {}").unwrap();
+        fs::write(src.path().join("README.md"), "# Demo").unwrap();
+        let extras = tempdir().unwrap();
+        let setup = extras.path().join("setup");
+        fs::write(&setup, "npm install").unwrap();
+        let (bytes, _) = create_archive_with_extras(src.path(), &[(".baro/setup", setup.as_path())]).unwrap();
+
+        let archived = read_archive_files(bytes.as_slice()).unwrap();
+        assert_eq!(archived.keys().collect::<Vec<_>>(), vec!["README.md", "src/lib.rs"]);
+        assert_eq!(archived, read_tree(src.path()).unwrap());
+    }
+
+    #[test]
+    fn lists_archive_as_tree() {
+        let src = tempdir().unwrap();
+        fs::create_dir_all(src.path().join("src/bin")).unwrap();
+        fs::write(src.path().join("src/bin/tool.rs"), "fn main() {}").unwrap();
+        fs::write(src.path().join("src/lib.rs"), "").unwrap();
+        fs::write(src.path().join("README.md"), "# Demo").unwrap();
+        let (bytes, _) = create_archive(src.path()).unwrap();
+
+        let files = list_archive(bytes.as_slice()).unwrap();
+        assert_eq!(files.len(), 3);
+        assert!(files.contains(&ArchiveEntry { path: "src/bin/tool.rs".to_string(), size: 12 }));
+        assert_eq!(
+            render_tree(&files),
+            vec!["README.md  (6 B)", "src/", "  bin/", "    tool.rs  (12 B)", "  lib.rs  (0 B)"]
+        );
+    }
+
+    #[test]
+    fn flags_home_root_and_huge_uninitialized_trees() {
+        let home = tempdir().unwrap();
+        assert!(suspicious_root(home.path(), Some(home.path())).unwrap().contains("home directory"));
+        assert!(suspicious_root(Path::new("/"), None).unwrap().contains("top of the filesystem"));
+
+        let project = home.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("main.py"), "print()").unwrap();
+        assert!(suspicious_root(&project, Some(home.path())).is_none());
+
+        for i in 0..=LARGE_TREE_FILES {
+            fs::write(project.join(format!("f{}.txt", i)), "").unwrap();
+        }
+        assert!(suspicious_root(&project, None).unwrap().contains("no .baro/manifest.json"));
+        fs::create_dir_all(project.join(".baro")).unwrap();
+        fs::write(project.join(".baro/manifest.json"), "{}").unwrap();
+        assert!(suspicious_root(&project, None).is_none());
+    }
+
+    #[test]
+    fn assets_named_by_file_name_and_unique() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("linux")).unwrap();
+        fs::write(dir.path().join("tool-linux-x64"), "bin").unwrap();
+        fs::write(dir.path().join("linux/tool-linux-x64"), "bin").unwrap();
+
+        let named = asset_names(&[dir.path().join("tool-linux-x64")]).unwrap();
+        assert_eq!(named[0].0, "tool-linux-x64");
+        assert!(asset_names(&[dir.path().join("tool-linux-x64"), dir.path().join("linux/tool-linux-x64")])
+            .unwrap_err()
+            .to_string()
+            .contains("Two assets"));
+        assert!(asset_names(&[dir.path().join("missing")]).is_err());
+        assert!(asset_names(&[dir.path().join("linux")]).is_err());
+    }
 }
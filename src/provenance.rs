@@ -0,0 +1,247 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::git;
+use crate::signing;
+use crate::types::SignedProvenance;
+
+const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+const PREDICATE_TYPE: &str = "https://slsa.dev/provenance/v1";
+
+/// An in-toto statement with a SLSA-style predicate: which CI builder produced
+/// which archive digest from which commit.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Provenance {
+    #[serde(rename = "_type")]
+    pub statement_type: String,
+    pub subject: Vec<Subject>,
+    #[serde(rename = "predicateType")]
+    pub predicate_type: String,
+    pub predicate: Predicate,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Subject {
+    pub name: String,
+    pub digest: Digest,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Digest {
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Predicate {
+    pub builder: Builder,
+    pub source: Source,
+    pub built_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Builder {
+    /// URL of the CI run, or the CI system name when no run URL is available
+    pub id: String,
+    /// CI system, e.g. `github-actions`
+    pub ci: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Source {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    pub commit: String,
+}
+
+/// What the CI environment says about the build. `None` outside CI.
+#[derive(Debug, PartialEq)]
+pub struct CiEnvironment {
+    pub builder: Builder,
+    pub repository: Option<String>,
+    pub commit: Option<String>,
+}
+
+/// Detect the CI system from environment variables (`env` looks one up).
+pub fn detect_ci(env: impl Fn(&str) -> Option<String>) -> Option<CiEnvironment> {
+    let is_true = |key: &str| env(key).is_some_and(|v| v == "true" || v == "1");
+
+    if is_true("GITHUB_ACTIONS") {
+        let server = env("GITHUB_SERVER_URL").unwrap_or_else(|| "https://github.com".to_string());
+        let repository = env("GITHUB_REPOSITORY").map(|r| format!("{}/{}", server, r));
+        let id = match (&repository, env("GITHUB_RUN_ID")) {
+            (Some(repo), Some(run)) => format!("{}/actions/runs/{}", repo, run),
+            _ => "github-actions".to_string(),
+        };
+        return Some(CiEnvironment {
+            builder: Builder {
+                id,
+                ci: "github-actions".to_string(),
+                workflow: env("GITHUB_WORKFLOW_REF"),
+            },
+            repository,
+            commit: env("GITHUB_SHA"),
+        });
+    }
+
+    if is_true("GITLAB_CI") {
+        return Some(CiEnvironment {
+            builder: Builder {
+                id: env("CI_JOB_URL").unwrap_or_else(|| "gitlab-ci".to_string()),
+                ci: "gitlab-ci".to_string(),
+                workflow: env("CI_CONFIG_PATH"),
+            },
+            repository: env("CI_PROJECT_URL"),
+            commit: env("CI_COMMIT_SHA"),
+        });
+    }
+
+    if is_true("CI") {
+        return Some(CiEnvironment {
+            builder: Builder {
+                id: env("BUILD_URL").unwrap_or_else(|| "ci".to_string()),
+                ci: "ci".to_string(),
+                workflow: None,
+            },
+            repository: None,
+            commit: None,
+        });
+    }
+
+    None
+}
+
+/// Build the provenance statement for an archive published from `cwd` at `target`.
+/// The commit comes from git when available, else from the CI environment.
+pub fn build(
+    ci: CiEnvironment,
+    cwd: &Path,
+    target: &str,
+    archive_name: &str,
+    archive_hash: &str,
+) -> Result<Provenance> {
+    let commit = git::rev_parse(cwd, target)
+        .ok()
+        .or(ci.commit)
+        .ok_or_else(|| anyhow::anyhow!("Could not determine the source commit for provenance"))?;
+    Ok(Provenance {
+        statement_type: STATEMENT_TYPE.to_string(),
+        subject: vec![Subject {
+            name: archive_name.to_string(),
+            digest: Digest { sha256: archive_hash.to_string() },
+        }],
+        predicate_type: PREDICATE_TYPE.to_string(),
+        predicate: Predicate {
+            builder: ci.builder,
+            source: Source {
+                repository: ci.repository.or_else(|| git::origin_url(cwd)),
+                commit,
+            },
+            built_at: chrono::Utc::now().to_rfc3339(),
+        },
+    })
+}
+
+/// Serialize and sign a statement. The signature covers the exact payload string.
+pub fn sign(key: &ed25519_dalek::SigningKey, provenance: &Provenance) -> Result<SignedProvenance> {
+    let payload = serde_json::to_string(provenance)?;
+    Ok(SignedProvenance {
+        signature: signing::sign_bytes(key, payload.as_bytes()),
+        public_key: signing::public_key_hex(key),
+        payload,
+    })
+}
+
+/// Check a signed statement against the publisher's registered keys and the
+/// release's archive digest. Returns the statement when everything matches.
+pub fn verify(signed: &SignedProvenance, publisher_keys: &[String], archive_hash: &str) -> Result<Provenance> {
+    let trusted = publisher_keys.iter().any(|k| {
+        k.eq_ignore_ascii_case(&signed.public_key)
+            && signing::verify_bytes(k, signed.payload.as_bytes(), &signed.signature)
+    });
+    if !trusted {
+        return Err(anyhow::anyhow!(
+            "Provenance signature does not match any of the publisher's registered signing keys"
+        ));
+    }
+
+    let provenance: Provenance = serde_json::from_str(&signed.payload)
+        .map_err(|e| anyhow::anyhow!("Malformed provenance document: {}", e))?;
+    if provenance.predicate_type != PREDICATE_TYPE {
+        return Err(anyhow::anyhow!("Unsupported provenance type: {}", provenance.predicate_type));
+    }
+    if !provenance.subject.iter().any(|s| s.digest.sha256 == archive_hash) {
+        return Err(anyhow::anyhow!(
+            "Provenance does not cover this archive (expected digest {})",
+            archive_hash
+        ));
+    }
+    Ok(provenance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env_from(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |key| map.get(key).cloned()
+    }
+
+    fn sample(hash: &str) -> Provenance {
+        Provenance {
+            statement_type: STATEMENT_TYPE.to_string(),
+            subject: vec![Subject {
+                name: "demo-1.0.0.tar.gz".to_string(),
+                digest: Digest { sha256: hash.to_string() },
+            }],
+            predicate_type: PREDICATE_TYPE.to_string(),
+            predicate: Predicate {
+                builder: Builder { id: "ci".to_string(), ci: "ci".to_string(), workflow: None },
+                source: Source { repository: None, commit: "abc123".to_string() },
+                built_at: "2026-01-01T00:00:00Z".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn detects_github_actions() {
+        let ci = detect_ci(env_from(&[
+            ("GITHUB_ACTIONS", "true"),
+            ("GITHUB_REPOSITORY", "acme/demo"),
+            ("GITHUB_RUN_ID", "42"),
+            ("GITHUB_SHA", "deadbeef"),
+        ]))
+        .unwrap();
+        assert_eq!(ci.builder.id, "https://github.com/acme/demo/actions/runs/42");
+        assert_eq!(ci.repository.as_deref(), Some("https://github.com/acme/demo"));
+        assert_eq!(ci.commit.as_deref(), Some("deadbeef"));
+
+        assert!(detect_ci(env_from(&[])).is_none());
+        assert_eq!(detect_ci(env_from(&[("CI", "true")])).unwrap().builder.ci, "ci");
+    }
+
+    #[test]
+    fn sign_and_verify_roundtrip() {
+        let key = signing::generate().unwrap();
+        let hash = "ab".repeat(32);
+        let signed = sign(&key, &sample(&hash)).unwrap();
+        let keys = vec![signing::public_key_hex(&key)];
+
+        assert_eq!(verify(&signed, &keys, &hash).unwrap(), sample(&hash));
+        assert!(verify(&signed, &keys, &"cd".repeat(32)).is_err());
+        let other = vec![signing::public_key_hex(&signing::generate().unwrap())];
+        assert!(verify(&signed, &other, &hash).is_err());
+    }
+
+    #[test]
+    fn verify_rejects_edited_payload() {
+        let key = signing::generate().unwrap();
+        let hash = "ab".repeat(32);
+        let mut signed = sign(&key, &sample(&hash)).unwrap();
+        signed.payload = signed.payload.replace("abc123", "evil99");
+        assert!(verify(&signed, &[signing::public_key_hex(&key)], &hash).is_err());
+    }
+}
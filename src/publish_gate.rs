@@ -1,6 +1,8 @@
 use std::path::Path;
 
+use crate::packaging;
 use crate::types::Category;
+use crate::utils;
 
 pub struct GateResult {
     pub passed: bool,
@@ -48,27 +50,8 @@ pub fn run(
     let mut failures = Vec::new();
     let mut warnings = Vec::new();
 
-    // Required: build file
-    if !BUILD_FILES.iter().any(|f| dir.join(f).exists()) {
-        failures.push(CheckFailure {
-            message: "No build file found (Cargo.toml, package.json, Makefile, etc.)".to_string(),
-            ai_fix_prompt: "Create a build file (e.g., Cargo.toml for Rust, package.json for Node.js) that describes how to build this project.".to_string(),
-        });
-    }
-
-    // Required: README
-    let has_readme = dir.join("README.md").exists()
-        || dir.join("readme.md").exists()
-        || dir.join("README").exists();
-    if !has_readme {
-        failures.push(CheckFailure {
-            message: "README.md not found".to_string(),
-            ai_fix_prompt: "Create a README.md with: project description (what it does, who it's for), setup instructions, and usage examples. Minimum 200 words.".to_string(),
-        });
-    }
-
-    // Required: no secrets
-    check_secrets(dir, &mut failures);
+    check_contents(dir, false, &mut failures);
+    check_path_lengths(dir, &mut failures, &mut warnings);
 
     // Required: valid version
     let version_re = regex_lite(r"^\d+(\.\d+)*$");
@@ -95,8 +78,14 @@ pub fn run(
     // Required: valid category
     if !categories.iter().any(|c| c.slug == category_slug) {
         let available: Vec<&str> = categories.iter().map(|c| c.slug.as_str()).collect();
+        let suggestions = utils::closest_matches(category_slug, &available, 3);
+        let hint = if suggestions.is_empty() {
+            String::new()
+        } else {
+            format!(" Did you mean: {}?", suggestions.join(", "))
+        };
         failures.push(CheckFailure {
-            message: format!("Invalid category: '{}'", category_slug),
+            message: format!("Invalid category: '{}'.{}", category_slug, hint),
             ai_fix_prompt: format!(
                 "Use --category with a valid slug. Available: {}",
                 available.join(", ")
@@ -130,28 +119,242 @@ pub fn run(
     }
 }
 
-fn check_secrets(dir: &Path, failures: &mut Vec<CheckFailure>) {
-    let mut found_secrets: Vec<String> = Vec::new();
+/// Checks on the files themselves: build file, README, no secrets. `archived`
+/// when `dir` is an extracted archive rather than a project about to be packaged.
+fn check_contents(dir: &Path, archived: bool, failures: &mut Vec<CheckFailure>) {
+    // Required: build file
+    if !BUILD_FILES.iter().any(|f| dir.join(f).exists()) {
+        failures.push(CheckFailure {
+            message: "No build file found (Cargo.toml, package.json, Makefile, etc.)".to_string(),
+            ai_fix_prompt: "Create a build file (e.g., Cargo.toml for Rust, package.json for Node.js) that describes how to build this project.".to_string(),
+        });
+    }
+
+    // Required: README
+    let has_readme = dir.join("README.md").exists()
+        || dir.join("readme.md").exists()
+        || dir.join("README").exists();
+    if !has_readme {
+        failures.push(CheckFailure {
+            message: "README.md not found".to_string(),
+            ai_fix_prompt: "Create a README.md with: project description (what it does, who it's for), setup instructions, and usage examples. Minimum 200 words.".to_string(),
+        });
+    }
+
+    // Required: no secrets
+    check_secrets(dir, archived, failures);
+}
+
+/// How many offending paths to list before "and N more".
+const MAX_LISTED_PATHS: usize = 5;
+
+fn list_paths(paths: &[String]) -> String {
+    let mut listed = paths.iter().take(MAX_LISTED_PATHS).cloned().collect::<Vec<_>>().join("\n  ");
+    if paths.len() > MAX_LISTED_PATHS {
+        listed.push_str(&format!("\n  ... and {} more", paths.len() - MAX_LISTED_PATHS));
+    }
+    listed
+}
+
+/// Paths other machines can't extract (see `packaging::long_paths`).
+fn check_path_lengths(dir: &Path, failures: &mut Vec<CheckFailure>, warnings: &mut Vec<CheckWarning>) {
+    let Ok(files) = packaging::list_files(dir) else { return };
+    let (unextractable, windows) = packaging::long_paths(&files);
+    if !unextractable.is_empty() {
+        failures.push(CheckFailure {
+            message: format!(
+                "{} path(s) too long to extract (names over {} bytes or paths over {} bytes):\n  {}",
+                unextractable.len(),
+                packaging::MAX_NAME_BYTES,
+                packaging::MAX_PATH_BYTES,
+                list_paths(&unextractable)
+            ),
+            ai_fix_prompt: format!(
+                "Shorten these paths or add them to .gitignore (e.g. generated or dependency folders): {}",
+                unextractable.join(", ")
+            ),
+        });
+    }
+    if !windows.is_empty() {
+        warnings.push(CheckWarning {
+            message: format!(
+                "{} path(s) longer than {} characters won't extract on Windows without long path support:\n  {}",
+                windows.len(),
+                packaging::WINDOWS_MAX_PATH,
+                list_paths(&windows)
+            ),
+        });
+    }
+}
+
+/// Content checks only, for auditing an archive that was already published
+/// (version, description and category were validated by the registry).
+#[cfg(feature = "moderation")]
+pub fn audit(dir: &Path) -> GateResult {
+    let mut failures = Vec::new();
+    check_contents(dir, true, &mut failures);
+    GateResult {
+        passed: failures.is_empty(),
+        failures,
+        warnings: Vec::new(),
+    }
+}
+
+/// Bounds for a product page supplied with `--readme` / `readme_path`.
+const MIN_README_CHARS: usize = 200;
+const MAX_README_BYTES: usize = 100 * 1024;
+
+/// Fail when an explicitly chosen product-page README (`path`) is too short
+/// to describe the product, or too large for the product page.
+pub fn check_readme(path: &str, content: &str) -> Option<CheckFailure> {
+    let chars = content.trim().chars().count();
+    if chars < MIN_README_CHARS {
+        return Some(CheckFailure {
+            message: format!("Product page README {} is too short ({} chars, need {}+)", path, chars, MIN_README_CHARS),
+            ai_fix_prompt: format!(
+                "Expand {} with what the product does, who it's for, setup steps and a usage example.",
+                path
+            ),
+        });
+    }
+    if content.len() > MAX_README_BYTES {
+        return Some(CheckFailure {
+            message: format!(
+                "Product page README {} is too large ({}, max {})",
+                path,
+                utils::format_bytes(content.len() as i64),
+                utils::format_bytes(MAX_README_BYTES as i64)
+            ),
+            ai_fix_prompt: format!("Trim {} to an overview; link to full documentation with --docs.", path),
+        });
+    }
+    None
+}
+
+/// Fail when `version` was already released or is lower than the latest of
+/// `existing` (the product's published versions).
+pub fn check_release_version(version: &str, existing: &[String]) -> Option<CheckFailure> {
+    let latest = existing
+        .iter()
+        .max_by(|a, b| utils::compare_versions(a, b))?;
+    let next = utils::bump_last_component(latest);
+    if existing.iter().any(|v| utils::compare_versions(v, version).is_eq()) {
+        return Some(CheckFailure {
+            message: format!("Version {} is already published", version),
+            ai_fix_prompt: format!("Bump the version; the latest release is {}. Try --version {}.", latest, next),
+        });
+    }
+    if utils::compare_versions(version, latest).is_lt() {
+        return Some(CheckFailure {
+            message: format!("Version {} is lower than the latest release ({})", version, latest),
+            ai_fix_prompt: format!("Publish a version higher than {}, e.g. --version {}.", latest, next),
+        });
+    }
+    None
+}
+
+/// Fail when an archive of `archive_size` bytes would exceed the quota.
+pub fn check_quota(archive_size: i64, used: i64, limit: i64, tier: &str) -> Option<CheckFailure> {
+    let remaining = (limit - used).max(0);
+    if archive_size <= remaining {
+        return None;
+    }
+    Some(CheckFailure {
+        message: format!(
+            "Not enough storage: need {} more, currently using {} of {} ({} tier)",
+            utils::format_bytes(archive_size - remaining),
+            utils::format_bytes(used),
+            utils::format_bytes(limit),
+            tier
+        ),
+        ai_fix_prompt: "Shrink the archive (add large generated files to .gitignore), delete old releases on baro-sync.com, or upgrade your tier.".to_string(),
+    })
+}
+
+/// Fail when the archive is over the per-release limit the registry reported.
+pub fn check_archive_size(archive_size: i64, limit: i64, tier: &str) -> Option<CheckFailure> {
+    if archive_size <= limit {
+        return None;
+    }
+    Some(CheckFailure {
+        message: format!(
+            "Archive is {}, over the {} per-release limit of the {} tier",
+            utils::format_bytes(archive_size),
+            utils::format_bytes(limit),
+            tier
+        ),
+        ai_fix_prompt: format!(
+            "Reduce the archive by at least {}: add build outputs, datasets and binaries to .gitignore, move large assets to external storage, or upgrade your tier.",
+            utils::format_bytes(archive_size - limit)
+        ),
+    })
+}
+
+fn is_secret_name(name: &str) -> bool {
+    (name.starts_with(".env") && name != ".env.example")
+        || SECRET_FILES.contains(&name)
+        || SECRET_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+}
+
+/// Basic scan of git history for secret files that were ever committed, even
+/// if later deleted. Only needed when the history itself is published.
+pub fn check_git_history(paths: &[String]) -> Vec<CheckFailure> {
+    let leaked: Vec<&str> = paths
+        .iter()
+        .map(String::as_str)
+        .filter(|p| is_secret_name(p.rsplit('/').next().unwrap_or(p)))
+        .collect();
+    if leaked.is_empty() {
+        return Vec::new();
+    }
+    vec![CheckFailure {
+        message: format!("Potential secrets in git history: {}", leaked.join(", ")),
+        ai_fix_prompt: format!(
+            "These files were committed at some point and would ship with --with-git-history: {}. \
+            Rewrite history to remove them (e.g. git filter-repo) and rotate the credentials, or publish without --with-git-history.",
+            leaked.join(", ")
+        ),
+    }]
+}
 
-    // Check .env* files
+/// Secret files a publish would be exposed by: those at the project root
+/// (even when packaging leaves them out) and any that would be packaged.
+fn packaged_secrets(dir: &Path) -> Vec<String> {
+    let mut found = Vec::new();
     if let Ok(entries) = std::fs::read_dir(dir) {
         for entry in entries.flatten() {
             let name = entry.file_name().to_string_lossy().to_string();
-            let mut is_secret = false;
-            if name.starts_with(".env") && name != ".env.example" {
-                is_secret = true;
+            if is_secret_name(&name) {
+                found.push(name);
             }
-            if SECRET_FILES.contains(&name.as_str()) {
-                is_secret = true;
-            }
-            if SECRET_EXTENSIONS.iter().any(|ext| name.ends_with(ext)) {
-                is_secret = true;
-            }
-            if is_secret {
-                found_secrets.push(name);
+        }
+    }
+    for path in packaging::list_files(dir).unwrap_or_default() {
+        let path = path.to_string_lossy().replace('\\', "/");
+        if path.contains('/') && is_secret_name(path.rsplit('/').next().unwrap_or(&path)) {
+            found.push(path);
+        }
+    }
+    found
+}
+
+/// Secret files at any depth, ignored or not, for an extracted archive where
+/// everything present was shipped.
+fn all_secrets(dir: &Path) -> Vec<String> {
+    let mut found = Vec::new();
+    for entry in ignore::WalkBuilder::new(dir).standard_filters(false).build().flatten() {
+        if entry.file_type().is_some_and(|ft| ft.is_file()) && is_secret_name(&entry.file_name().to_string_lossy()) {
+            if let Ok(relative) = entry.path().strip_prefix(dir) {
+                found.push(relative.to_string_lossy().replace('\\', "/"));
             }
         }
     }
+    found
+}
+
+fn check_secrets(dir: &Path, archived: bool, failures: &mut Vec<CheckFailure>) {
+    let mut found_secrets = if archived { all_secrets(dir) } else { packaged_secrets(dir) };
+    found_secrets.sort();
 
     if !found_secrets.is_empty() {
         failures.push(CheckFailure {
@@ -258,6 +461,21 @@ mod tests {
         assert!(result.failures.iter().any(|f| f.message.contains(".env")));
     }
 
+    #[test]
+    fn detects_secrets_in_subdirectories() {
+        let dir = setup_valid_dir();
+        fs::create_dir_all(dir.path().join("config/keys")).unwrap();
+        fs::write(dir.path().join("config/keys/server.pem"), "key").unwrap();
+        // Never packaged, so not a reason to block the publish
+        fs::create_dir_all(dir.path().join("services/api")).unwrap();
+        fs::write(dir.path().join("services/api/.env"), "X=1").unwrap();
+        fs::create_dir_all(dir.path().join("target/debug")).unwrap();
+        fs::write(dir.path().join("target/debug/id_rsa"), "key").unwrap();
+        let result = run(dir.path(), "1.0.0", Some(&valid_description()), "developer-tools", &sample_categories());
+        let secrets = result.failures.iter().find(|f| f.message.starts_with("Potential secrets")).unwrap();
+        assert_eq!(secrets.message, "Potential secrets found: config/keys/server.pem");
+    }
+
     #[test]
     fn env_example_allowed_env_local_blocked() {
         let dir = setup_valid_dir();
@@ -332,6 +550,18 @@ mod tests {
             "None description should skip check for existing products");
     }
 
+    #[test]
+    fn git_history_secret_scan() {
+        let clean = vec!["src/main.rs".to_string(), ".env.example".to_string()];
+        assert!(check_git_history(&clean).is_empty());
+
+        let leaky = vec!["src/main.rs".to_string(), "config/prod.pem".to_string(), ".env".to_string()];
+        let failures = check_git_history(&leaky);
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].message.contains("config/prod.pem"));
+        assert!(failures[0].message.contains(".env"));
+    }
+
     #[test]
     fn invalid_category_fails() {
         let dir = setup_valid_dir();
@@ -339,4 +569,84 @@ mod tests {
         assert!(!result.passed);
         assert!(result.failures.iter().any(|f| f.message.contains("Invalid category")));
     }
+
+    #[test]
+    fn misspelled_category_gets_suggestion() {
+        let dir = setup_valid_dir();
+        let result = run(dir.path(), "1.0.0", Some(&valid_description()), "developer-tool", &sample_categories());
+        assert!(result.failures.iter().any(|f| f.message.contains("Did you mean: developer-tools?")));
+    }
+
+    #[test]
+    fn overlong_paths_fail_with_list() {
+        let dir = setup_valid_dir();
+        // 1200+ bytes deep: fine on Linux, not extractable on macOS
+        let deep: std::path::PathBuf = (0..6).map(|i| format!("{}{}", i, "d".repeat(200))).collect();
+        fs::create_dir_all(dir.path().join(&deep)).unwrap();
+        fs::write(dir.path().join(&deep).join("index.js"), "x").unwrap();
+        let result = run(dir.path(), "1.0.0", Some(&valid_description()), "developer-tools", &sample_categories());
+        assert!(!result.passed);
+        assert!(result.failures.iter().any(|f| f.message.contains("too long to extract") && f.message.contains("index.js")));
+    }
+
+    #[cfg(feature = "moderation")]
+    #[test]
+    fn audit_checks_contents_only() {
+        let dir = setup_valid_dir();
+        assert!(audit(dir.path()).passed);
+        fs::write(dir.path().join("id_rsa"), "key").unwrap();
+        assert!(!audit(dir.path()).passed);
+
+        // Everything in an archive shipped, so nested secrets count too
+        let dir = setup_valid_dir();
+        fs::create_dir_all(dir.path().join("services/api")).unwrap();
+        fs::write(dir.path().join("services/api/.env"), "X=1").unwrap();
+        let result = audit(dir.path());
+        assert!(result.failures.iter().any(|f| f.message == "Potential secrets found: services/api/.env"));
+    }
+
+    #[test]
+    fn readme_override_length_bounds() {
+        assert!(check_readme("MARKETPLACE.md", &"word ".repeat(60)).is_none());
+        let short = check_readme("MARKETPLACE.md", "# Demo\n\nSoon.").unwrap();
+        assert!(short.message.contains("MARKETPLACE.md is too short"));
+        assert!(check_readme("big.md", &"x".repeat(MAX_README_BYTES + 1)).unwrap().message.contains("too large"));
+    }
+
+    #[test]
+    fn release_version_must_be_new_and_higher() {
+        let existing = vec!["1.0.0".to_string(), "1.2.0".to_string(), "1.10.0".to_string()];
+        assert!(check_release_version("1.11.0", &existing).is_none());
+        assert!(check_release_version("2.0", &[]).is_none());
+
+        let dup = check_release_version("1.2.0", &existing).unwrap();
+        assert!(dup.message.contains("already published"));
+        let lower = check_release_version("1.9.0", &existing).unwrap();
+        assert!(lower.message.contains("lower than the latest release (1.10.0)"));
+        assert!(lower.ai_fix_prompt.contains("1.10.1"));
+    }
+
+    #[test]
+    fn quota_check_reports_shortfall() {
+        let mb = 1024 * 1024;
+        assert!(check_quota(10 * mb, 100 * mb, 200 * mb, "free").is_none());
+        assert!(check_quota(100 * mb, 100 * mb, 200 * mb, "free").is_none());
+
+        let f = check_quota(12 * mb, 995 * mb, 1000 * mb, "free").unwrap();
+        assert_eq!(
+            f.message,
+            "Not enough storage: need 7.0 MB more, currently using 995.0 MB of 1000.0 MB (free tier)"
+        );
+    }
+
+    #[test]
+    fn archive_size_check_reports_overage() {
+        let mb = 1024 * 1024;
+        assert!(check_archive_size(50 * mb, 100 * mb, "free").is_none());
+        assert!(check_archive_size(100 * mb, 100 * mb, "free").is_none());
+
+        let f = check_archive_size(150 * mb, 100 * mb, "free").unwrap();
+        assert_eq!(f.message, "Archive is 150.0 MB, over the 100.0 MB per-release limit of the free tier");
+        assert!(f.ai_fix_prompt.contains("50.0 MB"));
+    }
 }
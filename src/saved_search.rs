@@ -0,0 +1,159 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+use crate::config;
+use crate::utils;
+
+/// A named search (query + filters), with the results of its last run so the
+/// next run can highlight what is new.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SavedSearch {
+    #[serde(default)]
+    pub query: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_publishers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_rating: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_forks: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remakes_of: Option<String>,
+    #[serde(default = "default_sort")]
+    pub sort: String,
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    /// `user/slug` of each result from the last run
+    #[serde(default)]
+    pub last_results: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<String>,
+}
+
+// The `baro search` defaults, for searches defined in config.json
+fn default_sort() -> String {
+    "recent".to_string()
+}
+
+fn default_limit() -> u32 {
+    20
+}
+
+/// The searches in config.json, each parsed on its own so one malformed
+/// entry doesn't hide the others.
+fn defined() -> BTreeMap<String, serde_json::Value> {
+    config::settings().searches
+}
+
+pub fn get(name: &str) -> Result<SavedSearch> {
+    let mut all = defined();
+    match all.remove(name) {
+        Some(entry) => serde_json::from_value(entry)
+            .map_err(|e| anyhow::anyhow!("Invalid search '{}' in config.json: {}", name, e)),
+        None if all.is_empty() => Err(anyhow::anyhow!(
+            "No saved search '{}'. Save one with: baro search <query> --save <name>",
+            name
+        )),
+        None => Err(anyhow::anyhow!(
+            "No saved search '{}'. Saved: {}",
+            name,
+            all.keys().map(String::as_str).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Set `searches.<name>` in the parsed config.json `config`, keeping every
+/// other setting as written.
+fn insert(config: &mut serde_json::Value, name: &str, search: &SavedSearch) -> Result<()> {
+    let Some(settings) = config.as_object_mut() else {
+        return Err(anyhow::anyhow!("config.json is not a JSON object"));
+    };
+    let searches = settings
+        .entry("searches")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("`searches` in config.json is not an object"))?;
+    searches.insert(name.to_string(), serde_json::to_value(search)?);
+    Ok(())
+}
+
+/// Insert or replace the search `name` in config.json.
+pub fn put(name: &str, search: SavedSearch) -> Result<()> {
+    let path = config::config_path()?;
+    let mut config = match std::fs::read_to_string(&path) {
+        // Never overwrite a config the user is halfway through editing
+        Ok(data) => serde_json::from_str(&data).map_err(|e| anyhow::anyhow!("Can't save the search, config.json is invalid: {}", e))?,
+        Err(_) => serde_json::json!({}),
+    };
+    insert(&mut config, name, &search)?;
+    utils::write_atomic(&path, serde_json::to_string_pretty(&config)?.as_bytes())?;
+    Ok(())
+}
+
+/// Every `user/slug` seen in any saved search's last run.
+pub fn known_results() -> Vec<String> {
+    defined()
+        .into_values()
+        .filter_map(|entry| serde_json::from_value::<SavedSearch>(entry).ok())
+        .flat_map(|s| s.last_results)
+        .collect()
+}
+
+/// Results in `current` that were not in `previous`.
+pub fn new_results<'a>(previous: &[String], current: &'a [String]) -> Vec<&'a str> {
+    current
+        .iter()
+        .filter(|id| !previous.contains(id))
+        .map(|id| id.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_results_only_unseen() {
+        let previous = vec!["a/one".to_string(), "b/two".to_string()];
+        let current = vec!["b/two".to_string(), "c/three".to_string()];
+        assert_eq!(new_results(&previous, &current), vec!["c/three"]);
+        assert!(new_results(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn hand_written_entry_parses() {
+        let s: SavedSearch =
+            serde_json::from_str(r#"{"query": "agent", "sort": "recent", "limit": 20}"#).unwrap();
+        assert!(s.last_results.is_empty());
+        assert!(s.category.is_none());
+
+        let s: SavedSearch = serde_json::from_str(r#"{"category": "ai-agents"}"#).unwrap();
+        assert_eq!((s.query.as_str(), s.category.as_deref()), ("", Some("ai-agents")));
+    }
+
+    #[test]
+    fn insert_replaces_search_and_keeps_other_settings() {
+        let mut config = serde_json::json!({
+            "readme_path": "docs/PAGE.md",
+            "searches": {"ai": {"category": "ai-agents"}, "old": {"query": "x"}}
+        });
+        let search: SavedSearch =
+            serde_json::from_str(r#"{"query": "agent", "last_results": ["a/one"]}"#).unwrap();
+        insert(&mut config, "ai", &search).unwrap();
+        assert_eq!(config["readme_path"], "docs/PAGE.md");
+        assert_eq!(config["searches"]["old"]["query"], "x");
+        assert_eq!(config["searches"]["ai"]["query"], "agent");
+        assert_eq!(config["searches"]["ai"]["last_results"][0], "a/one");
+        assert!(config["searches"]["ai"].get("category").is_none());
+
+        let mut empty = serde_json::json!({});
+        insert(&mut empty, "ai", &search).unwrap();
+        assert_eq!(empty["searches"]["ai"]["query"], "agent");
+        assert!(insert(&mut serde_json::json!({"searches": []}), "ai", &search).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use std::path::Path;
+
+/// A locked dependency, as read from a lockfile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    pub ecosystem: &'static str,
+    pub name: String,
+    pub version: String,
+}
+
+impl Component {
+    /// Package URL (https://github.com/package-url/purl-spec).
+    fn purl(&self) -> String {
+        let name = match self.ecosystem {
+            "npm" => self.name.replacen('@', "%40", 1),
+            "pypi" => self.name.to_lowercase().replace('_', "-"),
+            _ => self.name.clone(),
+        };
+        format!("pkg:{}/{}@{}", self.ecosystem, name, self.version)
+    }
+}
+
+/// `[[package]]` entries from a TOML lockfile (Cargo.lock, poetry.lock, uv.lock).
+/// Only `name` and `version` are read, so no TOML parser is needed.
+fn parse_toml_lock(content: &str, ecosystem: &'static str) -> Vec<Component> {
+    let mut out = Vec::new();
+    let mut in_package = false;
+    let mut name: Option<String> = None;
+    let mut version: Option<String> = None;
+
+    let mut flush = |name: &mut Option<String>, version: &mut Option<String>| {
+        if let (Some(n), Some(v)) = (name.take(), version.take()) {
+            out.push(Component { ecosystem, name: n, version: v });
+        }
+    };
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            flush(&mut name, &mut version);
+            in_package = line == "[[package]]";
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim().trim_matches('"').to_string();
+            match key.trim() {
+                "name" => name = Some(value),
+                "version" => version = Some(value),
+                _ => {}
+            }
+        }
+    }
+    flush(&mut name, &mut version);
+    out
+}
+
+/// Packages from package-lock.json (lockfile v2/v3 `packages`, or v1 `dependencies`).
+fn parse_package_lock(content: &str) -> Vec<Component> {
+    let Ok(lock) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    if let Some(packages) = lock["packages"].as_object() {
+        for (path, info) in packages {
+            // The root project is keyed by "" and is not a dependency
+            let Some(idx) = path.rfind("node_modules/") else { continue };
+            if let Some(version) = info["version"].as_str() {
+                out.push(Component {
+                    ecosystem: "npm",
+                    name: path[idx + "node_modules/".len()..].to_string(),
+                    version: version.to_string(),
+                });
+            }
+        }
+    } else if let Some(deps) = lock["dependencies"].as_object() {
+        for (name, info) in deps {
+            if let Some(version) = info["version"].as_str() {
+                out.push(Component { ecosystem: "npm", name: name.clone(), version: version.to_string() });
+            }
+        }
+    }
+    out
+}
+
+/// Exact pins (`name==version`) from requirements.txt; ranges are skipped.
+fn parse_requirements(content: &str) -> Vec<Component> {
+    content
+        .lines()
+        .map(|l| l.split('#').next().unwrap_or("").trim())
+        .filter_map(|l| {
+            let (name, version) = l.split_once("==")?;
+            let name = name.split('[').next()?.trim();
+            let version = version.split(';').next()?.trim();
+            (!name.is_empty() && !version.is_empty()).then(|| Component {
+                ecosystem: "pypi",
+                name: name.to_string(),
+                version: version.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Locked dependencies from every lockfile found in `dir`. Empty when there are none.
+pub fn collect_components(dir: &Path) -> Vec<Component> {
+    let read = |name: &str| std::fs::read_to_string(dir.join(name)).ok();
+    let mut components = Vec::new();
+    if let Some(c) = read("Cargo.lock") {
+        components.extend(parse_toml_lock(&c, "cargo"));
+    }
+    if let Some(c) = read("package-lock.json") {
+        components.extend(parse_package_lock(&c));
+    }
+    if let Some(c) = read("poetry.lock").or_else(|| read("uv.lock")) {
+        components.extend(parse_toml_lock(&c, "pypi"));
+    } else if let Some(c) = read("requirements.txt") {
+        components.extend(parse_requirements(&c));
+    }
+    components.sort_by(|a, b| (a.ecosystem, &a.name, &a.version).cmp(&(b.ecosystem, &b.name, &b.version)));
+    components.dedup();
+    components
+}
+
+/// A CycloneDX 1.5 JSON document for the product and its locked dependencies.
+pub fn cyclonedx(product_name: &str, version: &str, components: &[Component]) -> serde_json::Value {
+    let components: Vec<serde_json::Value> = components
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "type": "library",
+                "name": c.name,
+                "version": c.version,
+                "purl": c.purl(),
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "serialNumber": format!("urn:uuid:{}", uuid::Uuid::new_v4()),
+        "version": 1,
+        "metadata": {
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "tools": [{ "vendor": "baro", "name": "baro-cli", "version": env!("CARGO_PKG_VERSION") }],
+            "component": { "type": "application", "name": product_name, "version": version },
+        },
+        "components": components,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parses_cargo_lock_packages() {
+        let lock = r#"
+version = 3
+
+[[package]]
+name = "anyhow"
+version = "1.0.86"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "demo"
+version = "0.1.0"
+dependencies = [
+ "anyhow",
+]
+"#;
+        let parsed = parse_toml_lock(lock, "cargo");
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].name, "anyhow");
+        assert_eq!(parsed[0].purl(), "pkg:cargo/anyhow@1.0.86");
+    }
+
+    #[test]
+    fn parses_package_lock_v3_and_scoped_purls() {
+        let lock = r#"{"lockfileVersion": 3, "packages": {
+            "": {"name": "demo", "version": "1.0.0"},
+            "node_modules/react": {"version": "18.2.0"},
+            "node_modules/@types/node/node_modules/undici-types": {"version": "5.26.5"},
+            "node_modules/@types/node": {"version": "20.1.0"}
+        }}"#;
+        let parsed = parse_package_lock(lock);
+        assert_eq!(parsed.len(), 3);
+        let scoped = parsed.iter().find(|c| c.name == "@types/node").unwrap();
+        assert_eq!(scoped.purl(), "pkg:npm/%40types/node@20.1.0");
+        assert!(parsed.iter().any(|c| c.name == "undici-types"));
+    }
+
+    #[test]
+    fn requirements_only_exact_pins() {
+        let reqs = "requests==2.31.0\nflask>=2\nDjango_Extensions[all]==3.2.3 ; python_version > '3.8'  # admin\n";
+        let parsed = parse_requirements(reqs);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].purl(), "pkg:pypi/django-extensions@3.2.3");
+    }
+
+    #[test]
+    fn cyclonedx_document_shape() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("requirements.txt"), "requests==2.31.0\n").unwrap();
+        let components = collect_components(dir.path());
+        let bom = cyclonedx("demo", "1.0.0", &components);
+        assert_eq!(bom["bomFormat"], "CycloneDX");
+        assert_eq!(bom["metadata"]["component"]["version"], "1.0.0");
+        assert_eq!(bom["components"][0]["purl"], "pkg:pypi/requests@2.31.0");
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::config;
+use crate::utils;
+
+/// Repeating a search within this long reuses the cached response.
+pub const FRESH_SECS: i64 = 10 * 60;
+
+/// Searches kept; the least recently fetched are dropped first.
+const MAX_ENTRIES: usize = 100;
+
+/// A registry search response, keyed by its request (query and filters).
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SearchEntry {
+    pub response: serde_json::Value,
+    pub fetched_at: i64,
+}
+
+impl SearchEntry {
+    pub fn age_secs(&self) -> i64 {
+        chrono::Utc::now().timestamp() - self.fetched_at
+    }
+}
+
+fn cache_path() -> Option<PathBuf> {
+    config::config_dir().ok().map(|d| d.join("search-cache.json"))
+}
+
+fn load() -> BTreeMap<String, SearchEntry> {
+    cache_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save(entries: &BTreeMap<String, SearchEntry>) {
+    let Some(path) = cache_path() else { return };
+    if let Ok(json) = serde_json::to_string(entries) {
+        let _ = utils::write_atomic(&path, json.as_bytes());
+    }
+}
+
+/// The cached response for `key`, however old.
+pub fn get(key: &str) -> Option<SearchEntry> {
+    load().remove(key)
+}
+
+/// Cache `response` for `key`. Best-effort: errors are ignored.
+pub fn record(key: &str, response: &serde_json::Value) {
+    let mut entries = load();
+    entries.insert(key.to_string(), SearchEntry { response: response.clone(), fetched_at: chrono::Utc::now().timestamp() });
+    evict(&mut entries, MAX_ENTRIES);
+    save(&entries);
+}
+
+/// Drop the oldest entries beyond `keep`.
+fn evict(entries: &mut BTreeMap<String, SearchEntry>, keep: usize) {
+    if entries.len() <= keep {
+        return;
+    }
+    let mut by_age: Vec<(i64, String)> = entries.iter().map(|(k, e)| (e.fetched_at, k.clone())).collect();
+    by_age.sort();
+    for (_, key) in by_age.into_iter().take(entries.len() - keep) {
+        entries.remove(&key);
+    }
+}
+
+/// Drop searches not repeated within `max_age`. Returns how many were removed.
+pub fn prune(max_age: std::time::Duration) -> usize {
+    let mut entries = load();
+    let before = entries.len();
+    let cutoff = chrono::Utc::now().timestamp() - max_age.as_secs() as i64;
+    entries.retain(|_, e| e.fetched_at >= cutoff);
+    let removed = before - entries.len();
+    if removed > 0 {
+        save(&entries);
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_oldest_first() {
+        let entry = |t| SearchEntry { response: serde_json::Value::Null, fetched_at: t };
+        let mut entries: BTreeMap<String, SearchEntry> =
+            [("a", 30), ("b", 10), ("c", 20)].iter().map(|(k, t)| (k.to_string(), entry(*t))).collect();
+        evict(&mut entries, 2);
+        assert_eq!(entries.keys().collect::<Vec<_>>(), vec!["a", "c"]);
+        evict(&mut entries, 5);
+        assert_eq!(entries.len(), 2);
+    }
+}
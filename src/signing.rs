@@ -0,0 +1,144 @@
+use anyhow::{Context, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+use crate::config;
+
+/// Local ed25519 signing key, stored as a hex seed next to the credentials.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct StoredKey {
+    secret_key: String,
+    public_key: String,
+    created_at: String,
+}
+
+fn key_path() -> Result<PathBuf> {
+    Ok(config::config_dir()?.join("signing-key.json"))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex<const N: usize>(s: &str) -> Option<[u8; N]> {
+    let s = s.trim();
+    if s.len() != N * 2 || !s.is_ascii() {
+        return None;
+    }
+    let mut out = [0u8; N];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(out)
+}
+
+/// Generate a new key pair.
+pub fn generate() -> Result<SigningKey> {
+    let mut seed = [0u8; 32];
+    getrandom::getrandom(&mut seed).map_err(|e| anyhow::anyhow!("No secure randomness available: {}", e))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+pub fn key_exists() -> bool {
+    key_path().map(|p| p.exists()).unwrap_or(false)
+}
+
+/// Save the key with owner-only permissions.
+pub fn save(key: &SigningKey) -> Result<()> {
+    let path = key_path()?;
+    let stored = StoredKey {
+        secret_key: to_hex(&key.to_bytes()),
+        public_key: public_key_hex(key),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    std::fs::write(&path, serde_json::to_string_pretty(&stored)?)?;
+
+    #[cfg(unix)]
+    {
+        let mut perms = std::fs::metadata(&path)?.permissions();
+        perms.set_mode(0o600);
+        std::fs::set_permissions(&path, perms)?;
+    }
+
+    Ok(())
+}
+
+/// The local signing key, if `baro keygen` has been run.
+pub fn load() -> Result<Option<SigningKey>> {
+    let path = key_path()?;
+    let data = match std::fs::read_to_string(&path) {
+        Ok(d) => d,
+        Err(_) => return Ok(None),
+    };
+    let stored: StoredKey = serde_json::from_str(&data)
+        .with_context(|| format!("Corrupt signing key file: {}", path.display()))?;
+    let seed = from_hex::<32>(&stored.secret_key)
+        .ok_or_else(|| anyhow::anyhow!("Corrupt signing key file: {}", path.display()))?;
+    Ok(Some(SigningKey::from_bytes(&seed)))
+}
+
+pub fn public_key_hex(key: &SigningKey) -> String {
+    to_hex(key.verifying_key().as_bytes())
+}
+
+/// Sign an archive's SHA-256 hex digest. Returns the signature as hex.
+pub fn sign_hash(key: &SigningKey, hash_hex: &str) -> String {
+    sign_bytes(key, hash_hex.as_bytes())
+}
+
+/// Sign an arbitrary payload (e.g. a provenance document). Returns the signature as hex.
+pub fn sign_bytes(key: &SigningKey, payload: &[u8]) -> String {
+    to_hex(&key.sign(payload).to_bytes())
+}
+
+/// Check a hex signature over `hash_hex` against a hex public key.
+pub fn verify_hash(public_key_hex: &str, hash_hex: &str, signature_hex: &str) -> bool {
+    verify_bytes(public_key_hex, hash_hex.as_bytes(), signature_hex)
+}
+
+/// Check a hex signature over `payload` against a hex public key.
+pub fn verify_bytes(public_key_hex: &str, payload: &[u8], signature_hex: &str) -> bool {
+    let (Some(pk), Some(sig)) = (from_hex::<32>(public_key_hex), from_hex::<64>(signature_hex)) else {
+        return false;
+    };
+    let Ok(vk) = VerifyingKey::from_bytes(&pk) else {
+        return false;
+    };
+    vk.verify(payload, &Signature::from_bytes(&sig)).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_and_verify_roundtrip() {
+        let key = generate().unwrap();
+        let hash = "ab".repeat(32);
+        let sig = sign_hash(&key, &hash);
+        assert_eq!(sig.len(), 128);
+        assert!(verify_hash(&public_key_hex(&key), &hash, &sig));
+    }
+
+    #[test]
+    fn verify_rejects_tampering_and_wrong_key() {
+        let key = generate().unwrap();
+        let other = generate().unwrap();
+        let hash = "cd".repeat(32);
+        let sig = sign_hash(&key, &hash);
+
+        assert!(!verify_hash(&public_key_hex(&key), &"ef".repeat(32), &sig));
+        assert!(!verify_hash(&public_key_hex(&other), &hash, &sig));
+        assert!(!verify_hash(&public_key_hex(&key), &hash, "not-hex"));
+    }
+
+    #[test]
+    fn hex_roundtrip() {
+        let bytes = [0u8, 1, 254, 255];
+        assert_eq!(to_hex(&bytes), "0001feff");
+        assert_eq!(from_hex::<4>("0001feff"), Some(bytes));
+        assert_eq!(from_hex::<4>("0001fe"), None);
+    }
+}
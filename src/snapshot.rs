@@ -0,0 +1,219 @@
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::packaging;
+
+const SNAPSHOT_DIR: &str = ".baro/snapshots";
+const OBJECTS_DIR: &str = "objects";
+
+/// A saved checkpoint: every product file mapped to the hash of its content.
+/// File contents live once per hash under `.baro/snapshots/objects/`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Relative path (always `/`-separated) → sha256 hex
+    pub files: BTreeMap<String, String>,
+}
+
+/// What a restore changed in the working tree.
+#[derive(Debug, Default)]
+pub struct RestoreReport {
+    pub written: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+fn snapshots_dir(root: &Path) -> PathBuf {
+    root.join(SNAPSHOT_DIR)
+}
+
+fn object_path(root: &Path, hash: &str) -> PathBuf {
+    snapshots_dir(root).join(OBJECTS_DIR).join(hash)
+}
+
+fn path_key(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Current product files (same selection as the publish archive) as path → hash,
+/// storing any content not yet in the object store.
+fn capture(root: &Path) -> Result<BTreeMap<String, String>> {
+    let objects = snapshots_dir(root).join(OBJECTS_DIR);
+    std::fs::create_dir_all(&objects)?;
+
+    let mut files = BTreeMap::new();
+    for relative in packaging::list_files(root)? {
+        let content = std::fs::read(root.join(&relative))
+            .with_context(|| format!("Failed to read {}", relative.display()))?;
+        let hash = format!("{:x}", Sha256::digest(&content));
+        let object = objects.join(&hash);
+        if !object.exists() {
+            std::fs::write(&object, &content)?;
+        }
+        files.insert(path_key(&relative), hash);
+    }
+    Ok(files)
+}
+
+fn new_id(root: &Path) -> String {
+    let base = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let dir = snapshots_dir(root);
+    let mut id = base.clone();
+    let mut n = 2;
+    while dir.join(format!("{}.json", id)).exists() {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+    id
+}
+
+/// Save a snapshot of the working tree under `root`.
+pub fn save(root: &Path, message: Option<&str>) -> Result<Snapshot> {
+    let files = capture(root)?;
+    let snapshot = Snapshot {
+        id: new_id(root),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        message: message.map(String::from),
+        files,
+    };
+    let path = snapshots_dir(root).join(format!("{}.json", snapshot.id));
+    std::fs::write(&path, serde_json::to_string_pretty(&snapshot)?)?;
+    Ok(snapshot)
+}
+
+/// All snapshots, oldest first.
+pub fn list(root: &Path) -> Result<Vec<Snapshot>> {
+    let dir = snapshots_dir(root);
+    let mut snapshots = Vec::new();
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(snapshots),
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            let data = std::fs::read_to_string(&path)?;
+            let snapshot: Snapshot = serde_json::from_str(&data)
+                .with_context(|| format!("Corrupt snapshot index: {}", path.display()))?;
+            snapshots.push(snapshot);
+        }
+    }
+    snapshots.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    Ok(snapshots)
+}
+
+pub fn load(root: &Path, id: &str) -> Result<Snapshot> {
+    let path = snapshots_dir(root).join(format!("{}.json", id));
+    let data = std::fs::read_to_string(&path)
+        .with_context(|| format!("No snapshot '{}'. Run `baro snapshot list` to see saved snapshots.", id))?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+fn matches_any(key: &str, paths: &[String]) -> bool {
+    paths.iter().any(|p| {
+        let p = p.trim_end_matches('/');
+        key == p || key.starts_with(&format!("{}/", p))
+    })
+}
+
+/// Restore files from `snapshot`. With `paths` empty the whole tree is restored,
+/// including removing product files that did not exist at snapshot time;
+/// otherwise only files equal to or under the given paths are touched.
+pub fn restore(root: &Path, snapshot: &Snapshot, paths: &[String]) -> Result<RestoreReport> {
+    let current = capture(root)?;
+    let selected = |key: &str| paths.is_empty() || matches_any(key, paths);
+    let mut report = RestoreReport::default();
+
+    for (key, hash) in &snapshot.files {
+        if !selected(key) || current.get(key) == Some(hash) {
+            continue;
+        }
+        let content = std::fs::read(object_path(root, hash))
+            .with_context(|| format!("Snapshot object missing for {}", key))?;
+        let dest = root.join(key);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, content)?;
+        report.written.push(key.clone());
+    }
+
+    for key in current.keys() {
+        if selected(key) && !snapshot.files.contains_key(key) {
+            std::fs::remove_file(root.join(key))?;
+            report.removed.push(key.clone());
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn save_dedupes_content() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.txt"), "same").unwrap();
+        fs::write(dir.path().join("b.txt"), "same").unwrap();
+
+        let snap = save(dir.path(), Some("first")).unwrap();
+        assert_eq!(snap.files.len(), 2);
+        assert_eq!(snap.files["a.txt"], snap.files["b.txt"]);
+        let objects = fs::read_dir(dir.path().join(SNAPSHOT_DIR).join(OBJECTS_DIR)).unwrap().count();
+        assert_eq!(objects, 1);
+
+        let listed = list(dir.path()).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].message.as_deref(), Some("first"));
+    }
+
+    #[test]
+    fn full_restore_reverts_edits_and_removes_new_files() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/main.rs"), "original").unwrap();
+        fs::write(dir.path().join("gone.txt"), "keep me").unwrap();
+        let snap = save(dir.path(), None).unwrap();
+
+        fs::write(dir.path().join("src/main.rs"), "broken by AI").unwrap();
+        fs::remove_file(dir.path().join("gone.txt")).unwrap();
+        fs::write(dir.path().join("extra.txt"), "new").unwrap();
+
+        let report = restore(dir.path(), &load(dir.path(), &snap.id).unwrap(), &[]).unwrap();
+        assert_eq!(fs::read_to_string(dir.path().join("src/main.rs")).unwrap(), "original");
+        assert_eq!(fs::read_to_string(dir.path().join("gone.txt")).unwrap(), "keep me");
+        assert!(!dir.path().join("extra.txt").exists());
+        assert_eq!(report.removed, vec!["extra.txt"]);
+    }
+
+    #[test]
+    fn selective_restore_only_touches_given_paths() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "v1").unwrap();
+        fs::write(dir.path().join("README.md"), "v1").unwrap();
+        let snap = save(dir.path(), None).unwrap();
+
+        fs::write(dir.path().join("src/lib.rs"), "v2").unwrap();
+        fs::write(dir.path().join("README.md"), "v2").unwrap();
+        fs::write(dir.path().join("notes.txt"), "new").unwrap();
+
+        let report = restore(dir.path(), &snap, &["src/".to_string()]).unwrap();
+        assert_eq!(report.written, vec!["src/lib.rs"]);
+        assert_eq!(fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(), "v1");
+        assert_eq!(fs::read_to_string(dir.path().join("README.md")).unwrap(), "v2");
+        assert!(dir.path().join("notes.txt").exists());
+    }
+}
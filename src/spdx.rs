@@ -0,0 +1,208 @@
+use anyhow::Result;
+
+use crate::utils;
+
+/// SPDX license identifiers accepted for `--license` (the commonly used subset of
+/// https://spdx.org/licenses/, including deprecated GPL-family short forms).
+const LICENSE_IDS: &[&str] = &[
+    "0BSD", "AFL-3.0", "AGPL-3.0", "AGPL-3.0-only", "AGPL-3.0-or-later", "Apache-1.1",
+    "Apache-2.0", "Artistic-2.0", "BlueOak-1.0.0", "BSD-1-Clause", "BSD-2-Clause",
+    "BSD-2-Clause-Patent", "BSD-3-Clause", "BSD-3-Clause-Clear", "BSD-4-Clause", "BSL-1.0",
+    "BUSL-1.1", "CC-BY-4.0", "CC-BY-NC-4.0", "CC-BY-NC-SA-4.0", "CC-BY-ND-4.0",
+    "CC-BY-SA-4.0", "CC0-1.0", "CDDL-1.0", "CDDL-1.1", "CECILL-2.1", "ECL-2.0", "EPL-1.0",
+    "EPL-2.0", "EUPL-1.1", "EUPL-1.2", "GFDL-1.3-only", "GFDL-1.3-or-later", "GPL-2.0",
+    "GPL-2.0-only", "GPL-2.0-or-later", "GPL-3.0", "GPL-3.0-only", "GPL-3.0-or-later", "ISC",
+    "LGPL-2.0", "LGPL-2.0-only", "LGPL-2.0-or-later", "LGPL-2.1", "LGPL-2.1-only",
+    "LGPL-2.1-or-later", "LGPL-3.0", "LGPL-3.0-only", "LGPL-3.0-or-later", "LPPL-1.3c", "MIT",
+    "MIT-0", "MPL-1.1", "MPL-2.0", "MPL-2.0-no-copyleft-exception", "MS-PL", "MS-RL",
+    "MulanPSL-2.0", "NCSA", "ODbL-1.0", "OFL-1.1", "OpenSSL", "OSL-3.0", "PostgreSQL",
+    "PSF-2.0", "Python-2.0", "Ruby", "SSPL-1.0", "Unicode-3.0", "Unicode-DFS-2016",
+    "Unlicense", "UPL-1.0", "Vim", "W3C", "WTFPL", "X11", "Zlib", "ZPL-2.1",
+];
+
+/// SPDX exception identifiers accepted after `WITH`.
+const EXCEPTION_IDS: &[&str] = &[
+    "Autoconf-exception-3.0", "Bison-exception-2.2", "Classpath-exception-2.0",
+    "Font-exception-2.0", "GCC-exception-3.1", "GPL-CC-1.0", "Linux-syscall-note",
+    "LLVM-exception", "OpenJDK-assembly-exception-1.0", "Qt-GPL-exception-1.0",
+    "Qt-LGPL-exception-1.1", "Swift-exception", "u-boot-exception-2.0",
+    "WxWindows-exception-3.1",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    With,
+    Word(String),
+}
+
+fn tokenize(expr: &str) -> Vec<Token> {
+    let spaced = expr.replace('(', " ( ").replace(')', " ) ");
+    spaced
+        .split_whitespace()
+        .map(|w| match w {
+            "(" => Token::Open,
+            ")" => Token::Close,
+            _ if w.eq_ignore_ascii_case("AND") => Token::And,
+            _ if w.eq_ignore_ascii_case("OR") => Token::Or,
+            _ if w.eq_ignore_ascii_case("WITH") => Token::With,
+            _ => Token::Word(w.to_string()),
+        })
+        .collect()
+}
+
+/// Canonical spelling of `word` from `ids` (matched case-insensitively), or an
+/// error naming the closest known identifiers.
+fn lookup(word: &str, ids: &[&'static str], kind: &str) -> Result<&'static str> {
+    if let Some(id) = ids.iter().find(|id| id.eq_ignore_ascii_case(word)) {
+        return Ok(id);
+    }
+    let suggestions = utils::closest_matches(word, ids, 3);
+    let hint = if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(" Did you mean: {}?", suggestions.join(", "))
+    };
+    Err(anyhow::anyhow!(
+        "Unknown SPDX {} '{}'.{} Use LicenseRef-<name> for a custom license.",
+        kind, word, hint
+    ))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    // or_expr := and_expr ("OR" and_expr)*
+    fn or_expr(&mut self) -> Result<String> {
+        let mut out = self.and_expr()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            out = format!("{} OR {}", out, self.and_expr()?);
+        }
+        Ok(out)
+    }
+
+    // and_expr := term ("AND" term)*
+    fn and_expr(&mut self) -> Result<String> {
+        let mut out = self.term()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            out = format!("{} AND {}", out, self.term()?);
+        }
+        Ok(out)
+    }
+
+    // term := "(" or_expr ")" | license ["WITH" exception]
+    fn term(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Open) => {
+                let inner = self.or_expr()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(format!("({})", inner)),
+                    _ => Err(anyhow::anyhow!("Unbalanced parentheses in license expression")),
+                }
+            }
+            Some(Token::Word(word)) => {
+                let license = license_id(&word)?;
+                if self.peek() == Some(&Token::With) {
+                    self.pos += 1;
+                    match self.next() {
+                        Some(Token::Word(exception)) => {
+                            Ok(format!("{} WITH {}", license, lookup(&exception, EXCEPTION_IDS, "exception")?))
+                        }
+                        _ => Err(anyhow::anyhow!("Expected an exception identifier after WITH")),
+                    }
+                } else {
+                    Ok(license)
+                }
+            }
+            Some(t) => Err(anyhow::anyhow!("Unexpected {:?} in license expression", t)),
+            None => Err(anyhow::anyhow!("Incomplete license expression")),
+        }
+    }
+}
+
+/// A single license: a known identifier (optionally with `+`) or `LicenseRef-*`.
+fn license_id(word: &str) -> Result<String> {
+    if word.len() > "LicenseRef-".len() && word.get(.."LicenseRef-".len()).is_some_and(|p| p.eq_ignore_ascii_case("LicenseRef-")) {
+        return Ok(format!("LicenseRef-{}", &word["LicenseRef-".len()..]));
+    }
+    let (base, plus) = match word.strip_suffix('+') {
+        Some(base) => (base, "+"),
+        None => (word, ""),
+    };
+    Ok(format!("{}{}", lookup(base, LICENSE_IDS, "license")?, plus))
+}
+
+/// Validate an SPDX license expression (e.g. `Apache-2.0 OR MIT`) and return it
+/// with canonical identifier casing and uppercase operators.
+pub fn normalize(expr: &str) -> Result<String> {
+    let mut parser = Parser { tokens: tokenize(expr), pos: 0 };
+    if parser.tokens.is_empty() {
+        return Err(anyhow::anyhow!("License expression is empty"));
+    }
+    let out = parser.or_expr()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(anyhow::anyhow!(
+            "Unexpected {:?} in license expression. Combine licenses with AND / OR.",
+            parser.tokens[parser.pos]
+        ));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_and_normalizes_expressions() {
+        assert_eq!(normalize("mit").unwrap(), "MIT");
+        assert_eq!(normalize("Apache-2.0 or MIT").unwrap(), "Apache-2.0 OR MIT");
+        assert_eq!(
+            normalize("(MIT AND bsd-3-clause) OR GPL-2.0-or-later WITH classpath-exception-2.0").unwrap(),
+            "(MIT AND BSD-3-Clause) OR GPL-2.0-or-later WITH Classpath-exception-2.0"
+        );
+        assert_eq!(normalize("GPL-2.0+").unwrap(), "GPL-2.0+");
+        assert_eq!(normalize("LicenseRef-Acme-EULA").unwrap(), "LicenseRef-Acme-EULA");
+    }
+
+    #[test]
+    fn rejects_unknown_ids_with_suggestions() {
+        let err = normalize("Apache2").unwrap_err().to_string();
+        assert!(err.contains("Unknown SPDX license 'Apache2'"));
+        assert!(err.contains("Apache-2.0"));
+
+        let err = normalize("MIT WITH made-up-exception").unwrap_err().to_string();
+        assert!(err.contains("exception"));
+
+        // Multibyte text must not be sliced mid-character
+        let err = normalize("日本語ライセンス").unwrap_err().to_string();
+        assert!(err.contains("Unknown SPDX license '日本語ライセンス'"));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        assert!(normalize("").is_err());
+        assert!(normalize("MIT OR").is_err());
+        assert!(normalize("(MIT OR Apache-2.0").is_err());
+        assert!(normalize("MIT Apache-2.0").is_err());
+        assert!(normalize("MIT WITH").is_err());
+    }
+}
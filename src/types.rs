@@ -14,6 +14,12 @@ pub struct Publisher {
     pub display_name: Option<String>,
     pub personal_tier: String,
     pub storage_used_bytes: i64,
+    /// Storage quota for the account, when the registry reports it
+    #[serde(default)]
+    pub storage_limit_bytes: Option<i64>,
+    /// Largest archive a single release may upload, when the registry reports it
+    #[serde(default)]
+    pub max_archive_bytes: Option<i64>,
     pub publish_cooldown_until: Option<String>,
 }
 
@@ -24,7 +30,7 @@ pub struct CategoriesResponse {
     pub categories: Vec<Category>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Category {
     pub id: i64,
     pub slug: String,
@@ -34,6 +40,24 @@ pub struct Category {
 
 // -- Products --
 
+/// Filters for `GET /api/products`.
+#[derive(Debug, Default)]
+pub struct ProductFilters<'a> {
+    pub category: Option<&'a str>,
+    /// Products must carry every one of these tags
+    pub tags: &'a [String],
+    /// Only products of this publisher
+    pub publisher: Option<&'a str>,
+    /// Never products of these publishers
+    pub exclude_publishers: &'a [String],
+    /// Average rating at least this (1-5)
+    pub min_rating: Option<f64>,
+    /// At least this many forks
+    pub min_forks: Option<u64>,
+    /// Only published remakes of this `user/slug`
+    pub remakes_of: Option<&'a str>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ProductsResponse {
     pub products: Vec<Product>,
@@ -42,7 +66,7 @@ pub struct ProductsResponse {
     pub limit: u64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Product {
     pub id: String,
     pub slug: String,
@@ -57,21 +81,105 @@ pub struct Product {
     pub publisher: Option<PublisherRef>,
     pub category: Option<CategoryRef>,
     pub stats: Option<ProductStats>,
+    #[serde(default, flatten)]
+    pub links: ProductLinks,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Present on search results when `explain=1` was requested
+    #[serde(default)]
+    pub ranking: Option<RankingExplanation>,
+    /// Set when the publisher deprecated the product (`baro deprecate`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_message: Option<String>,
+    /// `public`, `unlisted` or `private`; older registries only send `is_private`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+impl Product {
+    pub fn visibility(&self) -> &str {
+        match self.visibility.as_deref() {
+            Some(v) => v,
+            None if self.is_private => "private",
+            None if self.review_status == "unlisted" => "unlisted",
+            None => "public",
+        }
+    }
+
+    /// The warning for consumers of a deprecated product.
+    pub fn deprecation_notice(&self, id: &str) -> Option<String> {
+        self.deprecation_message.as_deref().map(|msg| format!("Warning: {} is deprecated: {}", id, msg))
+    }
+}
+
+/// Homepage, source repository and documentation URLs shown on the product page.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProductLinks {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<String>,
+}
+
+impl ProductLinks {
+    pub fn is_empty(&self) -> bool {
+        self.homepage.is_none() && self.repository.is_none() && self.documentation.is_none()
+    }
+
+    /// Fields set in `self`, falling back to `other`.
+    pub fn or(self, other: ProductLinks) -> ProductLinks {
+        ProductLinks {
+            homepage: self.homepage.or(other.homepage),
+            repository: self.repository.or(other.repository),
+            documentation: self.documentation.or(other.documentation),
+        }
+    }
+
+    /// Fields of `self` that are set where `current` has none.
+    pub fn missing_from(&self, current: &ProductLinks) -> ProductLinks {
+        let fill = |new: &Option<String>, old: &Option<String>| new.clone().filter(|_| old.is_none());
+        ProductLinks {
+            homepage: fill(&self.homepage, &current.homepage),
+            repository: fill(&self.repository, &current.repository),
+            documentation: fill(&self.documentation, &current.documentation),
+        }
+    }
+
+    /// Fields of `self` that are set and differ from `current`.
+    pub fn changed_from(&self, current: &ProductLinks) -> ProductLinks {
+        let diff = |new: &Option<String>, old: &Option<String>| new.clone().filter(|n| old.as_ref() != Some(n));
+        ProductLinks {
+            homepage: diff(&self.homepage, &current.homepage),
+            repository: diff(&self.repository, &current.repository),
+            documentation: diff(&self.documentation, &current.documentation),
+        }
+    }
+}
+
+/// How a search result's score was computed: component name → weighted contribution.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RankingExplanation {
+    #[serde(default)]
+    pub score: Option<f64>,
+    #[serde(default)]
+    pub components: std::collections::BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PublisherRef {
     pub username: String,
     pub display_name: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CategoryRef {
     pub slug: String,
     pub name: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ProductStats {
     #[serde(default)]
     pub fork_count: Option<u64>,
@@ -83,13 +191,40 @@ pub struct ProductStats {
     pub rating_count: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct CreateProductRequest {
-    pub slug: String,
-    pub name: String,
-    pub description: String,
-    pub category_slug: String,
-    pub license: String,
+#[derive(Debug, Serialize)]
+pub struct CreateProductRequest<'a> {
+    pub slug: &'a str,
+    pub name: &'a str,
+    pub description: &'a str,
+    pub category_slug: &'a str,
+    pub license: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<&'a str>,
+    pub is_private: bool,
+    /// Team namespace to create the product under (default: the caller)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team: Option<&'a str>,
+    #[serde(flatten)]
+    pub links: ProductLinks,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    pub tags: &'a [String],
+}
+
+/// `PATCH /api/products/{id}`: only the fields being changed are sent.
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateProductRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(flatten)]
+    pub links: ProductLinks,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -104,13 +239,50 @@ pub struct ReleasesResponse {
     pub releases: Vec<Release>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Release {
     pub id: String,
     pub version: String,
     pub changelog: Option<String>,
     pub file_size_bytes: Option<i64>,
     pub created_at: String,
+    /// `published`, `preview`, `pending_review` or `yanked`; absent means published
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Owner's view only: `pending`, `uploaded` or `failed`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_status: Option<String>,
+    /// Owner's view only: `pending_review`, `approved` or `rejected`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_status: Option<String>,
+}
+
+impl Release {
+    pub fn is_yanked(&self) -> bool {
+        self.status.as_deref() == Some("yanked")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReadmeResponse {
+    #[serde(default)]
+    pub readme: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateReleaseRequest<'a> {
+    pub version: &'a str,
+    pub changelog: &'a str,
+    pub file_size_bytes: i64,
+    pub file_hash_sha256: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readme: Option<&'a str>,
+    /// Hex ed25519 signature over `file_hash_sha256`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<&'a str>,
+    /// Preview release: only forkable with the returned preview token until promoted
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub preview: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -120,12 +292,48 @@ pub struct CreateReleaseResponse {
     pub upload_expires_in: u64,
 }
 
+/// An extra file published next to the source archive (e.g. a prebuilt binary).
+#[derive(Debug, Serialize)]
+pub struct CreateAssetRequest<'a> {
+    pub name: &'a str,
+    pub file_size_bytes: i64,
+    pub file_hash_sha256: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAssetResponse {
+    pub upload_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssetsResponse {
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub file_size_bytes: i64,
+    pub file_hash_sha256: String,
+    pub download_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UploadUrlResponse {
+    pub upload_url: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ConfirmResponse {
     pub release_id: String,
     pub upload_status: String,
     #[serde(default)]
     pub review_status: Option<String>,
+    /// Set for preview releases
+    #[serde(default)]
+    pub preview_token: Option<String>,
+    #[serde(default)]
+    pub preview_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -134,6 +342,218 @@ pub struct DownloadResponse {
     pub expires_in: u64,
     pub file_size_bytes: i64,
     pub file_hash_sha256: String,
+    #[serde(default)]
+    pub signature: Option<String>,
+    #[serde(default)]
+    pub release_id: Option<String>,
+}
+
+// -- Signing keys --
+
+#[derive(Debug, Deserialize)]
+pub struct SigningKeysResponse {
+    pub keys: Vec<SigningKeyInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SigningKeyInfo {
+    pub public_key: String,
+}
+
+// -- Stats --
+
+/// Download totals for a product. Platform breakdowns only count consumers
+/// who opted in to sharing client hints.
+#[derive(Debug, Deserialize)]
+pub struct DownloadStats {
+    pub total_downloads: u64,
+    #[serde(default)]
+    pub by_os: Vec<StatBucket>,
+    #[serde(default)]
+    pub by_cli_version: Vec<StatBucket>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatBucket {
+    pub value: String,
+    pub count: u64,
+}
+
+/// Publisher analytics for one product over the requested window.
+#[derive(Debug, Deserialize)]
+pub struct ProductAnalytics {
+    #[serde(default)]
+    pub versions: Vec<VersionAnalytics>,
+    /// One entry per day, oldest first (days without downloads included)
+    #[serde(default)]
+    pub daily_downloads: Vec<DailyCount>,
+    /// Average rating per month, oldest first
+    #[serde(default)]
+    pub rating_trend: Vec<RatingPoint>,
+    #[serde(default)]
+    pub remake_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VersionAnalytics {
+    pub version: String,
+    #[serde(default)]
+    pub forks: u64,
+    #[serde(default)]
+    pub downloads: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DailyCount {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RatingPoint {
+    /// `YYYY-MM`
+    pub period: String,
+    pub avg_rating: Option<f64>,
+    #[serde(default)]
+    pub count: u64,
+}
+
+// -- Reviews --
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewsResponse {
+    pub reviews: Vec<Review>,
+}
+
+/// A rating (1-5) with optional text, and the publisher's reply if any.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Review {
+    pub id: String,
+    pub rating: u8,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub author: Option<PublisherRef>,
+    pub created_at: String,
+    #[serde(default)]
+    pub reply: Option<ReviewReply>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReviewReply {
+    pub body: String,
+    pub created_at: String,
+}
+
+// -- Provenance --
+
+/// A provenance statement as attached to a release: the exact JSON payload,
+/// signed with the publisher's key.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignedProvenance {
+    pub payload: String,
+    pub signature: String,
+    pub public_key: String,
+}
+
+// -- Moderation --
+
+#[cfg(feature = "moderation")]
+#[derive(Debug, Deserialize)]
+pub struct ReviewQueueResponse {
+    pub releases: Vec<PendingRelease>,
+}
+
+#[cfg(feature = "moderation")]
+#[derive(Debug, Deserialize)]
+pub struct PendingRelease {
+    pub id: String,
+    pub version: String,
+    pub product_slug: String,
+    pub publisher: String,
+    #[serde(default)]
+    pub file_size_bytes: Option<i64>,
+    pub created_at: String,
+}
+
+// -- Teams --
+
+#[derive(Debug, Deserialize)]
+pub struct TeamsResponse {
+    pub teams: Vec<TeamMembership>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamMembership {
+    pub slug: String,
+    pub role: String,
+}
+
+// -- Publish result (`baro publish --output json`) --
+
+#[derive(Debug, Serialize)]
+pub struct PublishResult {
+    pub schema_version: u32,
+    pub product_id: String,
+    pub namespace: String,
+    pub slug: String,
+    pub version: String,
+    pub release_id: String,
+    pub archive_size_bytes: i64,
+    pub sha256: String,
+    /// published, unlisted, pending_review or preview
+    pub review_status: String,
+    pub product_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview_url: Option<String>,
+}
+
+// -- Product media --
+
+#[derive(Debug, Serialize)]
+pub struct CreateMediaRequest<'a> {
+    pub filename: &'a str,
+    pub content_type: &'a str,
+    pub file_size_bytes: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateMediaResponse {
+    pub media_id: String,
+    pub upload_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MediaResponse {
+    pub media: Vec<ProductMedia>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProductMedia {
+    pub id: String,
+    pub url: String,
+    pub content_type: String,
+    pub file_size_bytes: i64,
+    #[serde(default)]
+    pub caption: Option<String>,
+    pub created_at: String,
+}
+
+// -- Slugs --
+
+/// Slug availability: globally (any publisher) and in the caller's namespace.
+#[derive(Debug, Deserialize)]
+pub struct SlugAvailability {
+    pub available_globally: bool,
+    /// None when the request was anonymous
+    #[serde(default)]
+    pub available_for_you: Option<bool>,
+    /// Namespaces already using the slug (`user` or team names)
+    #[serde(default)]
+    pub taken_by: Vec<String>,
 }
 
 // -- My Products --
@@ -141,6 +561,9 @@ pub struct DownloadResponse {
 #[derive(Debug, Deserialize)]
 pub struct MyProductsResponse {
     pub products: Vec<Product>,
+    /// Total across all pages, when the registry paginates
+    #[serde(default)]
+    pub total: Option<u64>,
 }
 
 // -- Error --
@@ -148,6 +571,10 @@ pub struct MyProductsResponse {
 #[derive(Debug, Deserialize)]
 pub struct ApiError {
     pub error: String,
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default)]
+    pub hint: Option<String>,
 }
 
 // -- Manifest --
@@ -161,6 +588,21 @@ pub struct Manifest {
     pub cloned_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub file_hash: Option<String>,
+    /// Upstream version and release ID that were forked (`version` changes once
+    /// the fork is published under its own name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin_release_id: Option<String>,
+    /// Further products this one tracks besides `origin` (e.g. another remake)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remotes: Vec<Remote>,
+    /// Origin releases `baro upstream` shouldn't offer (`baro upstream ignore`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignored_versions: Vec<String>,
+    /// Origin releases above this aren't offered (`baro upstream pin`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned_version: Option<String>,
 
     // Publish identity (present for published products)
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -170,10 +612,152 @@ pub struct Manifest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub publisher: Option<String>,
 
+    /// Shell command forkers are offered after unpacking (e.g.
+    /// `npm install && cp .env.example .env`), run from the product root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<String>,
+    /// Other products this one builds on (`user/product` or `user/product@version`),
+    /// fetched into `vendor/` when it is forked
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+
     // Version (always present)
     pub version: String,
 }
 
+/// A tracked upstream: `origin` itself (named `PRIMARY_REMOTE`) or an entry of `remotes`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Remote {
+    pub name: String,
+    /// `user/product`
+    pub origin: String,
+    /// Release last forked or merged from it
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignored_versions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned_version: Option<String>,
+}
+
+/// Name `baro upstream --remote` uses for the manifest's own `origin`.
+pub const PRIMARY_REMOTE: &str = "origin";
+
+impl Manifest {
+    /// The upstream version this fork was taken from.
+    pub fn forked_version(&self) -> &str {
+        self.origin_version.as_deref().unwrap_or(&self.version)
+    }
+
+    /// The named upstream, or `origin` when `name` is None.
+    pub fn upstream(&self, name: Option<&str>) -> anyhow::Result<Remote> {
+        match name.filter(|n| *n != PRIMARY_REMOTE) {
+            None => {
+                let origin = self.origin.clone().ok_or_else(|| {
+                    anyhow::anyhow!("No fork origin in manifest. This product was not forked.")
+                })?;
+                Ok(Remote {
+                    name: PRIMARY_REMOTE.to_string(),
+                    origin,
+                    version: self.forked_version().to_string(),
+                    release_id: self.origin_release_id.clone(),
+                    file_hash: self.file_hash.clone(),
+                    ignored_versions: self.ignored_versions.clone(),
+                    pinned_version: self.pinned_version.clone(),
+                })
+            }
+            Some(name) => self.remotes.iter().find(|r| r.name == name).cloned().ok_or_else(|| {
+                let known: Vec<&str> = self.remotes.iter().map(|r| r.name.as_str()).collect();
+                anyhow::anyhow!(
+                    "No upstream named '{}' (known: {})",
+                    name,
+                    std::iter::once(PRIMARY_REMOTE).chain(known).collect::<Vec<_>>().join(", ")
+                )
+            }),
+        }
+    }
+
+    /// Record that the tree now includes `version` of upstream `name`. For
+    /// `origin`, an unpublished fork's own version follows it.
+    pub fn advance(&mut self, name: &str, version: &str, release_id: Option<String>, file_hash: Option<String>) {
+        if name == PRIMARY_REMOTE {
+            if self.slug.is_none() {
+                self.version = version.to_string();
+            }
+            self.origin_version = Some(version.to_string());
+            self.origin_release_id = release_id;
+            self.file_hash = file_hash;
+        } else if let Some(remote) = self.remotes.iter_mut().find(|r| r.name == name) {
+            remote.version = version.to_string();
+            remote.release_id = release_id;
+            remote.file_hash = file_hash;
+        }
+    }
+
+    /// Store upstream `name`'s pin and ignore list.
+    pub fn set_policy(&mut self, name: &str, pinned_version: Option<String>, ignored_versions: Vec<String>) {
+        if name == PRIMARY_REMOTE {
+            self.pinned_version = pinned_version;
+            self.ignored_versions = ignored_versions;
+        } else if let Some(remote) = self.remotes.iter_mut().find(|r| r.name == name) {
+            remote.pinned_version = pinned_version;
+            remote.ignored_versions = ignored_versions;
+        }
+    }
+}
+
+// -- Status (`baro status --json`) --
+
+/// Stable machine-readable status for editor integrations.
+/// Bump `schema_version` on any breaking change to this shape.
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    pub schema_version: u32,
+    pub manifest: ManifestHealth,
+    pub product: Option<String>,
+    pub product_id: Option<String>,
+    pub version: Option<String>,
+    pub origin: Option<OriginStatus>,
+    pub local_changes: Option<LocalChanges>,
+    pub auth: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManifestHealth {
+    pub present: bool,
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OriginStatus {
+    pub origin: String,
+    pub forked_at: Option<String>,
+    pub upstream_latest: Option<String>,
+    pub upstream_checked_at: Option<String>,
+    pub up_to_date: Option<bool>,
+    /// Files changed against `.baro/files.json`; null for forks without one
+    pub since_fork: Option<ForkChanges>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ForkChanges {
+    /// Upstream version the comparison is against
+    pub base_version: String,
+    pub modified: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LocalChanges {
+    pub source: String,
+    pub changed_files: usize,
+}
+
 // -- Supabase token refresh --
 
 #[derive(Debug, Deserialize)]
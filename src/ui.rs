@@ -0,0 +1,303 @@
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
+
+use crate::utils;
+
+/// Set by `--accessible`, `BARO_ACCESSIBLE=1` or `TERM=dumb`.
+static ACCESSIBLE: AtomicBool = AtomicBool::new(false);
+
+/// How often long transfers report progress in accessible mode.
+const STATUS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How timestamps are shown, from `--time-format`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, clap::ValueEnum)]
+pub enum TimeFormat {
+    /// "2 days ago (2024-06-01 14:03 UTC+09:00)" in the local timezone
+    Local,
+    /// Like local, but in UTC
+    Utc,
+    /// RFC 3339 in UTC, for scripts
+    Iso,
+}
+
+static TIME_FORMAT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_time_format(format: TimeFormat) {
+    TIME_FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+fn time_format() -> TimeFormat {
+    match TIME_FORMAT.load(Ordering::Relaxed) {
+        1 => TimeFormat::Utc,
+        2 => TimeFormat::Iso,
+        _ => TimeFormat::Local,
+    }
+}
+
+/// A registry or manifest timestamp in the `--time-format` style. Values
+/// that don't parse as RFC 3339 are shown unchanged.
+pub fn timestamp(ts: &str) -> String {
+    format_timestamp(ts, time_format(), &Local, Utc::now())
+}
+
+/// `local` is the zone for `TimeFormat::Local`; each timestamp gets the offset
+/// in effect at that moment, so times across a DST change stay right.
+pub fn format_timestamp<Tz: TimeZone>(ts: &str, format: TimeFormat, local: &Tz, now: DateTime<Utc>) -> String {
+    let Ok(t) = DateTime::parse_from_rfc3339(ts) else {
+        return ts.to_string();
+    };
+    let t = t.with_timezone(&Utc);
+    let (offset, zone) = match format {
+        TimeFormat::Iso => return t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        TimeFormat::Utc => (FixedOffset::east_opt(0).unwrap(), "UTC".to_string()),
+        TimeFormat::Local => {
+            let offset = t.with_timezone(local).offset().fix();
+            let zone = if offset.local_minus_utc() == 0 { "UTC".to_string() } else { format!("UTC{}", offset) };
+            (offset, zone)
+        }
+    };
+    format!(
+        "{} ({} {})",
+        relative((now - t).num_seconds()),
+        t.with_timezone(&offset).format("%Y-%m-%d %H:%M"),
+        zone
+    )
+}
+
+/// Like `timestamp`, without the absolute time: for table cells.
+pub fn short_timestamp(ts: &str) -> String {
+    match (time_format(), DateTime::parse_from_rfc3339(ts)) {
+        (TimeFormat::Iso, _) | (_, Err(_)) => timestamp(ts),
+        (_, Ok(t)) => relative((Utc::now() - t.with_timezone(&Utc)).num_seconds()),
+    }
+}
+
+/// "3 hours ago", "in 2 days", "just now".
+pub fn relative(seconds_ago: i64) -> String {
+    let secs = seconds_ago.abs();
+    if secs < 60 {
+        return "just now".to_string();
+    }
+    let (n, unit) = match secs {
+        s if s < 3600 => (s / 60, "minute"),
+        s if s < 86_400 => (s / 3600, "hour"),
+        s if s < 30 * 86_400 => (s / 86_400, "day"),
+        s if s < 365 * 86_400 => (s / (30 * 86_400), "month"),
+        s => (s / (365 * 86_400), "year"),
+    };
+    let unit = if n == 1 { unit.to_string() } else { format!("{}s", unit) };
+    if seconds_ago < 0 {
+        format!("in {} {}", n, unit)
+    } else {
+        format!("{} {} ago", n, unit)
+    }
+}
+
+pub fn init(flag: bool) {
+    let env = |k: &str| std::env::var(k).ok();
+    let on = flag
+        || env("TERM").is_some_and(|t| t == "dumb")
+        || env("BARO_ACCESSIBLE").is_some_and(|v| !v.is_empty() && v != "0");
+    ACCESSIBLE.store(on, Ordering::Relaxed);
+}
+
+/// Screen-reader friendly output: plain words instead of symbols and
+/// decorations, and periodic status lines for long transfers.
+pub fn accessible() -> bool {
+    ACCESSIBLE.load(Ordering::Relaxed)
+}
+
+/// "→" in normal output, "to" in accessible mode.
+pub fn arrow() -> &'static str {
+    if accessible() {
+        "to"
+    } else {
+        "→"
+    }
+}
+
+/// A horizontal rule for emphasis; empty in accessible mode.
+pub fn rule(width: usize) -> String {
+    if accessible() {
+        String::new()
+    } else {
+        "=".repeat(width)
+    }
+}
+
+/// `values` as a one-line bar chart, scaled to the largest. Accessible mode
+/// gets a plain summary instead, since block characters read poorly aloud.
+pub fn sparkline(values: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().copied().max().unwrap_or(0);
+    if accessible() {
+        let total: u64 = values.iter().sum();
+        return format!("total {}, daily peak {}", total, max);
+    }
+    values
+        .iter()
+        .map(|&v| match v {
+            0 => ' ',
+            // Anything above zero shows at least the lowest bar
+            v => BARS[((v * 8).div_ceil(max) as usize - 1).min(7)],
+        })
+        .collect()
+}
+
+/// (columns, rows) of the terminal on stdout; None when stdout isn't one.
+pub fn terminal_size() -> Option<(usize, usize)> {
+    use std::io::IsTerminal;
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    #[cfg(unix)]
+    {
+        // SAFETY: TIOCGWINSZ fills in the winsize struct we pass
+        let mut ws = unsafe { std::mem::zeroed::<libc::winsize>() };
+        if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut ws) } == 0 && ws.ws_col > 0 {
+            return Some((usize::from(ws.ws_col), usize::from(ws.ws_row)));
+        }
+    }
+    let env = |k: &str| std::env::var(k).ok()?.parse::<usize>().ok().filter(|n| *n > 0);
+    Some((env("COLUMNS").unwrap_or(80), env("LINES").unwrap_or(24)))
+}
+
+/// Narrowest a column is cut to when a table doesn't fit.
+const MIN_COLUMN: usize = 8;
+
+/// `rows` aligned under `headers`, each column as wide as its widest cell.
+/// With `max_width`, lines are made to fit by cutting the `flex` column
+/// first, then the widest of the others.
+pub fn table(headers: &[&str], rows: &[Vec<String>], flex: usize, max_width: Option<usize>) -> Vec<String> {
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, h)| rows.iter().map(|r| r[i].chars().count()).chain([h.len()]).max().unwrap_or(0))
+        .collect();
+    if let Some(max) = max_width {
+        let gaps = 2 * widths.len().saturating_sub(1);
+        let mut total: usize = widths.iter().sum::<usize>() + gaps;
+        while total > max {
+            let shrink = if widths[flex] > MIN_COLUMN {
+                flex
+            } else {
+                match (0..widths.len()).filter(|&i| widths[i] > MIN_COLUMN).max_by_key(|&i| widths[i]) {
+                    Some(i) => i,
+                    None => break,
+                }
+            };
+            widths[shrink] -= 1;
+            total -= 1;
+        }
+    }
+    let last = widths.len().saturating_sub(1);
+    std::iter::once(headers.iter().map(|h| h.to_string()).collect::<Vec<_>>())
+        .chain(rows.iter().cloned())
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (cell, &w))| {
+                    let cell = utils::truncate_str(cell, w.max(4));
+                    // No trailing padding on the last column
+                    if i == last { cell } else { format!("{:<w$}", cell, w = w) }
+                })
+                .collect();
+            cells.join("  ")
+        })
+        .collect()
+}
+
+/// `label: 40% (12.0 MB of 30.0 MB)`, or just the byte count when the total is unknown.
+pub fn status_line(label: &str, done: u64, total: Option<u64>) -> String {
+    match total {
+        Some(total) if total > 0 => format!(
+            "{}: {}% ({} of {})",
+            label,
+            done.saturating_mul(100) / total,
+            utils::format_bytes(done as i64),
+            utils::format_bytes(total as i64)
+        ),
+        _ => format!("{}: {}", label, utils::format_bytes(done as i64)),
+    }
+}
+
+/// Periodic plain-text progress for a transfer. Silent outside accessible mode.
+pub struct Progress {
+    label: String,
+    total: Option<u64>,
+    last: Instant,
+}
+
+impl Progress {
+    pub fn new(label: impl Into<String>, total: Option<u64>) -> Self {
+        Self { label: label.into(), total, last: Instant::now() }
+    }
+
+    pub fn update(&mut self, done: u64) {
+        if accessible() && self.last.elapsed() >= STATUS_INTERVAL {
+            self.last = Instant::now();
+            eprintln!("{}", status_line(&self.label, done, self.total));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_lines_are_plain_text() {
+        let mb = 1024 * 1024;
+        assert_eq!(status_line("demo-1.0.0.tar.gz", 12 * mb, Some(30 * mb)), "demo-1.0.0.tar.gz: 40% (12.0 MB of 30.0 MB)");
+        assert_eq!(status_line("x", 2048, None), "x: 2.0 KB");
+        assert_eq!(status_line("x", 0, Some(0)), "x: 0 B");
+    }
+
+    #[test]
+    fn timestamps_relative_with_zone() {
+        let now = DateTime::parse_from_rfc3339("2024-06-03T05:03:00Z").unwrap().with_timezone(&Utc);
+        let kst = FixedOffset::east_opt(9 * 3600).unwrap();
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let ts = "2024-06-01T05:03:00Z";
+        assert_eq!(format_timestamp(ts, TimeFormat::Local, &kst, now), "2 days ago (2024-06-01 14:03 UTC+09:00)");
+        assert_eq!(format_timestamp(ts, TimeFormat::Local, &utc, now), "2 days ago (2024-06-01 05:03 UTC)");
+        assert_eq!(format_timestamp(ts, TimeFormat::Utc, &kst, now), "2 days ago (2024-06-01 05:03 UTC)");
+        assert_eq!(format_timestamp("2024-06-01T14:03:00+09:00", TimeFormat::Iso, &kst, now), "2024-06-01T05:03:00Z");
+        assert_eq!(format_timestamp("yesterday", TimeFormat::Local, &kst, now), "yesterday");
+    }
+
+    #[test]
+    fn sparkline_scales_to_peak() {
+        assert_eq!(sparkline(&[0, 1, 4, 8]), " ▁▄█");
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[0, 0]), "  ");
+    }
+
+    #[test]
+    fn tables_align_and_fit() {
+        let rows = vec![
+            vec!["alice/a-very-long-product-name".to_string(), "1.0.0".to_string(), "Scrapes the web for you".to_string()],
+            vec!["bob/x".to_string(), "12.3.4".to_string(), "-".to_string()],
+        ];
+        let lines = table(&["PRODUCT", "VERSION", "DESCRIPTION"], &rows, 2, None);
+        assert_eq!(lines[0], "PRODUCT                         VERSION  DESCRIPTION");
+        assert_eq!(lines[2], "bob/x                           12.3.4   -");
+
+        let lines = table(&["PRODUCT", "VERSION", "DESCRIPTION"], &rows, 2, Some(40));
+        assert!(lines.iter().all(|l| l.chars().count() <= 40), "{:?}", lines);
+        assert_eq!(lines[1], "alice/a-very-long-...  1.0.0    Scrap...");
+    }
+
+    #[test]
+    fn relative_units() {
+        assert_eq!(relative(5), "just now");
+        assert_eq!(relative(60), "1 minute ago");
+        assert_eq!(relative(7200), "2 hours ago");
+        assert_eq!(relative(-3 * 86_400), "in 3 days");
+        assert_eq!(relative(400 * 86_400), "1 year ago");
+    }
+}
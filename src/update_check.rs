@@ -2,16 +2,64 @@ use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::config;
+use crate::ui;
+use crate::utils;
 
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const CHECK_INTERVAL_SECS: u64 = 86400; // 24 hours
 const GITHUB_RELEASES_URL: &str =
     "https://api.github.com/repos/codetree21/baro-cli/releases/latest";
 
+const FAILURE_BACKOFF_SECS: u64 = 3600; // 1 hour
+
+/// Version info from the update endpoint. GitHub only provides the latest
+/// version; a registry endpoint may also set a minimum and its own install hint.
+#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct LatestInfo {
+    latest_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    minimum_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    install_hint: Option<String>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 struct CachedCheck {
-    latest_version: String,
+    /// Last successfully fetched version (kept across failed checks)
+    #[serde(default)]
+    latest_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    minimum_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    install_hint: Option<String>,
     checked_at: u64,
+    /// Set after a failed or rate-limited check: don't retry before this time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry_at: Option<u64>,
+    /// Endpoint the check came from; a different configured endpoint invalidates it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+}
+
+impl CachedCheck {
+    fn is_fresh(&self, now: u64) -> bool {
+        match self.retry_at {
+            Some(retry_at) => now < retry_at,
+            None => now.saturating_sub(self.checked_at) < CHECK_INTERVAL_SECS,
+        }
+    }
+}
+
+enum FetchOutcome {
+    Latest(LatestInfo),
+    Failed { retry_at: u64 },
+}
+
+/// The configured update endpoint (`update_check_url` in config.json), else GitHub releases.
+fn endpoint() -> String {
+    config::settings()
+        .update_check_url
+        .unwrap_or_else(|| GITHUB_RELEASES_URL.to_string())
 }
 
 fn cache_path() -> Option<PathBuf> {
@@ -36,61 +84,151 @@ pub fn spawn_check() -> tokio::task::JoinHandle<Option<String>> {
 async fn check_and_notify() -> Option<String> {
     // Read cache
     let path = cache_path()?;
-    if let Ok(data) = std::fs::read_to_string(&path) {
-        if let Ok(cached) = serde_json::from_str::<CachedCheck>(&data) {
-            if now_secs() - cached.checked_at < CHECK_INTERVAL_SECS {
-                return format_notice(&cached.latest_version);
-            }
+    let url = endpoint();
+    let source = (url != GITHUB_RELEASES_URL).then(|| url.clone());
+    let cached = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|data| serde_json::from_str::<CachedCheck>(&data).ok())
+        .filter(|c| c.source == source);
+    if let Some(ref cached) = cached {
+        if cached.is_fresh(now_secs()) {
+            return format_notice(cached);
         }
     }
 
-    // Fetch latest from GitHub
-    let latest = fetch_latest_version().await?;
-
-    // Write cache
-    let cached = CachedCheck {
-        latest_version: latest.clone(),
-        checked_at: now_secs(),
+    // Fetch latest; failures are cached too so they back off
+    let updated = match fetch_latest_version(&url).await {
+        FetchOutcome::Latest(info) => CachedCheck {
+            latest_version: Some(info.latest_version),
+            minimum_version: info.minimum_version,
+            install_hint: info.install_hint,
+            checked_at: now_secs(),
+            retry_at: None,
+            source,
+        },
+        FetchOutcome::Failed { retry_at } => {
+            let previous = cached.unwrap_or(CachedCheck {
+                latest_version: None,
+                minimum_version: None,
+                install_hint: None,
+                checked_at: 0,
+                retry_at: None,
+                source: None,
+            });
+            CachedCheck {
+                checked_at: now_secs(),
+                retry_at: Some(retry_at),
+                source,
+                ..previous
+            }
+        }
     };
-    if let Ok(json) = serde_json::to_string(&cached) {
-        let _ = std::fs::write(&path, json);
+
+    // Write cache atomically so concurrent runs never see a torn file
+    if let Ok(json) = serde_json::to_string(&updated) {
+        let _ = utils::write_atomic(&path, json.as_bytes());
     }
 
-    format_notice(&latest)
+    format_notice(&updated)
 }
 
-async fn fetch_latest_version() -> Option<String> {
-    let client = reqwest::Client::builder()
+/// Accepts GitHub's release format (`tag_name`) or a registry's
+/// `{ "latest_version", "minimum_version", "install_hint" }`.
+fn parse_latest(body: &serde_json::Value) -> Option<LatestInfo> {
+    if let Some(tag) = body["tag_name"].as_str() {
+        return Some(LatestInfo {
+            latest_version: tag.trim_start_matches('v').to_string(),
+            ..Default::default()
+        });
+    }
+    let mut info: LatestInfo = serde_json::from_value(body.clone()).ok()?;
+    info.latest_version = info.latest_version.trim_start_matches('v').to_string();
+    info.minimum_version = info.minimum_version.map(|v| v.trim_start_matches('v').to_string());
+    Some(info)
+}
+
+async fn fetch_latest_version(url: &str) -> FetchOutcome {
+    let failed = FetchOutcome::Failed {
+        retry_at: now_secs() + FAILURE_BACKOFF_SECS,
+    };
+    let client = match reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(3))
         .build()
-        .ok()?;
+    {
+        Ok(c) => c,
+        Err(_) => return failed,
+    };
 
-    let resp = client
-        .get(GITHUB_RELEASES_URL)
+    let resp = match client
+        .get(url)
         .header("User-Agent", format!("baro-cli/{}", CURRENT_VERSION))
         .send()
         .await
-        .ok()?;
+    {
+        Ok(r) => r,
+        Err(_) => return failed,
+    };
 
     if !resp.status().is_success() {
-        return None;
+        return FetchOutcome::Failed {
+            retry_at: retry_at_from_headers(resp.headers(), now_secs()),
+        };
     }
 
-    let body: serde_json::Value = resp.json().await.ok()?;
-    let tag = body["tag_name"].as_str()?;
-    Some(tag.trim_start_matches('v').to_string())
+    let body: serde_json::Value = match resp.json().await {
+        Ok(b) => b,
+        Err(_) => return failed,
+    };
+    match parse_latest(&body) {
+        Some(info) => FetchOutcome::Latest(info),
+        None => failed,
+    }
 }
 
-fn format_notice(latest: &str) -> Option<String> {
+/// When to retry after a failed response, honoring GitHub's rate-limit headers
+/// (`x-ratelimit-reset` when the quota is exhausted, else `retry-after`).
+fn retry_at_from_headers(headers: &reqwest::header::HeaderMap, now: u64) -> u64 {
+    let header_u64 = |name: &str| -> Option<u64> {
+        headers.get(name)?.to_str().ok()?.trim().parse().ok()
+    };
+    if header_u64("x-ratelimit-remaining") == Some(0) {
+        if let Some(reset) = header_u64("x-ratelimit-reset") {
+            return reset.max(now + 60);
+        }
+    }
+    if let Some(secs) = header_u64("retry-after") {
+        return now + secs.max(60);
+    }
+    now + FAILURE_BACKOFF_SECS
+}
+
+fn format_notice(cached: &CachedCheck) -> Option<String> {
+    let hint = match cached.install_hint {
+        Some(ref hint) => format!("  Run: {}", hint),
+        None if cfg!(windows) => {
+            "  Run in Git Bash: curl -fsSL https://raw.githubusercontent.com/codetree21/baro-cli/main/install.sh | sh".to_string()
+        }
+        None => {
+            "  Run: curl -fsSL https://raw.githubusercontent.com/codetree21/baro-cli/main/install.sh | sh".to_string()
+        }
+    };
+
+    if let Some(ref minimum) = cached.minimum_version {
+        if is_newer(minimum, CURRENT_VERSION) {
+            let bar = ui::rule(60);
+            return Some(format!(
+                "\n{}\nWARNING: baro v{} is below the minimum supported version (v{}).\n\
+                Some commands may fail against this registry. Update now:\n{}\n{}",
+                bar, CURRENT_VERSION, minimum, hint, bar
+            ));
+        }
+    }
+
+    let latest = cached.latest_version.as_deref()?;
     if is_newer(latest, CURRENT_VERSION) {
-        let hint = if cfg!(windows) {
-            "  Run in Git Bash: curl -fsSL https://raw.githubusercontent.com/codetree21/baro-cli/main/install.sh | sh"
-        } else {
-            "  Run: curl -fsSL https://raw.githubusercontent.com/codetree21/baro-cli/main/install.sh | sh"
-        };
         Some(format!(
-            "\nUpdate available: v{} → v{}\n{}",
-            CURRENT_VERSION, latest, hint
+            "\nUpdate available: v{} {} v{}\n{}",
+            CURRENT_VERSION, ui::arrow(), latest, hint
         ))
     } else {
         None
@@ -134,4 +272,98 @@ mod tests {
     fn older_not_newer() {
         assert!(!is_newer("0.2.0", "0.3.0"));
     }
+
+    #[test]
+    fn legacy_cache_format_still_parses() {
+        let cached: CachedCheck =
+            serde_json::from_str(r#"{"latest_version":"0.5.0","checked_at":100}"#).unwrap();
+        assert_eq!(cached.latest_version.as_deref(), Some("0.5.0"));
+        assert!(cached.is_fresh(100 + CHECK_INTERVAL_SECS - 1));
+        assert!(!cached.is_fresh(100 + CHECK_INTERVAL_SECS));
+    }
+
+    #[test]
+    fn failed_check_uses_retry_at() {
+        let cached = CachedCheck {
+            latest_version: None,
+            minimum_version: None,
+            install_hint: None,
+            checked_at: 1000,
+            retry_at: Some(1000 + FAILURE_BACKOFF_SECS),
+            source: None,
+        };
+        assert!(cached.is_fresh(1000 + FAILURE_BACKOFF_SECS - 1));
+        assert!(!cached.is_fresh(1000 + FAILURE_BACKOFF_SECS));
+    }
+
+    #[test]
+    fn clock_skew_does_not_underflow() {
+        let cached = CachedCheck {
+            latest_version: Some("1.0.0".to_string()),
+            minimum_version: None,
+            install_hint: None,
+            checked_at: 5000,
+            retry_at: None,
+            source: None,
+        };
+        assert!(cached.is_fresh(10));
+    }
+
+    #[test]
+    fn parse_latest_github_and_registry_formats() {
+        let github = serde_json::json!({"tag_name": "v1.2.3", "name": "Release"});
+        assert_eq!(parse_latest(&github).unwrap().latest_version, "1.2.3");
+
+        let registry = serde_json::json!({
+            "latest_version": "2.0.0",
+            "minimum_version": "v1.5.0",
+            "install_hint": "brew upgrade baro",
+        });
+        assert_eq!(
+            parse_latest(&registry).unwrap(),
+            LatestInfo {
+                latest_version: "2.0.0".to_string(),
+                minimum_version: Some("1.5.0".to_string()),
+                install_hint: Some("brew upgrade baro".to_string()),
+            }
+        );
+
+        assert!(parse_latest(&serde_json::json!({"unexpected": true})).is_none());
+    }
+
+    #[test]
+    fn minimum_version_triggers_prominent_warning() {
+        let cached = CachedCheck {
+            latest_version: Some("999.0.0".to_string()),
+            minimum_version: Some("998.0.0".to_string()),
+            install_hint: Some("brew upgrade baro".to_string()),
+            checked_at: 0,
+            retry_at: None,
+            source: Some("https://registry.example/api/cli/latest".to_string()),
+        };
+        let notice = format_notice(&cached).unwrap();
+        assert!(notice.contains("WARNING"));
+        assert!(notice.contains("v998.0.0"));
+        assert!(notice.contains("brew upgrade baro"));
+
+        let ok = CachedCheck { minimum_version: Some("0.0.1".to_string()), ..cached };
+        assert!(format_notice(&ok).unwrap().starts_with("\nUpdate available"));
+    }
+
+    #[test]
+    fn retry_at_honors_rate_limit_headers() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+        let now = 1_000_000;
+
+        let mut limited = HeaderMap::new();
+        limited.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        limited.insert("x-ratelimit-reset", HeaderValue::from_static("1003600"));
+        assert_eq!(retry_at_from_headers(&limited, now), 1_003_600);
+
+        let mut retry = HeaderMap::new();
+        retry.insert("retry-after", HeaderValue::from_static("120"));
+        assert_eq!(retry_at_from_headers(&retry, now), now + 120);
+
+        assert_eq!(retry_at_from_headers(&HeaderMap::new(), now), now + FAILURE_BACKOFF_SECS);
+    }
 }
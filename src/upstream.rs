@@ -0,0 +1,212 @@
+use crate::types::{Release, Remote};
+use crate::utils;
+
+/// Where a fork stands relative to its origin's published releases.
+#[derive(Debug, PartialEq)]
+pub enum Relation<'a> {
+    /// The forked release is still the newest upstream
+    UpToDate,
+    /// Upstream has `newer` releases above the forked version
+    Behind { latest: &'a Release, newer: usize },
+    /// The forked version was withdrawn and every remaining upstream release is older
+    Ahead { latest: &'a Release },
+    /// The forked version was re-released upstream with different contents
+    Rereleased { current: &'a Release },
+    /// The forked version was withdrawn; upstream has moved on to `latest`
+    Withdrawn { latest: &'a Release },
+    NoReleases,
+}
+
+/// Compare the forked release against upstream by semver, using the
+/// recorded release ID (when the fork has one) to spot yanks and re-releases.
+pub fn relation<'a>(forked_version: &str, forked_release_id: Option<&str>, releases: &'a [Release]) -> Relation<'a> {
+    // Yanked releases may still be listed, but they don't count as upstream
+    let releases: Vec<&'a Release> = releases.iter().filter(|r| !r.is_yanked()).collect();
+    let Some(latest) = releases.iter().max_by(|a, b| utils::compare_versions(&a.version, &b.version)) else {
+        return Relation::NoReleases;
+    };
+    let ordering = utils::compare_versions(&latest.version, forked_version);
+    let same_version = releases.iter().find(|r| utils::compare_versions(&r.version, forked_version).is_eq());
+    let still_published = match forked_release_id {
+        Some(id) => releases.iter().any(|r| r.id == id),
+        None => same_version.is_some(),
+    };
+
+    if !still_published {
+        return match same_version {
+            Some(current) => Relation::Rereleased { current },
+            None if ordering.is_gt() => Relation::Withdrawn { latest },
+            None => Relation::Ahead { latest },
+        };
+    }
+    if ordering.is_gt() {
+        let newer = releases
+            .iter()
+            .filter(|r| utils::compare_versions(&r.version, forked_version).is_gt())
+            .count();
+        Relation::Behind { latest, newer }
+    } else {
+        Relation::UpToDate
+    }
+}
+
+/// The releases `up` may be offered: without its ignored versions and, when
+/// pinned, anything above the pin. The forked release itself always stays, so
+/// yanks and re-releases are still detected.
+pub fn apply_policy(releases: &[Release], up: &Remote) -> Vec<Release> {
+    let is = |a: &str, b: &str| utils::compare_versions(a, b).is_eq();
+    releases
+        .iter()
+        .filter(|r| {
+            is(&r.version, &up.version)
+                || (!up.ignored_versions.iter().any(|v| is(v, &r.version))
+                    && up.pinned_version.as_deref().is_none_or(|pin| utils::compare_versions(&r.version, pin).is_le()))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Releases newer than the forked version, oldest first, leaving out yanked ones.
+pub fn newer_releases<'a>(forked_version: &str, releases: &'a [Release]) -> Vec<&'a Release> {
+    let mut newer: Vec<&'a Release> = releases
+        .iter()
+        .filter(|r| !r.is_yanked() && utils::compare_versions(&r.version, forked_version).is_gt())
+        .collect();
+    newer.sort_by(|a, b| utils::compare_versions(&a.version, &b.version));
+    newer
+}
+
+impl<'a> Relation<'a> {
+    /// One line stating the relationship, e.g. "Behind upstream: forked 1.2.0, latest is 1.4.0 (2 newer releases)".
+    pub fn summary(&self, forked_version: &str) -> String {
+        match self {
+            Relation::UpToDate => format!("Up to date: forked {}, the latest upstream release", forked_version),
+            Relation::Behind { latest, newer } => format!(
+                "Behind upstream: forked {}, latest is {} ({} newer release{})",
+                forked_version, latest.version, newer, if *newer == 1 { "" } else { "s" }
+            ),
+            Relation::Ahead { latest } => format!(
+                "Ahead of upstream: forked {}, which was withdrawn; the newest upstream release is now {}",
+                forked_version, latest.version
+            ),
+            Relation::Rereleased { .. } => format!(
+                "Diverged: upstream re-released {} with different contents since you forked it",
+                forked_version
+            ),
+            Relation::Withdrawn { latest } => format!(
+                "Diverged: {} was withdrawn upstream; latest is {}",
+                forked_version, latest.version
+            ),
+            Relation::NoReleases => "Upstream has no published releases".to_string(),
+        }
+    }
+
+    /// A few words for a table cell, e.g. "2 newer (1.4.0)".
+    pub fn label(&self) -> String {
+        match self {
+            Relation::UpToDate => "up to date".to_string(),
+            Relation::Behind { latest, newer } => format!("{} newer ({})", newer, latest.version),
+            Relation::Ahead { .. } => "ahead (forked release withdrawn)".to_string(),
+            Relation::Rereleased { .. } => "re-released".to_string(),
+            Relation::Withdrawn { latest } => format!("withdrawn ({})", latest.version),
+            Relation::NoReleases => "no releases".to_string(),
+        }
+    }
+
+    /// The release `baro pull` should fetch, if any: the newest one when it is
+    /// newer than the fork, or the replacement for a re-released version.
+    pub fn pull_target(&self) -> Option<&'a Release> {
+        match *self {
+            Relation::Behind { latest, .. } | Relation::Withdrawn { latest } => Some(latest),
+            Relation::Rereleased { current } => Some(current),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(id: &str, version: &str) -> Release {
+        Release {
+            id: id.to_string(),
+            version: version.to_string(),
+            changelog: None,
+            file_size_bytes: None,
+            created_at: "2024-06-01T00:00:00Z".to_string(),
+            status: None,
+            upload_status: None,
+            review_status: None,
+        }
+    }
+
+    #[test]
+    fn semver_order_not_list_order() {
+        // 1.10.0 is newer than 1.9.0 even when listed after it
+        let releases = vec![release("r2", "1.9.0"), release("r3", "1.10.0"), release("r1", "1.2.0")];
+        let rel = relation("1.2.0", Some("r1"), &releases);
+        assert_eq!(rel, Relation::Behind { latest: &releases[1], newer: 2 });
+        assert_eq!(rel.pull_target().unwrap().version, "1.10.0");
+        assert_eq!(rel.label(), "2 newer (1.10.0)");
+        let newer: Vec<&str> = newer_releases("1.2.0", &releases).iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(newer, vec!["1.9.0", "1.10.0"]);
+        assert_eq!(relation("1.10.0", Some("r3"), &releases), Relation::UpToDate);
+        assert_eq!(relation("1.2.0", Some("r1"), &[]), Relation::NoReleases);
+    }
+
+    #[test]
+    fn pins_and_ignores() {
+        let releases = vec![release("r1", "1.0.0"), release("r2", "1.1.0"), release("r3", "1.2.0"), release("r4", "2.0.0")];
+        let mut up = Remote {
+            name: "origin".to_string(),
+            origin: "alice/demo".to_string(),
+            version: "1.0.0".to_string(),
+            release_id: Some("r1".to_string()),
+            file_hash: None,
+            ignored_versions: vec!["1.2.0".to_string(), "1.0.0".to_string()],
+            pinned_version: Some("1.9.0".to_string()),
+        };
+        let offered = apply_policy(&releases, &up);
+        let versions: Vec<&str> = offered.iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.0.0", "1.1.0"]);
+        assert_eq!(relation("1.0.0", Some("r1"), &offered).pull_target().unwrap().version, "1.1.0");
+
+        up.ignored_versions.push("1.1.0".to_string());
+        assert_eq!(relation("1.0.0", Some("r1"), &apply_policy(&releases, &up)), Relation::UpToDate);
+    }
+
+    #[test]
+    fn yanks_and_rereleases() {
+        // 1.2.0 yanked and published again under a new ID
+        let releases = vec![release("r9", "1.2.0"), release("r0", "1.1.0")];
+        let rel = relation("1.2.0", Some("r1"), &releases);
+        assert_eq!(rel, Relation::Rereleased { current: &releases[0] });
+        assert!(rel.summary("1.2.0").starts_with("Diverged"));
+
+        // 1.3.0 forked, then yanked: upstream now tops out below the fork
+        let releases = vec![release("r1", "1.2.0")];
+        let rel = relation("1.3.0", Some("r2"), &releases);
+        assert_eq!(rel, Relation::Ahead { latest: &releases[0] });
+        assert_eq!(rel.pull_target(), None);
+
+        // 1.3.0 yanked, but 1.4.0 has since been published
+        let releases = vec![release("r4", "1.4.0"), release("r1", "1.2.0")];
+        assert_eq!(relation("1.3.0", Some("r2"), &releases), Relation::Withdrawn { latest: &releases[0] });
+
+        // Listed but marked yanked counts as withdrawn
+        let mut yanked = release("r2", "1.3.0");
+        yanked.status = Some("yanked".to_string());
+        let releases = vec![yanked, release("r1", "1.2.0")];
+        assert_eq!(relation("1.3.0", Some("r2"), &releases), Relation::Ahead { latest: &releases[1] });
+    }
+
+    #[test]
+    fn legacy_manifest_without_release_id() {
+        let releases = vec![release("r3", "2.0.0"), release("r1", "1.0.0")];
+        assert!(matches!(relation("1.0.0", None, &releases), Relation::Behind { newer: 1, .. }));
+        assert!(matches!(relation("v2.0", None, &releases), Relation::UpToDate));
+        assert!(matches!(relation("1.5.0", None, &releases), Relation::Withdrawn { .. }));
+        assert!(matches!(relation("3.0.0", None, &releases), Relation::Ahead { .. }));
+    }
+}
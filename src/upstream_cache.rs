@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::config;
+use crate::utils;
+
+/// Last known upstream release for a fork origin, as recorded by `baro upstream`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UpstreamEntry {
+    pub latest_version: String,
+    pub checked_at: String,
+}
+
+fn cache_path() -> Option<PathBuf> {
+    config::config_dir().ok().map(|d| d.join("upstream-cache.json"))
+}
+
+fn load() -> BTreeMap<String, UpstreamEntry> {
+    cache_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Cached upstream info for `origin` (user/product), if any.
+pub fn get(origin: &str) -> Option<UpstreamEntry> {
+    load().remove(origin)
+}
+
+/// Every origin (user/product) with a cached entry.
+pub fn origins() -> Vec<String> {
+    load().into_keys().collect()
+}
+
+/// Drop entries not refreshed within `max_age` (origins no longer forked
+/// anywhere, typically). Returns how many were removed.
+pub fn prune(max_age: std::time::Duration) -> usize {
+    let Some(path) = cache_path() else { return 0 };
+    let mut entries = load();
+    let before = entries.len();
+    let cutoff = chrono::Utc::now() - chrono::Duration::seconds(max_age.as_secs() as i64);
+    entries.retain(|_, e| chrono::DateTime::parse_from_rfc3339(&e.checked_at).is_ok_and(|t| t >= cutoff));
+    let removed = before - entries.len();
+    if removed > 0 {
+        if let Ok(json) = serde_json::to_string_pretty(&entries) {
+            let _ = utils::write_atomic(&path, json.as_bytes());
+        }
+    }
+    removed
+}
+
+/// Record the latest upstream version for `origin`. Best-effort: errors are ignored.
+pub fn record(origin: &str, latest_version: &str) {
+    let Some(path) = cache_path() else { return };
+    let mut entries = load();
+    entries.insert(
+        origin.to_string(),
+        UpstreamEntry {
+            latest_version: latest_version.to_string(),
+            checked_at: chrono::Utc::now().to_rfc3339(),
+        },
+    );
+    if let Ok(json) = serde_json::to_string_pretty(&entries) {
+        let _ = utils::write_atomic(&path, json.as_bytes());
+    }
+}
@@ -1,5 +1,7 @@
 use std::path::Path;
 
+use crate::types::{Category, ProductLinks, ProductStats, RankingExplanation};
+
 pub(crate) fn detect_metadata(dir: &Path) -> (Option<String>, Option<String>) {
     // Try Cargo.toml
     if let Ok(content) = std::fs::read_to_string(dir.join("Cargo.toml")) {
@@ -20,6 +22,81 @@ pub(crate) fn detect_metadata(dir: &Path) -> (Option<String>, Option<String>) {
     (None, None)
 }
 
+/// Homepage, repository and documentation URLs from Cargo.toml or package.json.
+pub(crate) fn detect_links(dir: &Path) -> ProductLinks {
+    if let Ok(content) = std::fs::read_to_string(dir.join("Cargo.toml")) {
+        return ProductLinks {
+            homepage: extract_toml_value(&content, "homepage"),
+            repository: extract_toml_value(&content, "repository"),
+            documentation: extract_toml_value(&content, "documentation"),
+        };
+    }
+    if let Ok(content) = std::fs::read_to_string(dir.join("package.json")) {
+        if let Ok(v) = serde_json::from_str::<serde_json::Value>(&content) {
+            // "repository" is either a URL string or {"type": "git", "url": "..."}
+            let repository = v["repository"].as_str().or_else(|| v["repository"]["url"].as_str());
+            return ProductLinks {
+                homepage: v["homepage"].as_str().map(String::from),
+                repository: repository.map(|r| r.trim_start_matches("git+").to_string()),
+                documentation: None,
+            };
+        }
+    }
+    ProductLinks::default()
+}
+
+/// Links must be absolute http(s) URLs. Detected values that aren't (e.g.
+/// npm's `github:user/repo` shorthand) are dropped rather than rejected.
+pub(crate) fn validate_links(links: ProductLinks, strict: bool) -> anyhow::Result<ProductLinks> {
+    let check = |field: &str, url: Option<String>| -> anyhow::Result<Option<String>> {
+        match url {
+            Some(u) if u.starts_with("https://") || u.starts_with("http://") => Ok(Some(u)),
+            Some(u) if strict => Err(anyhow::anyhow!("--{} must be an http(s) URL, got '{}'", field, u)),
+            _ => Ok(None),
+        }
+    };
+    Ok(ProductLinks {
+        homepage: check("homepage", links.homepage)?,
+        repository: check("repository", links.repository)?,
+        documentation: check("docs", links.documentation)?,
+    })
+}
+
+/// Where a download to `dest` accumulates until it completes (`<dest>.part`).
+pub(crate) fn partial_path(dest: &Path) -> std::path::PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// How to continue a download of which `have` bytes are on disk, given the
+/// response to a `Range: bytes=<have>-` request.
+#[derive(Debug, PartialEq)]
+pub(crate) enum RangeResume {
+    /// 206 starting at `have`: append the body
+    Append,
+    /// The server ignored the range (200) or answered for another offset: start over
+    Restart,
+    /// 416 and the file is already `have` bytes long: nothing left to fetch
+    Complete,
+}
+
+pub(crate) fn range_resume(status: u16, content_range: Option<&str>, have: u64) -> RangeResume {
+    // Content-Range: bytes <start>-<end>/<total>  or  bytes */<total>
+    let range = content_range.and_then(|r| r.trim().strip_prefix("bytes "));
+    match status {
+        206 => {
+            let start = range.and_then(|r| r.split('-').next()).and_then(|s| s.parse::<u64>().ok());
+            if start == Some(have) { RangeResume::Append } else { RangeResume::Restart }
+        }
+        416 => {
+            let total = range.and_then(|r| r.strip_prefix("*/")).and_then(|t| t.parse::<u64>().ok());
+            if total == Some(have) { RangeResume::Complete } else { RangeResume::Restart }
+        }
+        _ => RangeResume::Restart,
+    }
+}
+
 pub(crate) fn extract_toml_value(content: &str, key: &str) -> Option<String> {
     for line in content.lines() {
         let trimmed = line.trim();
@@ -43,18 +120,203 @@ pub(crate) fn dir_to_slug(dir: &Path) -> String {
         .replace(' ', "-")
 }
 
-pub(crate) fn read_changelog(dir: &Path, _version: &str) -> Option<String> {
-    let path = dir.join("CHANGELOG.md");
-    std::fs::read_to_string(path).ok().map(|content| {
-        // Take first non-empty section (simplistic)
-        content
-            .lines()
-            .skip_while(|l| l.trim().is_empty() || l.starts_with('#'))
-            .take_while(|l| !l.starts_with('#'))
-            .collect::<Vec<_>>()
-            .join("\n")
-            .trim()
-            .to_string()
+/// Product page media types, by file extension.
+const MEDIA_TYPES: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("mp4", "video/mp4"),
+    ("webm", "video/webm"),
+];
+
+/// MIME type for a product page media file, or None if the format isn't accepted.
+pub(crate) fn media_content_type(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    MEDIA_TYPES.iter().find(|(e, _)| *e == ext).map(|(_, t)| *t)
+}
+
+/// The closest valid slug to `name`: lowercase, with runs of other characters as one hyphen.
+pub(crate) fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+pub(crate) const MAX_TAGS: usize = 10;
+const MAX_TAG_LEN: usize = 32;
+
+/// Lowercase, hyphenated, de-duplicated tags (`"Discord Bot"` → `discord-bot`).
+pub(crate) fn normalize_tags(tags: &[String]) -> anyhow::Result<Vec<String>> {
+    let mut out: Vec<String> = Vec::new();
+    for tag in tags {
+        let t = slugify(tag);
+        if t.is_empty() || t.len() > MAX_TAG_LEN {
+            return Err(anyhow::anyhow!("Invalid tag '{}': use 1-{} letters, digits or hyphens", tag, MAX_TAG_LEN));
+        }
+        if !out.contains(&t) {
+            out.push(t);
+        }
+    }
+    if out.len() > MAX_TAGS {
+        return Err(anyhow::anyhow!("Too many tags ({}); at most {} per product", out.len(), MAX_TAGS));
+    }
+    Ok(out)
+}
+
+/// Candidate replacements for a taken slug, most natural first.
+pub(crate) fn slug_alternatives(slug: &str, username: &str) -> Vec<String> {
+    let mut candidates = vec![
+        format!("{}-{}", slugify(username), slug),
+        format!("{}-app", slug),
+        format!("{}-cli", slug),
+        format!("{}-2", slug),
+        format!("my-{}", slug),
+    ];
+    candidates.retain(|c| c != slug && !c.starts_with('-'));
+    candidates.dedup();
+    candidates
+}
+
+/// Version named by a changelog heading such as `## [1.2.0] - 2024-01-01`,
+/// `## 1.2.0` or `## v1.2.0`. None for other lines.
+fn changelog_heading_version(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("## ")?.trim();
+    let rest = rest.strip_prefix('[').unwrap_or(rest);
+    let token = rest
+        .split(|c: char| c == ']' || c.is_whitespace())
+        .next()?;
+    let token = token.strip_prefix('v').unwrap_or(token);
+    if token.is_empty() {
+        None
+    } else {
+        Some(token)
+    }
+}
+
+/// Extract the CHANGELOG.md section for `version` (Keep-a-Changelog style headings).
+/// Returns Ok(None) when there is no CHANGELOG.md, and an error when the file
+/// exists but has no usable section for this version.
+pub(crate) fn read_changelog(dir: &Path, version: &str) -> anyhow::Result<Option<String>> {
+    let content = match std::fs::read_to_string(dir.join("CHANGELOG.md")) {
+        Ok(c) => c,
+        Err(_) => return Ok(None),
+    };
+
+    let mut found = Vec::new();
+    let mut lines = content.lines();
+    for line in lines.by_ref() {
+        if let Some(v) = changelog_heading_version(line) {
+            if v == version {
+                break;
+            }
+            found.push(v.to_string());
+        }
+    }
+
+    let section = lines
+        .take_while(|l| !l.starts_with("## ") && !l.starts_with("# "))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+
+    if section.is_empty() {
+        let known = if found.is_empty() {
+            String::new()
+        } else {
+            format!(" (found: {})", found.join(", "))
+        };
+        return Err(anyhow::anyhow!(
+            "CHANGELOG.md has no entry for {}{}.\n\
+            Add a `## [{}]` section, or pass --changelog \"...\".",
+            version, known, version
+        ));
+    }
+    Ok(Some(section))
+}
+
+/// Conventional-commit types that get a changelog section, in display order.
+const COMMIT_GROUPS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("refactor", "Refactoring"),
+    ("docs", "Documentation"),
+];
+
+/// Housekeeping types left out of release notes.
+const SKIPPED_COMMIT_TYPES: &[&str] = &["chore", "ci", "test", "build", "style"];
+
+/// Render commit subjects as Markdown release notes grouped by conventional-commit
+/// type (`feat(scope)!: message`). Non-conventional subjects go under "Other".
+pub(crate) fn changelog_from_commits(subjects: &[String]) -> String {
+    let mut breaking = Vec::new();
+    let mut grouped: Vec<Vec<String>> = vec![Vec::new(); COMMIT_GROUPS.len()];
+    let mut other = Vec::new();
+
+    for subject in subjects {
+        let parsed = subject.split_once(": ").and_then(|(head, msg)| {
+            let is_breaking = head.ends_with('!');
+            let head = head.trim_end_matches('!');
+            let (kind, scope) = match head.split_once('(') {
+                Some((k, rest)) => (k, rest.strip_suffix(')')),
+                None => (head, None),
+            };
+            if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_lowercase()) {
+                return None;
+            }
+            let entry = match scope {
+                Some(scope) => format!("{}: {}", scope, msg.trim()),
+                None => msg.trim().to_string(),
+            };
+            Some((kind, is_breaking, entry))
+        });
+
+        match parsed {
+            Some((_, true, entry)) => breaking.push(entry),
+            Some((kind, false, entry)) => {
+                if let Some(i) = COMMIT_GROUPS.iter().position(|(k, _)| *k == kind) {
+                    grouped[i].push(entry);
+                } else if !SKIPPED_COMMIT_TYPES.contains(&kind) {
+                    other.push(entry);
+                }
+            }
+            None => other.push(subject.trim().to_string()),
+        }
+    }
+
+    let mut sections = Vec::new();
+    let mut push_section = |title: &str, entries: &[String]| {
+        if !entries.is_empty() {
+            let items: Vec<String> = entries.iter().map(|e| format!("- {}", e)).collect();
+            sections.push(format!("### {}\n{}", title, items.join("\n")));
+        }
+    };
+    push_section("Breaking Changes", &breaking);
+    for ((_, title), entries) in COMMIT_GROUPS.iter().zip(&grouped) {
+        push_section(title, entries);
+    }
+    push_section("Other", &other);
+    sections.join("\n\n")
+}
+
+/// Write a file by renaming a fully written temp file over it, so concurrent
+/// readers see either the old or the new content, never a partial write.
+pub(crate) fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = dir.join(format!(".{}.{}.tmp", name, uuid::Uuid::new_v4()));
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp);
     })
 }
 
@@ -78,6 +340,196 @@ pub(crate) fn format_bytes(bytes: i64) -> String {
     }
 }
 
+/// Render categories as an aligned `slug  Name — description` list for error messages.
+pub(crate) fn format_category_list(categories: &[Category]) -> String {
+    let width = categories.iter().map(|c| c.slug.len()).max().unwrap_or(0);
+    categories
+        .iter()
+        .map(|c| {
+            let mut line = format!("  {:<width$}  {}", c.slug, c.name, width = width);
+            if let Some(desc) = c.description.as_deref().filter(|d| !d.trim().is_empty()) {
+                let first_line = desc.lines().next().unwrap_or("").trim();
+                line.push_str(&format!(" — {}", truncate_str(first_line, 60)));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// One-line ranking breakdown, largest contribution first:
+/// `score 0.82 = text_relevance 0.50 + forks 0.20 + recency 0.12`.
+pub(crate) fn format_ranking(ranking: &RankingExplanation) -> String {
+    let mut parts: Vec<(&String, &f64)> = ranking.components.iter().collect();
+    parts.sort_by(|a, b| b.1.total_cmp(a.1));
+    let breakdown = parts
+        .iter()
+        .map(|(name, value)| format!("{} {:.2}", name, value))
+        .collect::<Vec<_>>()
+        .join(" + ");
+    match (ranking.score, breakdown.is_empty()) {
+        (Some(score), true) => format!("score {:.2}", score),
+        (Some(score), false) => format!("score {:.2} = {}", score, breakdown),
+        (None, true) => "-".to_string(),
+        (None, false) => breakdown,
+    }
+}
+
+/// Whether a product's stats reach `--min-rating` / `--min-forks`. Products
+/// without stats pass only when no threshold is set.
+pub(crate) fn meets_thresholds(stats: Option<&ProductStats>, min_rating: Option<f64>, min_forks: Option<u64>) -> bool {
+    let rating_ok = min_rating.is_none_or(|min| stats.and_then(|s| s.avg_rating).is_some_and(|r| r >= min));
+    let forks_ok = min_forks.is_none_or(|min| stats.and_then(|s| s.fork_count).unwrap_or(0) >= min);
+    rating_ok && forks_ok
+}
+
+/// Compare dotted numeric versions component by component; missing
+/// components count as 0, so `1.0` equals `1.0.0`. As in semver, a
+/// pre-release sorts before its release (`1.0.0-rc.1` < `1.0.0`) and
+/// build metadata (`+...`) is ignored.
+pub(crate) fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    let split = |v: &str| -> (Vec<u64>, Option<String>) {
+        let v = v.trim_start_matches('v');
+        let v = v.split('+').next().unwrap_or(v);
+        let (core, pre) = match v.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (v, None),
+        };
+        (core.split('.').map(|s| s.parse().unwrap_or(0)).collect(), pre)
+    };
+    let ((a, a_pre), (b, b_pre)) = (split(a), split(b));
+    let len = a.len().max(b.len());
+    let core = (0..len)
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal);
+    core.then_with(|| match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => {
+            // Numeric identifiers compare numerically and below alphanumeric ones
+            let ident = |x: &str, y: &str| match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            };
+            let (a, b): (Vec<&str>, Vec<&str>) = (a.split('.').collect(), b.split('.').collect());
+            a.iter().zip(&b).map(|(x, y)| ident(x, y)).find(|o| o.is_ne()).unwrap_or(a.len().cmp(&b.len()))
+        }
+    })
+}
+
+/// `version` with its last component incremented (`1.2.3` → `1.2.4`).
+pub(crate) fn bump_last_component(version: &str) -> String {
+    let mut parts: Vec<String> = version.split('.').map(String::from).collect();
+    if let Some(last) = parts.last_mut() {
+        if let Ok(n) = last.parse::<u64>() {
+            *last = (n + 1).to_string();
+        }
+    }
+    parts.join(".")
+}
+
+/// Levenshtein distance between two strings, compared case-insensitively.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+/// Up to `limit` candidates close to `input` (small edit distance or a shared
+/// prefix), closest first. For "did you mean" hints.
+pub(crate) fn closest_matches<'a>(input: &str, candidates: &[&'a str], limit: usize) -> Vec<&'a str> {
+    let lower = input.to_lowercase();
+    let max_distance = (input.chars().count() / 3).max(3);
+    let mut scored: Vec<(usize, &str)> = candidates
+        .iter()
+        .filter_map(|c| {
+            let d = edit_distance(input, c);
+            let prefix = lower.len() >= 3 && c.to_lowercase().starts_with(&lower);
+            (d <= max_distance || prefix).then_some((d, *c))
+        })
+        .collect();
+    scored.sort();
+    scored.into_iter().take(limit).map(|(_, c)| c).collect()
+}
+
+/// `user/slug` ids close to a search query. A query without a `/` is compared
+/// with the slugs alone, as a slug (so "web scrapr" finds `acme/web-scraper`).
+pub(crate) fn closest_products<'a>(query: &str, ids: &[&'a str], limit: usize) -> Vec<&'a str> {
+    if query.contains('/') {
+        return closest_matches(query, ids, limit);
+    }
+    let wanted = slugify(query);
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+    let slugs: Vec<&str> = ids.iter().map(|id| id.rsplit('/').next().unwrap_or(id)).collect();
+    let mut found = Vec::new();
+    for slug in closest_matches(&wanted, &slugs, slugs.len()) {
+        // The same slug may exist under several publishers
+        for (id, s) in ids.iter().zip(&slugs) {
+            if *s == slug && !found.contains(id) && found.len() < limit {
+                found.push(*id);
+            }
+        }
+    }
+    found
+}
+
+const LICENSE_FILES: &[&str] = &["LICENSE", "LICENSE.md", "LICENSE.txt", "LICENCE", "COPYING"];
+
+/// Distinguishing phrases from each license's SPDX template, most specific
+/// first. The copyleft licenses are matched on their title line, since their
+/// bodies name one another (GPL-3.0 section 13 mentions the AGPL, MPL-2.0
+/// lists the GNU licenses); BSD-3 contains BSD-2.
+const LICENSE_FINGERPRINTS: &[(&str, &[&str])] = &[
+    ("MPL-2.0", &["mozilla public license version 2.0"]),
+    ("AGPL-3.0-only", &["gnu affero general public license version 3, 19 november 2007"]),
+    ("LGPL-3.0-only", &["gnu lesser general public license version 3, 29 june 2007"]),
+    ("LGPL-2.1-only", &["gnu lesser general public license version 2.1, february 1999"]),
+    ("GPL-3.0-only", &["gnu general public license version 3, 29 june 2007"]),
+    ("GPL-2.0-only", &["gnu general public license version 2, june 1991"]),
+    ("Apache-2.0", &["apache license version 2.0, january 2004"]),
+    ("BSL-1.0", &["boost software license - version 1.0"]),
+    ("BSD-3-Clause", &["redistribution and use in source and binary forms", "neither the name of"]),
+    ("BSD-2-Clause", &["redistribution and use in source and binary forms"]),
+    ("ISC", &["permission to use, copy, modify, and/or distribute this software for any purpose"]),
+    ("MIT", &["permission is hereby granted, free of charge", "the above copyright notice and this permission notice shall be included"]),
+    ("Unlicense", &["this is free and unencumbered software released into the public domain"]),
+    ("CC0-1.0", &["cc0 1.0 universal"]),
+];
+
+/// SPDX identifier of the license text `content`, if it matches a known template.
+pub(crate) fn identify_license(content: &str) -> Option<&'static str> {
+    let text = content.to_lowercase().split_whitespace().collect::<Vec<_>>().join(" ");
+    LICENSE_FINGERPRINTS
+        .iter()
+        .find(|(_, phrases)| phrases.iter().all(|p| text.contains(p)))
+        .map(|(id, _)| *id)
+}
+
+/// The license file in `dir` (first of LICENSE, LICENSE.md, ...) and its
+/// detected SPDX identifier (None when the text is not recognized).
+pub(crate) fn detect_license(dir: &Path) -> Option<(&'static str, Option<&'static str>)> {
+    LICENSE_FILES.iter().find_map(|name| {
+        let content = std::fs::read_to_string(dir.join(name)).ok()?;
+        Some((*name, identify_license(&content)))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,6 +588,35 @@ mod tests {
         assert_eq!(desc, Some("A Node.js app".to_string()));
     }
 
+    #[test]
+    fn detect_links_from_build_files() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"homepage": "https://demo.dev", "repository": {"type": "git", "url": "git+https://github.com/u/demo.git"}}"#,
+        ).unwrap();
+        let links = detect_links(dir.path());
+        assert_eq!(links.homepage.as_deref(), Some("https://demo.dev"));
+        assert_eq!(links.repository.as_deref(), Some("https://github.com/u/demo.git"));
+
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nrepository = \"https://github.com/u/demo\"\ndocumentation = \"https://docs.rs/demo\"",
+        ).unwrap();
+        let links = detect_links(dir.path());
+        assert_eq!(links.homepage, None);
+        assert_eq!(links.documentation.as_deref(), Some("https://docs.rs/demo"));
+    }
+
+    #[test]
+    fn validate_links_strict_and_lenient() {
+        let links = ProductLinks { homepage: Some("github:u/demo".into()), repository: Some("https://x.dev".into()), documentation: None };
+        assert!(validate_links(links.clone(), true).unwrap_err().to_string().contains("--homepage"));
+        let lenient = validate_links(links, false).unwrap();
+        assert_eq!(lenient.homepage, None);
+        assert_eq!(lenient.repository.as_deref(), Some("https://x.dev"));
+    }
+
     #[test]
     fn detect_metadata_no_build_file() {
         let dir = tempdir().unwrap();
@@ -194,6 +675,18 @@ mod tests {
         assert!(result.is_char_boundary(result.len()));
     }
 
+    // -- write_atomic --
+
+    #[test]
+    fn write_atomic_replaces_and_leaves_no_temp_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        write_atomic(&path, b"one").unwrap();
+        write_atomic(&path, b"two").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
     // -- format_bytes --
 
     #[test]
@@ -214,16 +707,218 @@ mod tests {
             dir.path().join("CHANGELOG.md"),
             "# Changelog\n\n## 1.0.0\n\nFirst release with core features.\nBug fixes included.\n\n## 0.9.0\n\nBeta release.\n",
         ).unwrap();
-        let result = read_changelog(dir.path(), "1.0.0");
+        let result = read_changelog(dir.path(), "1.0.0").unwrap();
         assert!(result.is_some());
         let text = result.unwrap();
         assert!(text.contains("First release"));
         assert!(!text.contains("Beta release"));
     }
 
+    // -- changelog_from_commits --
+
+    #[test]
+    fn changelog_from_commits_groups_by_type() {
+        let subjects: Vec<String> = [
+            "feat(cli): add --json",
+            "fix: handle empty README",
+            "chore: bump deps",
+            "feat!: drop legacy manifest",
+            "Merge-free plain message",
+            "feat: second feature",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(
+            changelog_from_commits(&subjects),
+            "### Breaking Changes\n- drop legacy manifest\n\n\
+             ### Features\n- cli: add --json\n- second feature\n\n\
+             ### Bug Fixes\n- handle empty README\n\n\
+             ### Other\n- Merge-free plain message"
+        );
+    }
+
+    #[test]
+    fn changelog_from_commits_empty_when_only_chores() {
+        let subjects = vec!["chore: tidy".to_string(), "ci: cache".to_string()];
+        assert_eq!(changelog_from_commits(&subjects), "");
+    }
+
+    // -- format_category_list --
+
+    #[test]
+    fn format_category_list_aligns_and_includes_descriptions() {
+        let categories = vec![
+            Category {
+                id: 1,
+                slug: "devops".to_string(),
+                name: "DevOps".to_string(),
+                description: Some("Deploy and operate\nsecond line".to_string()),
+            },
+            Category {
+                id: 2,
+                slug: "developer-tools".to_string(),
+                name: "Developer Tools".to_string(),
+                description: None,
+            },
+        ];
+        let out = format_category_list(&categories);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "  devops           DevOps — Deploy and operate");
+        assert_eq!(lines[1], "  developer-tools  Developer Tools");
+    }
+
+    #[test]
+    fn read_changelog_matches_requested_version() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("CHANGELOG.md"),
+            "# Changelog\n\n## [Unreleased]\n\n- wip\n\n## [1.1.0] - 2024-05-01\n\n### Added\n- Dark mode\n\n## v1.0.0\n\nFirst release.\n",
+        ).unwrap();
+        let text = read_changelog(dir.path(), "1.1.0").unwrap().unwrap();
+        assert_eq!(text, "### Added\n- Dark mode");
+        let text = read_changelog(dir.path(), "1.0.0").unwrap().unwrap();
+        assert_eq!(text, "First release.");
+    }
+
+    #[test]
+    fn read_changelog_errors_when_version_missing() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("CHANGELOG.md"), "## 1.0.0\n\nFirst release.\n").unwrap();
+        let err = read_changelog(dir.path(), "1.1.0").unwrap_err().to_string();
+        assert!(err.contains("no entry for 1.1.0"));
+        assert!(err.contains("found: 1.0.0"));
+    }
+
     #[test]
     fn read_changelog_returns_none_when_missing() {
         let dir = tempdir().unwrap();
-        assert_eq!(read_changelog(dir.path(), "1.0.0"), None);
+        assert_eq!(read_changelog(dir.path(), "1.0.0").unwrap(), None);
+    }
+
+    #[test]
+    fn identify_license_from_full_texts() {
+        let texts = [
+            ("MIT", include_str!("../LICENSE")),
+            ("GPL-3.0-only", include_str!("../testdata/licenses/GPL-3.0")),
+            ("GPL-2.0-only", include_str!("../testdata/licenses/GPL-2.0")),
+            ("LGPL-3.0-only", include_str!("../testdata/licenses/LGPL-3.0")),
+            ("LGPL-2.1-only", include_str!("../testdata/licenses/LGPL-2.1")),
+            ("MPL-2.0", include_str!("../testdata/licenses/MPL-2.0")),
+            ("Apache-2.0", include_str!("../testdata/licenses/Apache-2.0")),
+        ];
+        for (id, text) in texts {
+            assert_eq!(identify_license(text), Some(id), "{id}");
+        }
+    }
+
+    #[test]
+    fn identify_license_agpl_title_and_bsd3() {
+        let agpl = "GNU AFFERO GENERAL PUBLIC LICENSE\n   Version 3, 19 November 2007\n\n Copyright (C) 2007 Free Software Foundation";
+        assert_eq!(identify_license(agpl), Some("AGPL-3.0-only"));
+
+        let bsd3 = "Redistribution and use in source and binary forms, with or without modification...\n3. Neither the name of the copyright holder";
+        assert_eq!(identify_license(bsd3), Some("BSD-3-Clause"));
+
+        assert_eq!(identify_license("All rights reserved."), None);
+    }
+
+    #[test]
+    fn detect_license_finds_file() {
+        let dir = tempdir().unwrap();
+        assert_eq!(detect_license(dir.path()), None);
+        fs::write(dir.path().join("LICENSE.txt"), "Apache License\nVersion 2.0, January 2004").unwrap();
+        assert_eq!(detect_license(dir.path()), Some(("LICENSE.txt", Some("Apache-2.0"))));
+    }
+
+    #[test]
+    fn closest_matches_ranks_by_distance() {
+        assert_eq!(edit_distance("kitten", "Sitting"), 3);
+        let ids = ["Apache-2.0", "Apache-1.1", "MIT", "MPL-2.0"];
+        assert_eq!(closest_matches("apache2", &ids, 1), vec!["Apache-2.0"]);
+        assert_eq!(closest_matches("MTI", &ids, 3)[0], "MIT");
+        assert!(closest_matches("completely-different", &ids, 3).is_empty());
+
+        let products = ["acme/web-scraper", "bob/web-scraper", "acme/pdf-tools"];
+        assert_eq!(closest_products("web scrapr", &products, 3), vec!["acme/web-scraper", "bob/web-scraper"]);
+        assert_eq!(closest_products("acme/pdf-tool", &products, 3), vec!["acme/pdf-tools"]);
+        assert!(closest_products("!!", &products, 3).is_empty());
+    }
+
+    #[test]
+    fn compare_versions_numeric() {
+        use std::cmp::Ordering;
+        assert_eq!(compare_versions("1.10.0", "1.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("0.9", "1.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.1-beta", "1.0.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.0-rc.1", "1.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0-rc.2", "1.0.0-rc.10"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0-alpha", "1.0.0-alpha.1"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0+build.5", "1.0.0"), Ordering::Equal);
+        assert_eq!(bump_last_component("1.2.9"), "1.2.10");
+    }
+
+    #[test]
+    fn format_ranking_orders_components() {
+        let ranking = RankingExplanation {
+            score: Some(0.82),
+            components: [("recency".to_string(), 0.12), ("text_relevance".to_string(), 0.5), ("forks".to_string(), 0.2)]
+                .into_iter()
+                .collect(),
+        };
+        assert_eq!(format_ranking(&ranking), "score 0.82 = text_relevance 0.50 + forks 0.20 + recency 0.12");
+        let empty = RankingExplanation { score: None, components: Default::default() };
+        assert_eq!(format_ranking(&empty), "-");
+    }
+
+    #[test]
+    fn thresholds_need_stats() {
+        let stats = ProductStats { fork_count: Some(12), remake_count: None, avg_rating: Some(4.2), rating_count: Some(9) };
+        assert!(meets_thresholds(Some(&stats), Some(4.0), Some(10)));
+        assert!(!meets_thresholds(Some(&stats), Some(4.5), None));
+        assert!(!meets_thresholds(Some(&stats), None, Some(20)));
+        assert!(meets_thresholds(None, None, None));
+        assert!(!meets_thresholds(None, Some(1.0), None));
+        assert!(meets_thresholds(None, None, Some(0)));
+    }
+
+    #[test]
+    fn partial_downloads_resume_by_range() {
+        assert_eq!(partial_path(Path::new("/tmp/a.tar.gz")), Path::new("/tmp/a.tar.gz.part"));
+        assert_eq!(range_resume(206, Some("bytes 100-999/1000"), 100), RangeResume::Append);
+        assert_eq!(range_resume(206, Some("bytes 0-999/1000"), 100), RangeResume::Restart);
+        assert_eq!(range_resume(200, None, 100), RangeResume::Restart);
+        assert_eq!(range_resume(416, Some("bytes */1000"), 1000), RangeResume::Complete);
+        assert_eq!(range_resume(416, Some("bytes */2000"), 1000), RangeResume::Restart);
+    }
+
+    #[test]
+    fn normalize_tags_slugifies_and_dedups() {
+        let tags: Vec<String> = ["Discord Bot", "rag", "RAG", "web_scraper"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(normalize_tags(&tags).unwrap(), vec!["discord-bot", "rag", "web-scraper"]);
+        assert!(normalize_tags(&["!!".to_string()]).is_err());
+        assert!(normalize_tags(&["x".repeat(40)]).is_err());
+        let many: Vec<String> = (0..11).map(|i| format!("t{}", i)).collect();
+        assert!(normalize_tags(&many).unwrap_err().to_string().contains("Too many"));
+    }
+
+    #[test]
+    fn slugify_and_alternatives() {
+        assert_eq!(slugify("My Cool_Tool!"), "my-cool-tool");
+        assert_eq!(slugify("--x--"), "x");
+        assert_eq!(
+            slug_alternatives("notes", "Alice.Dev"),
+            vec!["alice-dev-notes", "notes-app", "notes-cli", "notes-2", "my-notes"]
+        );
+    }
+
+    #[test]
+    fn media_types_by_extension() {
+        assert_eq!(media_content_type(Path::new("shots/Home.PNG")), Some("image/png"));
+        assert_eq!(media_content_type(Path::new("demo.jpeg")), Some("image/jpeg"));
+        assert_eq!(media_content_type(Path::new("demo.mp4")), Some("video/mp4"));
+        assert_eq!(media_content_type(Path::new("notes.txt")), None);
+        assert_eq!(media_content_type(Path::new("README")), None);
     }
 }
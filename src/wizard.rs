@@ -0,0 +1,177 @@
+use anyhow::Result;
+use std::io::{BufRead, IsTerminal, Write};
+use std::sync::OnceLock;
+
+use crate::spdx;
+use crate::types::Category;
+
+/// Licenses offered by the picker; anything else can be typed as an SPDX expression.
+const COMMON_LICENSES: &[&str] = &["MIT", "Apache-2.0", "Apache-2.0 OR MIT", "GPL-3.0-or-later", "BSD-3-Clause", "MPL-2.0", "Unlicense"];
+
+const MIN_DESCRIPTION_CHARS: usize = 50;
+
+/// Answers from `--yes` / `--non-interactive`, set once at startup.
+static MODE: OnceLock<(bool, bool)> = OnceLock::new();
+
+pub fn set_mode(assume_yes: bool, non_interactive: bool) {
+    let _ = MODE.set((assume_yes, non_interactive));
+}
+
+fn assume_yes() -> bool {
+    MODE.get().is_some_and(|m| m.0)
+}
+
+/// Whether we can ask questions: not `--non-interactive`, and stdin and stderr
+/// are both terminals (so CI pipelines never block on a prompt).
+pub fn is_interactive() -> bool {
+    !MODE.get().is_some_and(|m| m.1) && std::io::stdin().is_terminal() && std::io::stderr().is_terminal()
+}
+
+/// A `[Y/n]` confirmation. `--yes` answers it; otherwise, without a terminal,
+/// fails with `hint` telling the user how to proceed non-interactively.
+pub fn confirm(question: &str, hint: &str) -> Result<bool> {
+    if assume_yes() {
+        eprintln!("{} yes (--yes)", question);
+        return Ok(true);
+    }
+    if !is_interactive() {
+        return Err(anyhow::anyhow!("{} (no terminal to answer on)\n{}", question, hint));
+    }
+    let answer = ask(&format!("{} [Y/n] ", question))?.to_lowercase();
+    Ok(answer.is_empty() || answer == "y" || answer == "yes")
+}
+
+/// A `[y/N]` question that `--yes` does not answer, for steps that run code
+/// the user hasn't seen. Without a terminal the answer is no.
+pub fn confirm_explicit(question: &str) -> Result<bool> {
+    if !is_interactive() {
+        return Ok(false);
+    }
+    let answer = ask(&format!("{} [y/N] ", question))?.to_lowercase();
+    Ok(answer == "y" || answer == "yes")
+}
+
+/// "Load more" between result pages: Enter fetches the next page, anything
+/// else stops. Never asked without a terminal (or when stdout is piped), and
+/// `--yes` doesn't answer it, so scripts get a single page.
+pub fn prompt_more(shown: u64, total: u64) -> Result<bool> {
+    if !is_interactive() || !std::io::stdout().is_terminal() {
+        return Ok(false);
+    }
+    let answer = ask(&format!("-- {} of {} shown. Enter for more, q to stop: ", shown, total))?;
+    Ok(answer.is_empty() || answer.eq_ignore_ascii_case("y"))
+}
+
+fn ask(question: &str) -> Result<String> {
+    eprint!("{}", question);
+    std::io::stderr().flush()?;
+    let mut input = String::new();
+    if std::io::stdin().lock().read_line(&mut input)? == 0 {
+        return Err(anyhow::anyhow!("Cancelled (end of input)"));
+    }
+    Ok(input.trim().to_string())
+}
+
+/// Resolve a picker answer: a 1-based number or an exact option (case-insensitive).
+pub fn parse_choice(answer: &str, options: &[&str]) -> Option<usize> {
+    let answer = answer.trim();
+    if let Ok(n) = answer.parse::<usize>() {
+        return (1..=options.len()).contains(&n).then(|| n - 1);
+    }
+    options.iter().position(|o| o.eq_ignore_ascii_case(answer))
+}
+
+pub fn choose_category(categories: &[Category]) -> Result<String> {
+    if categories.is_empty() {
+        return Err(anyhow::anyhow!("No categories available. Use --category <slug>."));
+    }
+    eprintln!("Category:");
+    for (i, c) in categories.iter().enumerate() {
+        eprintln!("  {:>2}. {:<24} {}", i + 1, c.slug, c.name);
+    }
+    let slugs: Vec<&str> = categories.iter().map(|c| c.slug.as_str()).collect();
+    loop {
+        match parse_choice(&ask("Choose a number or slug: ")?, &slugs) {
+            Some(i) => return Ok(slugs[i].to_string()),
+            None => eprintln!("  Not one of the listed categories."),
+        }
+    }
+}
+
+pub fn prompt_description() -> Result<String> {
+    eprintln!("Description ({}+ characters, shown on the product page):", MIN_DESCRIPTION_CHARS);
+    loop {
+        let desc = ask("> ")?;
+        if desc.chars().count() >= MIN_DESCRIPTION_CHARS {
+            return Ok(desc);
+        }
+        eprintln!("  {} characters; at least {} needed.", desc.chars().count(), MIN_DESCRIPTION_CHARS);
+    }
+}
+
+pub fn choose_license() -> Result<String> {
+    eprintln!("License:");
+    for (i, l) in COMMON_LICENSES.iter().enumerate() {
+        eprintln!("  {}. {}", i + 1, l);
+    }
+    loop {
+        let answer = ask("Choose a number or type an SPDX expression [1]: ")?;
+        if answer.is_empty() {
+            return Ok(COMMON_LICENSES[0].to_string());
+        }
+        if let Some(i) = parse_choice(&answer, COMMON_LICENSES) {
+            return Ok(COMMON_LICENSES[i].to_string());
+        }
+        match spdx::normalize(&answer) {
+            Ok(expr) => return Ok(expr),
+            Err(e) => eprintln!("  {}", e),
+        }
+    }
+}
+
+/// Quote `arg` for a POSIX shell when it contains anything beyond safe characters.
+pub fn shell_quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./:@+=,".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// The non-interactive command equivalent to the wizard's answers.
+pub fn equivalent_command(flags: &[(&str, &str)]) -> String {
+    let mut cmd = String::from("baro publish");
+    for (flag, value) in flags {
+        cmd.push_str(&format!(" --{} {}", flag, shell_quote(value)));
+    }
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn choices_by_number_or_name() {
+        let options = ["developer-tools", "productivity"];
+        assert_eq!(parse_choice("2", &options), Some(1));
+        assert_eq!(parse_choice(" Productivity ", &options), Some(1));
+        assert_eq!(parse_choice("0", &options), None);
+        assert_eq!(parse_choice("3", &options), None);
+        assert_eq!(parse_choice("games", &options), None);
+    }
+
+    #[test]
+    fn equivalent_command_is_shell_safe() {
+        assert_eq!(
+            equivalent_command(&[
+                ("version", "1.0.0"),
+                ("description", "It's a tool"),
+                ("license", "Apache-2.0 OR MIT"),
+            ]),
+            r"baro publish --version 1.0.0 --description 'It'\''s a tool' --license 'Apache-2.0 OR MIT'"
+        );
+        assert_eq!(shell_quote(""), "''");
+    }
+}
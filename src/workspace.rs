@@ -0,0 +1,120 @@
+use anyhow::{Context, Result};
+use std::path::{Component, Path, PathBuf};
+
+const WORKSPACE_FILE: &str = ".baro/workspace.json";
+
+/// `.baro/workspace.json`: product directories published together by
+/// `baro publish --workspace`, each with its own version.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Workspace {
+    pub members: Vec<WorkspaceMember>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WorkspaceMember {
+    /// Directory relative to the workspace root
+    pub path: String,
+    /// Version to publish for this member
+    pub version: String,
+    /// Category slug (default: `--category`, or the existing product's)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+impl WorkspaceMember {
+    pub fn dir(&self, root: &Path) -> PathBuf {
+        root.join(&self.path)
+    }
+}
+
+/// Load and validate the workspace file in `root`.
+pub fn load(root: &Path) -> Result<Workspace> {
+    let path = root.join(WORKSPACE_FILE);
+    let data = std::fs::read_to_string(&path)
+        .with_context(|| format!("No workspace file at {}", path.display()))?;
+    let workspace: Workspace = serde_json::from_str(&data)
+        .with_context(|| format!("Invalid {}", WORKSPACE_FILE))?;
+    validate(root, &workspace)?;
+    Ok(workspace)
+}
+
+fn validate(root: &Path, workspace: &Workspace) -> Result<()> {
+    if workspace.members.is_empty() {
+        return Err(anyhow::anyhow!("{} lists no members", WORKSPACE_FILE));
+    }
+    let mut problems = Vec::new();
+    for (i, m) in workspace.members.iter().enumerate() {
+        let rel = Path::new(&m.path);
+        let escapes = rel.is_absolute()
+            || rel.components().any(|c| matches!(c, Component::ParentDir | Component::Prefix(_)));
+        if m.path.trim().is_empty() || escapes {
+            problems.push(format!("member '{}' must be a path inside the workspace", m.path));
+        } else if !m.dir(root).is_dir() {
+            problems.push(format!("member '{}' is not a directory", m.path));
+        }
+        if m.version.trim().is_empty() {
+            problems.push(format!("member '{}' has no version", m.path));
+        }
+        if workspace.members[..i].iter().any(|other| other.path == m.path) {
+            problems.push(format!("member '{}' is listed twice", m.path));
+        }
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Invalid {}:\n  {}", WORKSPACE_FILE, problems.join("\n  ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn write_workspace(root: &Path, json: &str) {
+        fs::create_dir_all(root.join(".baro")).unwrap();
+        fs::write(root.join(WORKSPACE_FILE), json).unwrap();
+    }
+
+    #[test]
+    fn loads_members_with_versions() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("packages/a")).unwrap();
+        fs::create_dir_all(dir.path().join("packages/b")).unwrap();
+        write_workspace(
+            dir.path(),
+            r#"{"members": [
+                {"path": "packages/a", "version": "1.2.0"},
+                {"path": "packages/b", "version": "0.1.0", "category": "productivity"}
+            ]}"#,
+        );
+        let ws = load(dir.path()).unwrap();
+        assert_eq!(ws.members.len(), 2);
+        assert_eq!(ws.members[1].category.as_deref(), Some("productivity"));
+        assert_eq!(ws.members[0].dir(dir.path()), dir.path().join("packages/a"));
+    }
+
+    #[test]
+    fn rejects_bad_members() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("a")).unwrap();
+        write_workspace(
+            dir.path(),
+            r#"{"members": [
+                {"path": "a", "version": "1.0.0"},
+                {"path": "a", "version": "1.0.0"},
+                {"path": "../outside", "version": "1.0.0"},
+                {"path": "missing", "version": ""}
+            ]}"#,
+        );
+        let err = load(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("listed twice"));
+        assert!(err.contains("inside the workspace"));
+        assert!(err.contains("not a directory"));
+        assert!(err.contains("has no version"));
+
+        write_workspace(dir.path(), r#"{"members": []}"#);
+        assert!(load(dir.path()).is_err());
+    }
+}